@@ -27,6 +27,8 @@ pub const LOW_RISK_POINTS: u32 = 10;     // very sad success
 pub const TUKTUK_PROGRAM_ID: Pubkey = pubkey!("tuktukUrfhXT6ZT77QTU8RQtvgL967uRuVagWF57zVA");
 pub const CRON_PROGRAM_ID: Pubkey = pubkey!("cronAjRZnJn3MTP3B9kE62NWDrjSuAPVXf9c4hu4grM");
 pub const CRANK_REWARD: u64 = 100_000; // 0.0001 SOL per crank
+pub const FEE_VAULT_LOW_BALANCE: u64 = 100 * CRANK_REWARD; // warn when the fee vault can pay for less than 100 cranks
+pub const QUEUE_AUTHORITY_SEED: &[u8] = b"queue_authority"; // tuktuk custom signer seed, used as signer_seeds when queueing crank tasks
 
 
 // PDA seeds
@@ -37,7 +39,15 @@ pub const GUILD_SEED: &[u8] = b"guild";
 pub const ROUND_SEED: &[u8] = b"round";
 pub const REWARD_POOL_SEED: &[u8] = b"reward_pool";
 pub const TASK_QUEUE_SEED: &[u8] = b"task_queue";
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+pub const SEASON_SEED: &[u8] = b"season";
+pub const SEASON_SUMMARY_SEED: &[u8] = b"season_summary";
+pub const GUILD_TREASURY_SEED: &[u8] = b"guild_treasury";
 
 // guild constants
 pub const MAX_GUILDS: u8 = 3;
 pub const GUILD_NAMES: [&str; 3] = ["Storm Runners", "Sand Walkers", "Void Seekers"];
+
+// season constants
+pub const MAX_SEASON_GUILDS: usize = 10; // leaderboard size
+pub const SEASON_PRIZE_SPLIT_BPS: [u16; 3] = [5000, 3000, 2000]; // 1st 50%, 2nd 30%, 3rd 20%
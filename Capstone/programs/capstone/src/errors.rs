@@ -57,4 +57,40 @@ pub enum ErrorCode {
     RewardsNotDistributed,
     #[msg("Rewards already claimed")]
     RewardsAlreadyClaimed,
+
+    // admin errors
+    #[msg("Game is paused")]
+    GamePaused,
+    #[msg("No pending authority to accept")]
+    NoPendingAuthority,
+    #[msg("Game state is not in the old layout")]
+    InvalidGameState,
+
+    // tuktuk crank errors
+    #[msg("Task queue not set")]
+    TaskQueueNotSet,
+    #[msg("Invalid task queue")]
+    InvalidTaskQueue,
+    #[msg("Crank must be invoked by the game's tuktuk queue authority")]
+    InvalidCrankSigner,
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Task queue is not active")]
+    TaskQueueInactive,
+    #[msg("Queue name too long")]
+    QueueNameTooLong,
+
+    // season errors
+    #[msg("A season is already active")]
+    SeasonAlreadyActive,
+    #[msg("No active season")]
+    SeasonNotActive,
+    #[msg("Season has not ended yet")]
+    SeasonNotEnded,
+    #[msg("Invalid season")]
+    InvalidSeason,
+    #[msg("Invalid season time window")]
+    InvalidSeasonTime,
+    #[msg("Invalid guild treasury account")]
+    InvalidGuildTreasury,
 }
\ No newline at end of file
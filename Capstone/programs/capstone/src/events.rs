@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+// fee vault events so the crank operator / discord bot knows when to top up
+
+#[event]
+pub struct FeeVaultFunded {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct FeeVaultLowBalance {
+    pub balance: u64,
+    pub threshold: u64,
+}
+
+#[event]
+pub struct CrankRewardPaid {
+    pub task_queue: Pubkey,
+    pub amount: u64,
+    pub fee_vault_balance: u64,
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::{GlobalGameState, LegacyGlobalGameState};
+use crate::errors::ErrorCode;
+
+/*
+move a game deployed before the pause/authority transfer/tuktuk/season fields onto the current layout
+
+GlobalGameState grew from 113 to 253 bytes, so an old account can't deserialize as GlobalGameState
+and every instruction touching it fails until it's migrated. run once right after upgrading the program:
+the authority pays the extra rent, the account grows and the new fields get initialize's defaults
+(not paused, no guardian, no task queue - call set_task_queue afterwards - and no season yet)
+*/
+
+#[derive(Accounts)]
+pub struct MigrateGameState<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: game state in the old layout, so it's loaded by hand. seeds and owner are checked here, discriminator, size and authority in the handler
+    #[account(
+        mut,
+        seeds = [b"global_game_state"],
+        bump,
+        owner = crate::ID,
+    )]
+    pub global_game_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_game_state(ctx: Context<MigrateGameState>) -> Result<()> {
+    let global_game_state_info = ctx.accounts.global_game_state.to_account_info();
+
+    let legacy = {
+        let data = global_game_state_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == *GlobalGameState::DISCRIMINATOR,
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        // anything else is already in the current layout
+        require!(data.len() == 8 + LegacyGlobalGameState::INIT_SPACE, ErrorCode::InvalidGameState);
+        LegacyGlobalGameState::deserialize(&mut &data[8..])?
+    };
+    require_keys_eq!(legacy.authority, ctx.accounts.authority.key(), ErrorCode::Unauthorized);
+
+    let global_game_state = GlobalGameState {
+        authority: legacy.authority,
+        next_expedition_id: legacy.next_expedition_id,
+        next_expedition_time: legacy.next_expedition_time,
+        expedition_interval: legacy.expedition_interval,
+        base_reward_per_expedition: legacy.base_reward_per_expedition,
+        scrap_mint: legacy.scrap_mint,
+        total_rewards_distributed: legacy.total_rewards_distributed,
+        pending_authority: None,
+        guardian: None,
+        paused: false,
+        task_queue: Pubkey::default(),
+        queue_authority: Pubkey::default(),
+        current_season_id: 0,
+        season_active: false,
+        bump: legacy.bump,
+    };
+
+    // top up to the new rent exempt minimum, then grow the account and write the new layout over it
+    let space = 8 + GlobalGameState::INIT_SPACE;
+    let rent = Rent::get()?.minimum_balance(space);
+    let shortfall = rent.saturating_sub(global_game_state_info.lamports());
+    if shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: global_game_state_info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    global_game_state_info.resize(space)?;
+    let mut data = global_game_state_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data[..];
+    global_game_state.try_serialize(&mut writer)?;
+
+    msg!("Game state migrated to {} bytes", space);
+
+    Ok(())
+}
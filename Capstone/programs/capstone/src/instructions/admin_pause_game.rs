@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::GlobalGameState;
+use crate::errors::ErrorCode;
+
+/*
+emergency stop for the whole game
+
+- authority can pause and unpause
+- guardian (optional) can only pause, so it can live on a hotter key / bot without being able to restart a broken game
+- every user and crank instruction checks global_game_state.paused
+*/
+
+#[derive(Accounts)]
+pub struct PauseGame<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_game_state"],
+        bump = global_game_state.bump,
+        constraint = global_game_state.authority == signer.key()
+            || global_game_state.guardian == Some(signer.key()) @ ErrorCode::Unauthorized
+    )]
+    pub global_game_state: Account<'info, GlobalGameState>,
+}
+
+#[derive(Accounts)]
+pub struct AdminGameSettings<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_game_state"],
+        bump = global_game_state.bump,
+        constraint = global_game_state.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub global_game_state: Account<'info, GlobalGameState>,
+}
+
+pub fn pause_game(ctx: Context<PauseGame>) -> Result<()> {
+    ctx.accounts.global_game_state.paused = true;
+
+    msg!("Game paused by {}", ctx.accounts.signer.key());
+
+    Ok(())
+}
+
+// guardian can't get in here, only the authority can restart the game
+pub fn unpause_game(ctx: Context<AdminGameSettings>) -> Result<()> {
+    ctx.accounts.global_game_state.paused = false;
+
+    msg!("Game unpaused by {}", ctx.accounts.authority.key());
+
+    Ok(())
+}
+
+pub fn set_guardian(ctx: Context<AdminGameSettings>, guardian: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.global_game_state.guardian = guardian;
+
+    match guardian {
+        Some(key) => msg!("Guardian set to {}", key),
+        None => msg!("Guardian removed"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{GlobalGameState, GuildTreasury};
+use crate::constants::GUILD_TREASURY_SEED;
+use crate::errors::ErrorCode;
+
+/*
+guilds are discord servers, they have no wallet of their own
+
+1. authority registers a guild treasury pda + its SCRAP ata and names the guild leader
+2. finalize_season pays season prizes into that ata
+3. the leader pulls them out with withdraw_guild_treasury
+running it again just changes the leader
+*/
+
+#[derive(Accounts)]
+#[instruction(guild_id: u64)]
+pub struct SetGuildLeader<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_game_state"],
+        bump = global_game_state.bump,
+        constraint = global_game_state.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub global_game_state: Account<'info, GlobalGameState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + GuildTreasury::INIT_SPACE,
+        seeds = [GUILD_TREASURY_SEED, guild_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub guild_treasury: Account<'info, GuildTreasury>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = scrap_mint,
+        associated_token::authority = guild_treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub guild_treasury_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = scrap_mint.key() == global_game_state.scrap_mint @ ErrorCode::InvalidTokenMint,
+        mint::token_program = token_program,
+    )]
+    pub scrap_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_guild_leader(ctx: Context<SetGuildLeader>, guild_id: u64, leader: Pubkey) -> Result<()> {
+    let guild_treasury = &mut ctx.accounts.guild_treasury;
+    guild_treasury.guild_id = guild_id;
+    guild_treasury.leader = leader;
+    guild_treasury.bump = ctx.bumps.guild_treasury;
+
+    msg!("Guild {} treasury {} now led by {}", guild_id, guild_treasury.key(), leader);
+
+    Ok(())
+}
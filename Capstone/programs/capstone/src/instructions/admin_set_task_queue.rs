@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use crate::instructions::AdminGameSettings;
+use crate::utils::queue_authority_key;
+
+/*
+link the game to its tuktuk task queue
+
+the queue authority is tuktuk's custom signer for this queue, every crank instruction
+requires it as a signer so nobody can crank the game outside of the task queue
+crank tasks have to be queued with signer_seeds = [[QUEUE_AUTHORITY_SEED]]
+*/
+
+pub fn set_task_queue(ctx: Context<AdminGameSettings>, task_queue: Pubkey) -> Result<()> {
+    let global_game_state = &mut ctx.accounts.global_game_state;
+
+    global_game_state.task_queue = task_queue;
+    global_game_state.queue_authority = queue_authority_key(&task_queue);
+
+    msg!("Task queue set to {}", task_queue);
+    msg!("Crank queue authority: {}", global_game_state.queue_authority);
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{GlobalGameState, RewardPool, Season, SeasonGuildScore};
+use crate::constants::{SEASON_SEED, MAX_SEASON_GUILDS};
+use crate::utils::validate_reward_pool_balance;
+use crate::errors::ErrorCode;
+
+/*
+seasons sit on top of expeditions
+
+1. authority starts a season with a time window and a SCRAP prize pool
+2. the prize pool is reserved on the RewardPool so expedition claims can't spend it
+3. every distribute_rewards crank inside the window adds the settled guild scores to the leaderboard
+4. finalize_season crank pays the top guilds and archives a SeasonSummary
+*/
+
+#[derive(Accounts)]
+#[instruction(season_id: u64)]
+pub struct StartSeason<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_game_state"],
+        bump = global_game_state.bump,
+        constraint = global_game_state.authority == authority.key() @ ErrorCode::Unauthorized,
+        constraint = !global_game_state.season_active @ ErrorCode::SeasonAlreadyActive
+    )]
+    pub global_game_state: Account<'info, GlobalGameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Season::INIT_SPACE,
+        seeds = [SEASON_SEED, season_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub season: Account<'info, Season>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_pool"],
+        bump = reward_pool.bump,
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    #[account(
+        associated_token::mint = scrap_mint,
+        associated_token::authority = reward_pool,
+        associated_token::token_program = token_program,
+    )]
+    pub reward_pool_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = scrap_mint.key() == global_game_state.scrap_mint @ ErrorCode::InvalidTokenMint,
+        mint::token_program = token_program,
+    )]
+    pub scrap_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn start_season(
+    ctx: Context<StartSeason>,
+    season_id: u64,
+    start_time: i64,
+    end_time: i64,
+    prize_pool: u64,
+) -> Result<()> {
+    let global_game_state = &mut ctx.accounts.global_game_state;
+
+    // season ids go up by one, same idea as expedition ids
+    let expected_season_id = global_game_state.current_season_id
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if season_id != expected_season_id {
+        msg!("Season ID mismatch: provided {}, expected {}", season_id, expected_season_id);
+        return Err(error!(ErrorCode::InvalidSeason));
+    }
+
+    require!(end_time > start_time, ErrorCode::InvalidSeasonTime);
+    require!(end_time > Clock::get()?.unix_timestamp, ErrorCode::InvalidSeasonTime);
+
+    // reserve the prize pool out of whatever isn't already reserved
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    let unreserved = ctx.accounts.reward_pool_ata.amount.saturating_sub(reward_pool.season_reserve);
+    validate_reward_pool_balance(unreserved, prize_pool)?;
+
+    reward_pool.season_reserve = reward_pool.season_reserve
+        .checked_add(prize_pool)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let season = &mut ctx.accounts.season;
+    season.id = season_id;
+    season.start_time = start_time;
+    season.end_time = end_time;
+    season.prize_pool = prize_pool;
+    season.expeditions_counted = 0;
+    season.leaderboard = [SeasonGuildScore::default(); MAX_SEASON_GUILDS];
+    season.guild_count = 0;
+    season.bump = ctx.bumps.season;
+
+    global_game_state.current_season_id = season_id;
+    global_game_state.season_active = true;
+
+    msg!("Season {} started: {} -> {}, prize pool {} SCRAP lamports",
+         season_id, start_time, end_time, prize_pool);
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalGameState, TaskQueueConfig};
+use crate::constants::TASK_QUEUE_SEED;
+use crate::utils::queue_authority_key;
+use crate::errors::ErrorCode;
+
+/*
+TaskQueueConfig is the one onchain place to check scheduler health
+
+- initialize it right after creating the tuktuk task queue, this also links the queue to the game
+- is_active = false stops new expeditions from being scheduled, running ones can still finish
+- every crank writes last_execution + current_expedition_id and clears retry_count
+- when a crank step fails the operator records a retry, hitting max_retries deactivates the queue
+*/
+
+#[derive(Accounts)]
+pub struct InitializeTaskQueueConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_game_state"],
+        bump = global_game_state.bump,
+        constraint = global_game_state.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub global_game_state: Account<'info, GlobalGameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TaskQueueConfig::SPACE,
+        seeds = [TASK_QUEUE_SEED],
+        bump
+    )]
+    pub task_queue_config: Account<'info, TaskQueueConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageTaskQueueConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_game_state"],
+        bump = global_game_state.bump,
+        constraint = global_game_state.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub global_game_state: Account<'info, GlobalGameState>,
+
+    #[account(
+        mut,
+        seeds = [TASK_QUEUE_SEED],
+        bump = task_queue_config.bump,
+    )]
+    pub task_queue_config: Account<'info, TaskQueueConfig>,
+}
+
+pub fn initialize_task_queue_config(
+    ctx: Context<InitializeTaskQueueConfig>,
+    task_queue: Pubkey,
+    queue_name: String,
+    max_retries: Option<u8>,
+) -> Result<()> {
+    require!(queue_name.len() <= TaskQueueConfig::MAX_QUEUE_NAME_LEN, ErrorCode::QueueNameTooLong);
+
+    let global_game_state = &mut ctx.accounts.global_game_state;
+    global_game_state.task_queue = task_queue;
+    global_game_state.queue_authority = queue_authority_key(&task_queue);
+
+    let task_queue_config = &mut ctx.accounts.task_queue_config;
+    task_queue_config.authority = ctx.accounts.authority.key();
+    task_queue_config.queue_name = queue_name;
+    task_queue_config.current_expedition_id = 0;
+    task_queue_config.last_execution = 0;
+    task_queue_config.is_active = true;
+    task_queue_config.retry_count = 0;
+    task_queue_config.max_retries = max_retries.unwrap_or(TaskQueueConfig::DEFAULT_MAX_RETRIES);
+    task_queue_config.bump = ctx.bumps.task_queue_config;
+
+    msg!("Task queue config initialized for queue {} ({})",
+         task_queue_config.queue_name,
+         task_queue);
+
+    Ok(())
+}
+
+pub fn set_task_queue_active(ctx: Context<ManageTaskQueueConfig>, is_active: bool) -> Result<()> {
+    let task_queue_config = &mut ctx.accounts.task_queue_config;
+    task_queue_config.is_active = is_active;
+
+    // turning the queue back on starts fresh
+    if is_active {
+        task_queue_config.retry_count = 0;
+    }
+
+    msg!("Task queue {} is now {}",
+         task_queue_config.queue_name,
+         if is_active { "active" } else { "inactive" });
+
+    Ok(())
+}
+
+pub fn record_crank_retry(ctx: Context<ManageTaskQueueConfig>) -> Result<()> {
+    let task_queue_config = &mut ctx.accounts.task_queue_config;
+
+    require!(
+        task_queue_config.retry_count < task_queue_config.max_retries,
+        ErrorCode::MaxRetriesExceeded
+    );
+
+    task_queue_config.retry_count = task_queue_config.retry_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    msg!("Crank retry {}/{} for expedition {}",
+         task_queue_config.retry_count,
+         task_queue_config.max_retries,
+         task_queue_config.current_expedition_id);
+
+    // too many failures, stop scheduling until someone looks at it
+    if task_queue_config.retry_count >= task_queue_config.max_retries {
+        task_queue_config.is_active = false;
+        msg!("Max retries reached, task queue deactivated");
+    }
+
+    Ok(())
+}
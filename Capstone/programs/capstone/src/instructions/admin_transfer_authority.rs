@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalGameState, RewardPool};
+use crate::errors::ErrorCode;
+
+/*
+two step authority transfer so a typo'd pubkey can't brick the game
+
+1. current authority proposes a new key -> stored in pending_authority
+2. new key signs accept -> becomes authority on both GlobalGameState and RewardPool
+
+the current authority can propose again (or propose None) to cancel before it gets accepted
+*/
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_game_state"],
+        bump = global_game_state.bump,
+        constraint = global_game_state.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub global_game_state: Account<'info, GlobalGameState>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_game_state"],
+        bump = global_game_state.bump,
+        constraint = global_game_state.pending_authority.is_some() @ ErrorCode::NoPendingAuthority,
+        constraint = global_game_state.pending_authority == Some(new_authority.key()) @ ErrorCode::Unauthorized
+    )]
+    pub global_game_state: Account<'info, GlobalGameState>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_pool"],
+        bump = reward_pool.bump,
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+}
+
+pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Option<Pubkey>) -> Result<()> {
+    let global_game_state = &mut ctx.accounts.global_game_state;
+    global_game_state.pending_authority = new_authority;
+
+    match new_authority {
+        Some(key) => msg!("Authority transfer proposed: {} -> {}", global_game_state.authority, key),
+        None => msg!("Pending authority transfer cancelled"),
+    }
+
+    Ok(())
+}
+
+pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let new_authority = ctx.accounts.new_authority.key();
+    let global_game_state = &mut ctx.accounts.global_game_state;
+    let previous_authority = global_game_state.authority;
+
+    global_game_state.authority = new_authority;
+    global_game_state.pending_authority = None;
+    ctx.accounts.reward_pool.authority = new_authority;
+
+    msg!("Authority transferred: {} -> {}", previous_authority, new_authority);
+
+    Ok(())
+}
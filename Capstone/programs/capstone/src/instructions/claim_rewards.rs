@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::associated_token::{AssociatedToken};
-use crate::state::{Expedition, ExpeditionStatus, GlobalGameState, UserExpeditionParticipation, GuildPerformance, UserAccount};
+use crate::state::{Expedition, ExpeditionStatus, GlobalGameState, UserExpeditionParticipation, GuildPerformance, UserAccount, RewardPool};
+use crate::utils::validate_reward_pool_balance;
 use crate::errors::ErrorCode;
+use anchor_helpers::{amount_with_transfer_fee, transfer_tokens};
 
 #[derive(Accounts)]
 pub struct ClaimRewards<'info> {
@@ -59,38 +61,43 @@ pub struct ClaimRewards<'info> {
         payer = participant,
         associated_token::mint = scrap_mint,
         associated_token::authority = participant,
+        associated_token::token_program = token_program,
     )]
-    pub participant_token_account: Account<'info, TokenAccount>,
+    pub participant_token_account: InterfaceAccount<'info, TokenAccount>,
     
     /// The reward pool PDA that holds all SCRAP tokens
-    /// CHECK: This is a PDA that acts as the token authority
+    /// read as RewardPool so claims can't dip into the season reserve
     #[account(
         seeds = [b"reward_pool"],
-        bump,
+        bump = reward_pool_pda.bump,
     )]
-    pub reward_pool_pda: UncheckedAccount<'info>,
+    pub reward_pool_pda: Account<'info, RewardPool>,
     
     /// The reward pool's Associated Token Account
     #[account(
         mut,
         associated_token::mint = scrap_mint,
         associated_token::authority = reward_pool_pda,
+        associated_token::token_program = token_program,
     )]
-    pub reward_pool_ata: Account<'info, TokenAccount>,
+    pub reward_pool_ata: InterfaceAccount<'info, TokenAccount>,
     
     /// The SCRAP token mint
     #[account(
         seeds = [b"global_game_state"],
         bump,
+        constraint = !global_game_state.paused @ ErrorCode::GamePaused
     )]
     pub global_game_state: Account<'info, GlobalGameState>,
     
     #[account(
-        constraint = scrap_mint.key() == global_game_state.scrap_mint @ ErrorCode::InvalidTokenMint
+        constraint = scrap_mint.key() == global_game_state.scrap_mint @ ErrorCode::InvalidTokenMint,
+        mint::token_program = token_program,
     )]
-    pub scrap_mint: Account<'info, Mint>,
+    pub scrap_mint: InterfaceAccount<'info, Mint>,
     
-    pub token_program: Program<'info, Token>,
+    // classic token program or token-2022, whichever SCRAP was created with
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -172,27 +179,31 @@ pub fn handler(ctx: Context<ClaimRewards>) -> Result<()> {
     msg!("  Reward per player: {} SCRAP lamports", reward_amount);
     
     if reward_amount > 0 {
+        // if SCRAP has a transfer fee the pool sends the fee on top, so the participant still receives reward_amount.
+        // the withheld fee goes back to the reward pool through harvest_scrap_fees
+        let gross_amount = amount_with_transfer_fee(&ctx.accounts.scrap_mint, reward_amount)?;
+
+        // season prize pools are reserved, only the rest of the pool pays expedition claims
+        let unreserved = ctx.accounts.reward_pool_ata.amount
+            .saturating_sub(ctx.accounts.reward_pool_pda.season_reserve);
+        validate_reward_pool_balance(unreserved, gross_amount)?;
+
         // Transfer tokens from reward pool to participant
-        let reward_pool_bump = ctx.bumps.reward_pool_pda;
+        let reward_pool_bump = ctx.accounts.reward_pool_pda.bump;
         let seeds = &[
             b"reward_pool".as_ref(),
             &[reward_pool_bump],
         ];
-        let signer_seeds = &[&seeds[..]];
-        
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.reward_pool_ata.to_account_info(),
-            to: ctx.accounts.participant_token_account.to_account_info(),
-            authority: ctx.accounts.reward_pool_pda.to_account_info(),
-        };
-        
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
-        );
         
-        token::transfer(cpi_ctx, reward_amount)?;
+        transfer_tokens(
+            &ctx.accounts.reward_pool_ata,
+            &ctx.accounts.participant_token_account,
+            gross_amount,
+            &ctx.accounts.scrap_mint,
+            &ctx.accounts.reward_pool_pda,
+            &ctx.accounts.token_program,
+            Some(&seeds[..]),
+        )?;
         
         msg!("Successfully transferred {} SCRAP lamports to participant", reward_amount);
     }
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::constants::{FEE_VAULT_LOW_BALANCE, FEE_VAULT_SEED};
+use crate::events::{FeeVaultFunded, FeeVaultLowBalance};
+use crate::errors::ErrorCode;
+
+/*
+fee vault is a plain system account pda that holds SOL for the cranks
+
+anyone can top it up, every crank pays CRANK_REWARD out of it into the task queue
+*/
+
+#[derive(Accounts)]
+pub struct FundFeeVault<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump,
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn fund_fee_vault(ctx: Context<FundFeeVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.funder.to_account_info(),
+            to: ctx.accounts.fee_vault.to_account_info(),
+        },
+    );
+    transfer(cpi_ctx, amount)?;
+
+    let balance = ctx.accounts.fee_vault.lamports();
+
+    msg!("Fee vault funded with {} lamports, balance now {}", amount, balance);
+
+    emit!(FeeVaultFunded {
+        funder: ctx.accounts.funder.key(),
+        amount,
+        balance,
+    });
+
+    if balance < FEE_VAULT_LOW_BALANCE {
+        emit!(FeeVaultLowBalance {
+            balance,
+            threshold: FEE_VAULT_LOW_BALANCE,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{GlobalGameState, GuildTreasury};
+use crate::constants::GUILD_TREASURY_SEED;
+use crate::errors::ErrorCode;
+use anchor_helpers::transfer_tokens;
+
+/*
+guild leader moves season prizes out of the guild treasury
+
+treasury pda signs for its own ata, so prizes only leave through here
+*/
+
+#[derive(Accounts)]
+#[instruction(guild_id: u64)]
+pub struct WithdrawGuildTreasury<'info> {
+    #[account(mut)]
+    pub leader: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_game_state"],
+        bump = global_game_state.bump,
+        constraint = !global_game_state.paused @ ErrorCode::GamePaused
+    )]
+    pub global_game_state: Account<'info, GlobalGameState>,
+
+    #[account(
+        seeds = [GUILD_TREASURY_SEED, guild_id.to_le_bytes().as_ref()],
+        bump = guild_treasury.bump,
+        has_one = leader @ ErrorCode::Unauthorized
+    )]
+    pub guild_treasury: Account<'info, GuildTreasury>,
+
+    #[account(
+        mut,
+        associated_token::mint = scrap_mint,
+        associated_token::authority = guild_treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub guild_treasury_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = leader,
+        associated_token::mint = scrap_mint,
+        associated_token::authority = leader,
+        associated_token::token_program = token_program,
+    )]
+    pub leader_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = scrap_mint.key() == global_game_state.scrap_mint @ ErrorCode::InvalidTokenMint,
+        mint::token_program = token_program,
+    )]
+    pub scrap_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn withdraw_guild_treasury(ctx: Context<WithdrawGuildTreasury>, guild_id: u64, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let guild_id_bytes = guild_id.to_le_bytes();
+    let guild_treasury_seeds = &[
+        GUILD_TREASURY_SEED,
+        guild_id_bytes.as_ref(),
+        &[ctx.accounts.guild_treasury.bump],
+    ];
+
+    transfer_tokens(
+        &ctx.accounts.guild_treasury_ata,
+        &ctx.accounts.leader_token_account,
+        amount,
+        &ctx.accounts.scrap_mint,
+        &ctx.accounts.guild_treasury,
+        &ctx.accounts.token_program,
+        Some(&guild_treasury_seeds[..]),
+    )?;
+
+    msg!("Guild {} leader {} withdrew {} SCRAP lamports from the treasury",
+         guild_id, ctx.accounts.leader.key(), amount);
+
+    Ok(())
+}
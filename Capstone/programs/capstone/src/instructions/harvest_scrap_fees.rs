@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{
+    self, HarvestWithheldTokensToMint, Mint, TokenAccount, WithdrawWithheldTokensFromMint,
+};
+use crate::state::GlobalGameState;
+use crate::errors::ErrorCode;
+
+/*
+only for the token-2022 SCRAP with the transfer fee extension
+
+transfer fees get withheld on the receiving token accounts, so this is a two step sweep:
+1. harvest withheld fees from the token accounts (remaining_accounts) into the mint
+2. withdraw everything withheld on the mint into the reward pool ata
+
+permissionless, anyone (or a tuktuk task) can run it
+*/
+
+#[derive(Accounts)]
+pub struct HarvestScrapFees<'info> {
+    #[account(
+        seeds = [b"global_game_state"],
+        bump = global_game_state.bump,
+    )]
+    pub global_game_state: Account<'info, GlobalGameState>,
+
+    #[account(
+        mut,
+        constraint = scrap_mint.key() == global_game_state.scrap_mint @ ErrorCode::InvalidTokenMint,
+        mint::token_program = token_program,
+    )]
+    pub scrap_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: This is a PDA that acts as the withdraw withheld authority
+    #[account(
+        seeds = [b"reward_pool"],
+        bump,
+    )]
+    pub reward_pool_pda: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = scrap_mint,
+        associated_token::authority = reward_pool_pda,
+        associated_token::token_program = token_program,
+    )]
+    pub reward_pool_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+pub fn harvest_scrap_fees<'info>(ctx: Context<'_, '_, '_, 'info, HarvestScrapFees<'info>>) -> Result<()> {
+    // SCRAP token accounts with withheld fees are passed as remaining accounts
+    if !ctx.remaining_accounts.is_empty() {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            HarvestWithheldTokensToMint {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.scrap_mint.to_account_info(),
+            },
+        );
+        token_interface::harvest_withheld_tokens_to_mint(cpi_ctx, ctx.remaining_accounts.to_vec())?;
+
+        msg!("Harvested withheld SCRAP fees from {} token accounts", ctx.remaining_accounts.len());
+    }
+
+    let reward_pool_bump = ctx.bumps.reward_pool_pda;
+    let seeds = &[
+        b"reward_pool".as_ref(),
+        &[reward_pool_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        WithdrawWithheldTokensFromMint {
+            token_program_id: ctx.accounts.token_program.to_account_info(),
+            mint: ctx.accounts.scrap_mint.to_account_info(),
+            destination: ctx.accounts.reward_pool_ata.to_account_info(),
+            authority: ctx.accounts.reward_pool_pda.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::withdraw_withheld_tokens_from_mint(cpi_ctx)?;
+
+    ctx.accounts.reward_pool_ata.reload()?;
+    msg!("Withheld SCRAP fees sent back to the reward pool, balance now {}",
+         ctx.accounts.reward_pool_ata.amount);
+
+    Ok(())
+}
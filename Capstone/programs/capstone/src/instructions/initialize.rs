@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token},
+    token_interface::{Mint, TokenInterface},
 };
 use crate::{
     state::{reward_pool::RewardPool, global_game_state::GlobalGameState},
@@ -10,6 +10,7 @@ use crate::{
     BASE_REWARD_AMOUNT,
 };
 use super::token_operations::*;
+use anchor_helpers::{create_ata, mint_tokens};
 
 /*
 so to init the game we need these accounts:
@@ -51,12 +52,13 @@ pub struct InitializeGame<'info> {
         mint::decimals = 9,
         mint::authority = authority.key(),
         mint::freeze_authority = authority.key(),
+        mint::token_program = token_program,
         seeds = [b"scrap_mint"],
         bump
     )]
-    pub scrap_mint: Account<'info, Mint>,
+    pub scrap_mint: InterfaceAccount<'info, Mint>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 
@@ -69,34 +71,24 @@ pub struct InitializeGame<'info> {
 }
 
 pub fn initialize_game(ctx: Context<InitializeGame>) -> Result<()> {
-    // init global game state
-    let global_game_state = &mut ctx.accounts.global_game_state;
-    let clock = Clock::get()?;
-    
-    global_game_state.authority = ctx.accounts.authority.key();
-    global_game_state.next_expedition_id = clock.unix_timestamp as u64; // Initialize with timestamp for uniqueness
-    global_game_state.next_expedition_time = clock.unix_timestamp + EXPEDITION_INTERVAL;
-    global_game_state.expedition_interval = EXPEDITION_INTERVAL;
-    global_game_state.base_reward_per_expedition = BASE_REWARD_AMOUNT;
-    global_game_state.scrap_mint = ctx.accounts.scrap_mint.key();
-    global_game_state.total_rewards_distributed = 0;
-    global_game_state.bump = ctx.bumps.global_game_state;
-
-    // iinit reward pool w/ defaults
-    let reward_pool = &mut ctx.accounts.reward_pool;
-    reward_pool.authority = ctx.accounts.authority.key();
-    reward_pool.scrap_mint = ctx.accounts.scrap_mint.key();
-    reward_pool.bump = ctx.bumps.reward_pool;
+    init_game_accounts(
+        &mut ctx.accounts.global_game_state,
+        &mut ctx.accounts.reward_pool,
+        ctx.accounts.authority.key(),
+        ctx.accounts.scrap_mint.key(),
+        ctx.bumps.global_game_state,
+        ctx.bumps.reward_pool,
+    )?;
 
     //create reward_pool_ata w/ helper function
-    create_reward_pool_ata(
-        &ctx.accounts.authority.to_account_info(),
-        &ctx.accounts.reward_pool.to_account_info(),
-        &ctx.accounts.reward_pool_ata.to_account_info(),
-        &ctx.accounts.scrap_mint.to_account_info(),
-        &ctx.accounts.associated_token_program.to_account_info(),
-        &ctx.accounts.token_program.to_account_info(),
-        &ctx.accounts.system_program.to_account_info(),
+    create_ata(
+        &ctx.accounts.authority,
+        &ctx.accounts.reward_pool_ata,
+        &ctx.accounts.reward_pool,
+        &ctx.accounts.scrap_mint,
+        &ctx.accounts.token_program,
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.system_program,
     )?;
 
     // transfer auth helper function
@@ -114,13 +106,13 @@ pub fn initialize_game(ctx: Context<InitializeGame>) -> Result<()> {
     let signer_seeds = &[&reward_pool_seeds[..]];
 
     // mint all SCRAP supply to RewardPool ata
-    mint_initial_supply(
-        &ctx.accounts.scrap_mint.to_account_info(),
-        &ctx.accounts.reward_pool_ata.to_account_info(),
-        &ctx.accounts.reward_pool.to_account_info(),
-        &ctx.accounts.token_program.to_account_info(),
-        signer_seeds,
+    mint_tokens(
+        &ctx.accounts.scrap_mint,
+        &ctx.accounts.reward_pool_ata,
         INITIAL_SUPPLY,
+        &ctx.accounts.reward_pool,
+        &ctx.accounts.token_program,
+        Some(&reward_pool_seeds[..]),
     )?;
 
     // remove auth -- no touchy
@@ -133,3 +125,40 @@ pub fn initialize_game(ctx: Context<InitializeGame>) -> Result<()> {
 
     Ok(())
 }
+
+// shared between the classic and the token-2022 initialize
+pub fn init_game_accounts(
+    global_game_state: &mut GlobalGameState,
+    reward_pool: &mut RewardPool,
+    authority: Pubkey,
+    scrap_mint: Pubkey,
+    global_game_state_bump: u8,
+    reward_pool_bump: u8,
+) -> Result<()> {
+    // init global game state
+    let clock = Clock::get()?;
+    
+    global_game_state.authority = authority;
+    global_game_state.next_expedition_id = clock.unix_timestamp as u64; // Initialize with timestamp for uniqueness
+    global_game_state.next_expedition_time = clock.unix_timestamp + EXPEDITION_INTERVAL;
+    global_game_state.expedition_interval = EXPEDITION_INTERVAL;
+    global_game_state.base_reward_per_expedition = BASE_REWARD_AMOUNT;
+    global_game_state.scrap_mint = scrap_mint;
+    global_game_state.total_rewards_distributed = 0;
+    global_game_state.pending_authority = None;
+    global_game_state.guardian = None;
+    global_game_state.paused = false;
+    global_game_state.task_queue = Pubkey::default(); // set later with set_task_queue once the tuktuk queue exists
+    global_game_state.queue_authority = Pubkey::default();
+    global_game_state.current_season_id = 0;
+    global_game_state.season_active = false;
+    global_game_state.bump = global_game_state_bump;
+
+    // iinit reward pool w/ defaults
+    reward_pool.authority = authority;
+    reward_pool.scrap_mint = scrap_mint;
+    reward_pool.season_reserve = 0;
+    reward_pool.bump = reward_pool_bump;
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::Token2022,
+};
+use crate::{
+    state::{reward_pool::RewardPool, global_game_state::GlobalGameState},
+    errors::ErrorCode,
+    INITIAL_SUPPLY,
+};
+use super::token_operations::*;
+use anchor_helpers::{create_ata, is_valid_bps, mint_tokens};
+use super::initialize::init_game_accounts;
+
+/*
+same as initialize_game but SCRAP is a token-2022 mint with:
+1. metadata pointer -> points at the mint itself
+2. token metadata -> name, symbol, uri so wallets show SCRAP instead of "unknown token"
+3. (optional) transfer fee -> small cut of every secondary transfer gets withheld,
+   harvest_scrap_fees sweeps it back into the reward pool
+
+architecture is the same
+
+scrap_mint (token-2022) ====> reward_pool_ata <===== reward_pool pda
+
+reward_pool is the mint authority from the start here (it has to sign the metadata init),
+then we mint the supply and throw away the mint + freeze authority like the classic path
+*/
+
+#[derive(Accounts)]
+pub struct InitializeGameToken2022<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GlobalGameState::INIT_SPACE,
+        seeds = [b"global_game_state"],
+        bump
+    )]
+    pub global_game_state: Account<'info, GlobalGameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RewardPool::INIT_SPACE,
+        seeds = [b"reward_pool"],
+        bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    /// CHECK: created and initialized as a token-2022 mint in the instruction logic
+    #[account(
+        mut,
+        seeds = [b"scrap_mint"],
+        bump
+    )]
+    pub scrap_mint: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: This account will be initialized in the instruction logic
+    #[account(mut)]
+    pub reward_pool_ata: UncheckedAccount<'info>,
+}
+
+pub fn initialize_game_token_2022(
+    ctx: Context<InitializeGameToken2022>,
+    name: String,
+    symbol: String,
+    uri: String,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+) -> Result<()> {
+    require!(is_valid_bps(transfer_fee_basis_points), ErrorCode::InvalidAmount);
+
+    init_game_accounts(
+        &mut ctx.accounts.global_game_state,
+        &mut ctx.accounts.reward_pool,
+        ctx.accounts.authority.key(),
+        ctx.accounts.scrap_mint.key(),
+        ctx.bumps.global_game_state,
+        ctx.bumps.reward_pool,
+    )?;
+
+    let reward_pool_key = ctx.accounts.reward_pool.key();
+    let metadata_len = scrap_metadata_len(
+        &ctx.accounts.scrap_mint.key(),
+        &reward_pool_key,
+        &name,
+        &symbol,
+        &uri,
+    )?;
+
+    // 0 bps = no transfer fee extension at all
+    let transfer_fee = if transfer_fee_basis_points > 0 {
+        Some((transfer_fee_basis_points, maximum_fee))
+    } else {
+        None
+    };
+
+    let scrap_mint_seeds = &[
+        b"scrap_mint".as_ref(),
+        &[ctx.bumps.scrap_mint]
+    ];
+
+    create_scrap_mint_2022(
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.scrap_mint.to_account_info(),
+        &reward_pool_key,
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &[&scrap_mint_seeds[..]],
+        metadata_len,
+        transfer_fee,
+    )?;
+
+    let reward_pool_seeds = &[
+        b"reward_pool".as_ref(),
+        &[ctx.bumps.reward_pool]
+    ];
+    let signer_seeds = &[&reward_pool_seeds[..]];
+
+    initialize_scrap_metadata(
+        &ctx.accounts.scrap_mint.to_account_info(),
+        &ctx.accounts.reward_pool.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+        name,
+        symbol,
+        uri,
+    )?;
+
+    //create reward_pool_ata w/ helper function
+    create_ata(
+        &ctx.accounts.authority,
+        &ctx.accounts.reward_pool_ata,
+        &ctx.accounts.reward_pool,
+        &ctx.accounts.scrap_mint,
+        &ctx.accounts.token_program,
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.system_program,
+    )?;
+
+    // mint all SCRAP supply to RewardPool ata
+    mint_tokens(
+        &ctx.accounts.scrap_mint,
+        &ctx.accounts.reward_pool_ata,
+        INITIAL_SUPPLY,
+        &ctx.accounts.reward_pool,
+        &ctx.accounts.token_program,
+        Some(&reward_pool_seeds[..]),
+    )?;
+
+    // remove auth -- no touchy
+    remove_authorities(
+        &ctx.accounts.reward_pool.to_account_info(),
+        &ctx.accounts.scrap_mint.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
@@ -9,6 +9,18 @@ pub mod tuktuk_crank_process_round;
 pub mod tuktuk_crank_complete_expedition;
 pub mod tuktuk_crank_distribute_rewards;
 pub mod claim_rewards;
+pub mod admin_transfer_authority;
+pub mod admin_pause_game;
+pub mod admin_migrate_game_state;
+pub mod admin_set_task_queue;
+pub mod fund_fee_vault;
+pub mod admin_task_queue_config;
+pub mod initialize_token_2022;
+pub mod harvest_scrap_fees;
+pub mod admin_start_season;
+pub mod tuktuk_crank_finalize_season;
+pub mod admin_set_guild_leader;
+pub mod guild_withdraw_treasury;
 
 pub use initialize::*;
 pub use user_create_account::*;
@@ -19,4 +31,16 @@ pub use tuktuk_crank_start_expedition::*;
 pub use tuktuk_crank_process_round::*;
 pub use tuktuk_crank_complete_expedition::*;
 pub use tuktuk_crank_distribute_rewards::*;
-pub use claim_rewards::*;
\ No newline at end of file
+pub use claim_rewards::*;
+pub use admin_transfer_authority::*;
+pub use admin_pause_game::*;
+pub use admin_migrate_game_state::*;
+pub use admin_set_task_queue::*;
+pub use fund_fee_vault::*;
+pub use admin_task_queue_config::*;
+pub use initialize_token_2022::*;
+pub use harvest_scrap_fees::*;
+pub use admin_start_season::*;
+pub use tuktuk_crank_finalize_season::*;
+pub use admin_set_guild_leader::*;
+pub use guild_withdraw_treasury::*;
\ No newline at end of file
@@ -1,31 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token;
-
-//create ata account for reward pool
-#[inline(never)] // im trying out inline(never) here for my helper functions to optimize stack usage cause i thought i had a memory issue, but i think i dont need it?
-pub fn create_reward_pool_ata<'info>(
-    authority: &AccountInfo<'info>,
-    reward_pool: &AccountInfo<'info>,
-    reward_pool_ata: &AccountInfo<'info>,
-    scrap_mint: &AccountInfo<'info>,
-    associated_token_program: &AccountInfo<'info>,
-    token_program: &AccountInfo<'info>,
-    system_program: &AccountInfo<'info>,
-) -> Result<()> {
-    let cpi_accounts = anchor_spl::associated_token::Create {
-        payer: authority.clone(),
-        associated_token: reward_pool_ata.clone(),
-        authority: reward_pool.clone(),
-        mint: scrap_mint.clone(),
-        system_program: system_program.clone(),
-        token_program: token_program.clone(),
-    };
-    let cpi_ctx = CpiContext::new(associated_token_program.clone(), cpi_accounts);
-    anchor_spl::associated_token::create(cpi_ctx)
-}
+use anchor_spl::token_interface;
+use anchor_spl::token_interface::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_interface::spl_token_metadata_interface::state::TokenMetadata;
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
 
 // create the transfer authority to pda func
-#[inline(never)]
+#[inline(never)] // im trying out inline(never) here for my helper functions to optimize stack usage cause i thought i had a memory issue, but i think i dont need it?
 pub fn transfer_authorities_to_pda<'info>(
     authority: &AccountInfo<'info>,
     reward_pool_key: &Pubkey,
@@ -33,56 +14,33 @@ pub fn transfer_authorities_to_pda<'info>(
     token_program: &AccountInfo<'info>,
 ) -> Result<()> {
     {
-        let cpi_accounts = anchor_spl::token::SetAuthority {
+        let cpi_accounts = anchor_spl::token_interface::SetAuthority {
             current_authority: authority.clone(),
             account_or_mint: scrap_mint.clone(),
         };
         let cpi_ctx = CpiContext::new(token_program.clone(), cpi_accounts);
-        anchor_spl::token::set_authority(
+        anchor_spl::token_interface::set_authority(
             cpi_ctx,
-            token::spl_token::instruction::AuthorityType::MintTokens,
+            AuthorityType::MintTokens,
             Some(*reward_pool_key),
         )?;
     }
 
     // transfer freeze authority to reward pool pda
     {
-        let cpi_accounts = anchor_spl::token::SetAuthority {
+        let cpi_accounts = anchor_spl::token_interface::SetAuthority {
             current_authority: authority.clone(),
             account_or_mint: scrap_mint.clone(),
         };
         let cpi_ctx = CpiContext::new(token_program.clone(), cpi_accounts);
-        anchor_spl::token::set_authority(
+        anchor_spl::token_interface::set_authority(
             cpi_ctx,
-            token::spl_token::instruction::AuthorityType::FreezeAccount,
+            AuthorityType::FreezeAccount,
             Some(*reward_pool_key),
         )
     }
 }
 
-// mint whole supply to the ata controlled by reward pool pda
-#[inline(never)]
-pub fn mint_initial_supply<'info>(
-    scrap_mint: &AccountInfo<'info>,
-    reward_pool_ata: &AccountInfo<'info>,
-    reward_pool: &AccountInfo<'info>,
-    token_program: &AccountInfo<'info>,
-    signer_seeds: &[&[&[u8]]],
-    amount: u64,
-) -> Result<()> {
-    let cpi_accounts = anchor_spl::token::MintTo {
-        mint: scrap_mint.clone(),
-        to: reward_pool_ata.clone(),
-        authority: reward_pool.clone(),
-    };
-    let cpi_ctx = CpiContext::new_with_signer(
-        token_program.clone(),
-        cpi_accounts,
-        signer_seeds,
-    );
-    anchor_spl::token::mint_to(cpi_ctx, amount)
-}
-
 // remove mint and freeze auth -- no touchy
 #[inline(never)]
 pub fn remove_authorities<'info>(
@@ -92,7 +50,7 @@ pub fn remove_authorities<'info>(
     signer_seeds: &[&[&[u8]]],
 ) -> Result<()> {
     {
-        let cpi_accounts = anchor_spl::token::SetAuthority {
+        let cpi_accounts = anchor_spl::token_interface::SetAuthority {
             current_authority: reward_pool.clone(),
             account_or_mint: scrap_mint.clone(),
         };
@@ -101,14 +59,14 @@ pub fn remove_authorities<'info>(
             cpi_accounts,
             signer_seeds,
         );
-        anchor_spl::token::set_authority(
+        anchor_spl::token_interface::set_authority(
             cpi_ctx,
-            token::spl_token::instruction::AuthorityType::MintTokens,
+            AuthorityType::MintTokens,
             None,
         )?;
     }
     {
-        let cpi_accounts = anchor_spl::token::SetAuthority {
+        let cpi_accounts = anchor_spl::token_interface::SetAuthority {
             current_authority: reward_pool.clone(),
             account_or_mint: scrap_mint.clone(),
         };
@@ -117,10 +75,138 @@ pub fn remove_authorities<'info>(
             cpi_accounts,
             signer_seeds,
         );
-        anchor_spl::token::set_authority(
+        anchor_spl::token_interface::set_authority(
             cpi_ctx,
-            token::spl_token::instruction::AuthorityType::FreezeAccount,
+            AuthorityType::FreezeAccount,
             None,
         )
     }
-}
\ No newline at end of file
+}
+
+// ============= token-2022 SCRAP =============
+
+// create the scrap_mint pda as a token-2022 mint with metadata pointer (and optional transfer fee)
+// we have to do this by hand instead of anchor's init because the transfer fee extension is optional
+// extensions have to be initialized BEFORE initialize_mint2, metadata goes in AFTER
+#[inline(never)]
+pub fn create_scrap_mint_2022<'info>(
+    authority: &AccountInfo<'info>,
+    scrap_mint: &AccountInfo<'info>,
+    reward_pool_key: &Pubkey,
+    token_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    mint_signer_seeds: &[&[&[u8]]],
+    metadata_len: usize,
+    transfer_fee: Option<(u16, u64)>,
+) -> Result<()> {
+    let mut extensions = vec![ExtensionType::MetadataPointer];
+    if transfer_fee.is_some() {
+        extensions.push(ExtensionType::TransferFeeConfig);
+    }
+    let mint_len = token_interface::find_mint_account_size(Some(&extensions))?;
+
+    // pay rent for the metadata up front, token_metadata_initialize reallocs the mint but doesn't pay for it
+    let lamports = Rent::get()?.minimum_balance(mint_len + metadata_len);
+
+    anchor_lang::system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            anchor_lang::system_program::CreateAccount {
+                from: authority.clone(),
+                to: scrap_mint.clone(),
+            },
+            mint_signer_seeds,
+        ),
+        lamports,
+        mint_len as u64,
+        token_program.key,
+    )?;
+
+    // metadata lives on the mint itself
+    token_interface::metadata_pointer_initialize(
+        CpiContext::new(
+            token_program.clone(),
+            token_interface::MetadataPointerInitialize {
+                token_program_id: token_program.clone(),
+                mint: scrap_mint.clone(),
+            },
+        ),
+        Some(*reward_pool_key),
+        Some(scrap_mint.key()),
+    )?;
+
+    // withheld fees can only be pulled out by the reward pool, see harvest_scrap_fees
+    if let Some((transfer_fee_basis_points, maximum_fee)) = transfer_fee {
+        token_interface::transfer_fee_initialize(
+            CpiContext::new(
+                token_program.clone(),
+                token_interface::TransferFeeInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: scrap_mint.clone(),
+                },
+            ),
+            Some(reward_pool_key),
+            Some(reward_pool_key),
+            transfer_fee_basis_points,
+            maximum_fee,
+        )?;
+    }
+
+    // reward pool is the mint authority from the start so it can sign for the metadata
+    token_interface::initialize_mint2(
+        CpiContext::new(
+            token_program.clone(),
+            token_interface::InitializeMint2 {
+                mint: scrap_mint.clone(),
+            },
+        ),
+        9,
+        reward_pool_key,
+        Some(reward_pool_key),
+    )
+}
+
+// write name/symbol/uri into the mint so wallets don't show an unnamed token
+#[inline(never)]
+pub fn initialize_scrap_metadata<'info>(
+    scrap_mint: &AccountInfo<'info>,
+    reward_pool: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    let cpi_accounts = token_interface::TokenMetadataInitialize {
+        program_id: token_program.clone(),
+        metadata: scrap_mint.clone(),
+        update_authority: reward_pool.clone(),
+        mint_authority: reward_pool.clone(),
+        mint: scrap_mint.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.clone(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token_interface::token_metadata_initialize(cpi_ctx, name, symbol, uri)
+}
+
+// size of the TokenMetadata TLV entry we're about to write
+pub fn scrap_metadata_len(
+    scrap_mint: &Pubkey,
+    reward_pool: &Pubkey,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Result<usize> {
+    let metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(*reward_pool))?,
+        mint: *scrap_mint,
+        name: name.to_string(),
+        symbol: symbol.to_string(),
+        uri: uri.to_string(),
+        additional_metadata: vec![],
+    };
+    Ok(metadata.tlv_size_of()?)
+}
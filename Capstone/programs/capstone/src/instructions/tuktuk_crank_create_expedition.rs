@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
+use crate::constants::{FEE_VAULT_SEED, TASK_QUEUE_SEED};
+use crate::utils::pay_crank_reward;
 use anchor_lang::solana_program::instruction::{Instruction, AccountMeta};
-use crate::state::{Expedition, ExpeditionStatus, GlobalGameState};
+use crate::state::{Expedition, ExpeditionStatus, GlobalGameState, TaskQueueConfig};
 use tuktuk_program::{RunTaskReturnV0, TaskReturnV0, TransactionSourceV0, TriggerV0, compile_transaction};
 use crate::errors::ErrorCode;
 
@@ -11,6 +13,8 @@ pub struct CreateExpedition<'info> {
         mut,
         seeds = [b"global_game_state"],
         bump = global_game_state.bump,
+        constraint = !global_game_state.paused @ ErrorCode::GamePaused,
+        constraint = global_game_state.task_queue != Pubkey::default() @ ErrorCode::TaskQueueNotSet
     )]
     pub global_game_state: Account<'info, GlobalGameState>,
     
@@ -26,10 +30,46 @@ pub struct CreateExpedition<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     
+    /// tuktuk custom signer for the game's task queue, proves this crank came from our queue
+    #[account(
+        address = global_game_state.queue_authority @ ErrorCode::InvalidCrankSigner
+    )]
+    pub queue_authority: Signer<'info>,
+
+    /// CHECK: the game's tuktuk task queue, only receives CRANK_REWARD lamports from the fee vault
+    #[account(
+        mut,
+        address = global_game_state.task_queue @ ErrorCode::InvalidTaskQueue
+    )]
+    pub task_queue: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump,
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [TASK_QUEUE_SEED],
+        bump = task_queue_config.bump,
+        constraint = task_queue_config.is_active @ ErrorCode::TaskQueueInactive,
+    )]
+    pub task_queue_config: Account<'info, TaskQueueConfig>,
+
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<CreateExpedition>, expedition_id: u64) -> Result<RunTaskReturnV0> {
+    pay_crank_reward(
+        &ctx.accounts.fee_vault,
+        &ctx.accounts.task_queue.to_account_info(),
+        &ctx.accounts.system_program,
+        ctx.bumps.fee_vault,
+    )?;
+    ctx.accounts.task_queue_config.record_execution(expedition_id)?;
+    
     let global_game_state = &mut ctx.accounts.global_game_state;
     
     // the provided expedition_id matches the current next_expedition_id
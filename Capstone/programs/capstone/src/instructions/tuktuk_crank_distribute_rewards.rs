@@ -1,8 +1,8 @@
 use anchor_lang::prelude::*;
+use crate::constants::{FEE_VAULT_SEED, TASK_QUEUE_SEED, SEASON_SEED};
+use crate::utils::pay_crank_reward;
 use anchor_lang::solana_program::program_memory::sol_memcpy;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
-use anchor_spl::associated_token::AssociatedToken;
-use crate::state::{Expedition, ExpeditionStatus, GlobalGameState, UserExpeditionParticipation, ExpeditionRound, GuildPerformance};
+use crate::state::{Season, Expedition, ExpeditionStatus, GlobalGameState, UserExpeditionParticipation, ExpeditionRound, GuildPerformance, TaskQueueConfig};
 use crate::constants::{HIGH_RISK_SUCCESS_BPS, MED_RISK_SUCCESS_BPS, LOW_RISK_SUCCESS_BPS, HIGH_RISK_REWARD_BPS, MED_RISK_REWARD_BPS, LOW_RISK_REWARD_BPS};
 use crate::errors::ErrorCode;
 use tuktuk_program::{RunTaskReturnV0};
@@ -21,14 +21,64 @@ pub struct DistributeRewards<'info> {
         mut,
         seeds = [b"global_game_state"],
         bump = global_game_state.bump,
+        constraint = !global_game_state.paused @ ErrorCode::GamePaused,
+        constraint = global_game_state.task_queue != Pubkey::default() @ ErrorCode::TaskQueueNotSet
     )]
     pub global_game_state: Account<'info, GlobalGameState>,
     
+    /// tuktuk custom signer for the game's task queue, proves this crank came from our queue
+    /// the game authority can also run it by hand, tuktuk drops the ExpeditionRound and GuildPerformance
+    /// remaining accounts when it compiles the task. the authority can point the game at any queue anyway
+    #[account(
+        constraint = queue_authority.key() == global_game_state.queue_authority
+            || queue_authority.key() == global_game_state.authority @ ErrorCode::InvalidCrankSigner
+    )]
+    pub queue_authority: Signer<'info>,
+
+    /// CHECK: the game's tuktuk task queue, only receives CRANK_REWARD lamports from the fee vault
+    #[account(
+        mut,
+        address = global_game_state.task_queue @ ErrorCode::InvalidTaskQueue
+    )]
+    pub task_queue: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump,
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [TASK_QUEUE_SEED],
+        bump = task_queue_config.bump,
+    )]
+    pub task_queue_config: Account<'info, TaskQueueConfig>,
+
+    // optional, when a season is running the settled guild scores feed its leaderboard
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.id.to_le_bytes().as_ref()],
+        bump = season.bump,
+        constraint = season.id == global_game_state.current_season_id @ ErrorCode::InvalidSeason,
+        constraint = global_game_state.season_active @ ErrorCode::SeasonNotActive
+    )]
+    pub season: Option<Account<'info, Season>>,
+
     pub system_program: Program<'info, System>,
 }
 
 
 pub fn handler(ctx: Context<DistributeRewards>) -> Result<RunTaskReturnV0> {
+    pay_crank_reward(
+        &ctx.accounts.fee_vault,
+        &ctx.accounts.task_queue.to_account_info(),
+        &ctx.accounts.system_program,
+        ctx.bumps.fee_vault,
+    )?;
+    ctx.accounts.task_queue_config.record_execution(ctx.accounts.expedition.id)?;
+    
     let expedition = &mut ctx.accounts.expedition;
     
     // check if rewards have already been distributed
@@ -151,6 +201,7 @@ pub fn handler(ctx: Context<DistributeRewards>) -> Result<RunTaskReturnV0> {
     
     let guild_accounts_start = rounds_completed;
     let mut guild_performances_updated = 0;
+    let mut settled_guild_scores = Vec::new();
        
     // iterate through remaining accounts after ExpeditionRound accounts
     for account_idx in guild_accounts_start..ctx.remaining_accounts.len() {
@@ -245,12 +296,33 @@ pub fn handler(ctx: Context<DistributeRewards>) -> Result<RunTaskReturnV0> {
         let participated_bytes = [guild_rounds_participated];
         sol_memcpy(&mut data[32..33], &participated_bytes, 1);
         
+        settled_guild_scores.push((
+            guild_id,
+            GuildPerformance::score_from(guild_total_risk, guild_successful_rounds, guild_rounds_participated),
+        ));
+        
         guild_performances_updated += 1;
         msg!("Successfully updated GuildPerformance for guild {} using sol_memcpy for persistence", guild_id);
     }
     
     msg!("Updated {} GuildPerformance accounts", guild_performances_updated);
     
+    // feed the season leaderboard, only while the season window is open
+    if let Some(season) = ctx.accounts.season.as_mut() {
+        let now = Clock::get()?.unix_timestamp;
+        if season.is_live(now) {
+            for &(guild_id, score) in settled_guild_scores.iter() {
+                season.record_guild_score(guild_id, score);
+                msg!("Season {}: guild {} +{} points", season.id, guild_id, score);
+            }
+            season.expeditions_counted = season.expeditions_counted
+                .checked_add(1)
+                .ok_or(ErrorCode::MathOverflow)?;
+        } else {
+            msg!("Season {} is not live, leaderboard not updated", season.id);
+        }
+    }
+    
    
     // for E2E testing with one single player (me), simplified distribution
     let reward_per_participant = total_pot_earned
@@ -0,0 +1,220 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::constants::{FEE_VAULT_SEED, TASK_QUEUE_SEED, SEASON_SEED, SEASON_SUMMARY_SEED, GUILD_TREASURY_SEED, SEASON_PRIZE_SPLIT_BPS};
+use crate::utils::pay_crank_reward;
+use crate::state::{GlobalGameState, RewardPool, Season, SeasonSummary, SeasonGuildScore, TaskQueueConfig};
+use crate::errors::ErrorCode;
+use tuktuk_program::RunTaskReturnV0;
+use anchor_helpers::{bps_of, transfer_tokens};
+
+/*
+runs once the season window is over
+
+1. rank the leaderboard, top 3 guilds get SEASON_PRIZE_SPLIT_BPS of the prize pool
+2. guilds don't have a wallet so prizes go to the guild treasury pda's SCRAP ata
+   (registered by set_guild_leader, the leader withdraws with withdraw_guild_treasury)
+   remaining accounts = treasury atas in rank order (client reads the season and sorts the same way)
+   a guild without a registered treasury gets nothing, its share stays in the pool
+3. the reserve is released, anything not paid out (less than 3 guilds) stays in the reward pool
+4. season account is closed into the fee vault, a SeasonSummary keeps the results
+*/
+
+#[derive(Accounts)]
+pub struct FinalizeSeason<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_game_state"],
+        bump = global_game_state.bump,
+        constraint = !global_game_state.paused @ ErrorCode::GamePaused,
+        constraint = global_game_state.task_queue != Pubkey::default() @ ErrorCode::TaskQueueNotSet,
+        constraint = global_game_state.season_active @ ErrorCode::SeasonNotActive
+    )]
+    pub global_game_state: Account<'info, GlobalGameState>,
+
+    #[account(
+        mut,
+        close = fee_vault,
+        seeds = [SEASON_SEED, season.id.to_le_bytes().as_ref()],
+        bump = season.bump,
+        constraint = season.id == global_game_state.current_season_id @ ErrorCode::InvalidSeason
+    )]
+    pub season: Account<'info, Season>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SeasonSummary::INIT_SPACE,
+        seeds = [SEASON_SUMMARY_SEED, season.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub season_summary: Account<'info, SeasonSummary>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_pool"],
+        bump = reward_pool.bump,
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    #[account(
+        mut,
+        associated_token::mint = scrap_mint,
+        associated_token::authority = reward_pool,
+        associated_token::token_program = token_program,
+    )]
+    pub reward_pool_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = scrap_mint.key() == global_game_state.scrap_mint @ ErrorCode::InvalidTokenMint,
+        mint::token_program = token_program,
+    )]
+    pub scrap_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// tuktuk custom signer for the game's task queue, proves this crank came from our queue
+    #[account(
+        address = global_game_state.queue_authority @ ErrorCode::InvalidCrankSigner
+    )]
+    pub queue_authority: Signer<'info>,
+
+    /// CHECK: the game's tuktuk task queue, only receives CRANK_REWARD lamports from the fee vault
+    #[account(
+        mut,
+        address = global_game_state.task_queue @ ErrorCode::InvalidTaskQueue
+    )]
+    pub task_queue: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump,
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [TASK_QUEUE_SEED],
+        bump = task_queue_config.bump,
+    )]
+    pub task_queue_config: Account<'info, TaskQueueConfig>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, FinalizeSeason<'info>>) -> Result<RunTaskReturnV0> {
+    pay_crank_reward(
+        &ctx.accounts.fee_vault,
+        &ctx.accounts.task_queue.to_account_info(),
+        &ctx.accounts.system_program,
+        ctx.bumps.fee_vault,
+    )?;
+    // seasons aren't tied to an expedition, keep whatever expedition the queue was on
+    let current_expedition_id = ctx.accounts.task_queue_config.current_expedition_id;
+    ctx.accounts.task_queue_config.record_execution(current_expedition_id)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let season = &ctx.accounts.season;
+    require!(now >= season.end_time, ErrorCode::SeasonNotEnded);
+
+    let ranked = season.ranked();
+    let winner_count = ranked.len().min(SEASON_PRIZE_SPLIT_BPS.len());
+
+    msg!("Finalizing season {}: {} guilds on the leaderboard, {} expeditions counted",
+         season.id, ranked.len(), season.expeditions_counted);
+
+    if ctx.remaining_accounts.len() < winner_count {
+        msg!("ERROR: Expected {} guild treasury accounts, got {}",
+             winner_count, ctx.remaining_accounts.len());
+        return Err(ErrorCode::InvalidAccountInput.into());
+    }
+
+    let reward_pool_seeds = &[
+        b"reward_pool".as_ref(),
+        &[ctx.accounts.reward_pool.bump],
+    ];
+
+    let mut winners = [SeasonGuildScore::default(); 3];
+    let mut payouts = [0u64; 3];
+    let mut total_paid = 0u64;
+
+    for (rank, entry) in ranked.iter().take(winner_count).enumerate() {
+        let treasury_ata = &ctx.remaining_accounts[rank];
+
+        // treasury ata has to belong to the guild's treasury pda, otherwise anyone could redirect prizes
+        let (guild_treasury, _) = Pubkey::find_program_address(
+            &[GUILD_TREASURY_SEED, entry.guild_id.to_le_bytes().as_ref()],
+            &crate::ID,
+        );
+        let expected_ata = get_associated_token_address_with_program_id(
+            &guild_treasury,
+            &ctx.accounts.scrap_mint.key(),
+            &ctx.accounts.token_program.key(),
+        );
+        if treasury_ata.key() != expected_ata {
+            msg!("ERROR: Treasury account for guild {} should be {}, got {}",
+                 entry.guild_id, expected_ata, treasury_ata.key());
+            return Err(ErrorCode::InvalidGuildTreasury.into());
+        }
+
+        let mut payout = bps_of(ctx.accounts.season.prize_pool, SEASON_PRIZE_SPLIT_BPS[rank])
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // no treasury ata yet means no leader was ever set, nobody could withdraw the prize
+        if treasury_ata.data_is_empty() {
+            msg!("Guild {} has no treasury yet, its share stays in the reward pool", entry.guild_id);
+            payout = 0;
+        }
+
+        if payout > 0 {
+            transfer_tokens(
+                &ctx.accounts.reward_pool_ata,
+                treasury_ata,
+                payout,
+                &ctx.accounts.scrap_mint,
+                &ctx.accounts.reward_pool,
+                &ctx.accounts.token_program,
+                Some(&reward_pool_seeds[..]),
+            )?;
+        }
+
+        msg!("  #{} guild {} with {} points -> {} SCRAP lamports",
+             rank + 1, entry.guild_id, entry.score, payout);
+
+        winners[rank] = *entry;
+        payouts[rank] = payout;
+        total_paid = total_paid.checked_add(payout).ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    // the whole prize pool comes off the reserve, unpaid shares just become normal pool balance again
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    reward_pool.season_reserve = reward_pool.season_reserve.saturating_sub(ctx.accounts.season.prize_pool);
+
+    let season = &ctx.accounts.season;
+    let season_summary = &mut ctx.accounts.season_summary;
+    season_summary.season_id = season.id;
+    season_summary.start_time = season.start_time;
+    season_summary.end_time = season.end_time;
+    season_summary.finalized_at = now;
+    season_summary.prize_pool = season.prize_pool;
+    season_summary.total_paid = total_paid;
+    season_summary.expeditions_counted = season.expeditions_counted;
+    season_summary.winners = winners;
+    season_summary.payouts = payouts;
+    season_summary.winner_count = winner_count as u8;
+    season_summary.bump = ctx.bumps.season_summary;
+
+    ctx.accounts.global_game_state.season_active = false;
+
+    msg!("Season {} finalized, paid {} of {} SCRAP lamports",
+         season.id, total_paid, season.prize_pool);
+
+    // next season is started by the authority, nothing to schedule here
+    Ok(RunTaskReturnV0 {
+        tasks: vec![],
+        accounts: vec![],
+    })
+}
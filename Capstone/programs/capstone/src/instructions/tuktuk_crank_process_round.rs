@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::state::{Expedition, ExpeditionStatus, ExpeditionRound, GlobalGameState, GuildPerformance};
+use crate::constants::{FEE_VAULT_SEED, TASK_QUEUE_SEED};
+use crate::utils::pay_crank_reward;
+use crate::state::{Expedition, ExpeditionStatus, ExpeditionRound, GlobalGameState, GuildPerformance, TaskQueueConfig};
 use crate::constants::{MAX_ROUNDS, HIGH_RISK_SUCCESS_BPS, MED_RISK_SUCCESS_BPS, LOW_RISK_SUCCESS_BPS};
 use tuktuk_program::RunTaskReturnV0;
 use crate::errors::ErrorCode;
@@ -31,18 +33,55 @@ pub struct ProcessRound<'info> {
         mut,
         seeds = [b"global_game_state"],
         bump = global_game_state.bump,
+        constraint = !global_game_state.paused @ ErrorCode::GamePaused,
+        constraint = global_game_state.task_queue != Pubkey::default() @ ErrorCode::TaskQueueNotSet
     )]
     pub global_game_state: Account<'info, GlobalGameState>,
     
     #[account(mut)]
     pub payer: Signer<'info>,
     
+    /// tuktuk custom signer for the game's task queue, proves this crank came from our queue
+    #[account(
+        address = global_game_state.queue_authority @ ErrorCode::InvalidCrankSigner
+    )]
+    pub queue_authority: Signer<'info>,
+
+    /// CHECK: the game's tuktuk task queue, only receives CRANK_REWARD lamports from the fee vault
+    #[account(
+        mut,
+        address = global_game_state.task_queue @ ErrorCode::InvalidTaskQueue
+    )]
+    pub task_queue: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump,
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [TASK_QUEUE_SEED],
+        bump = task_queue_config.bump,
+    )]
+    pub task_queue_config: Account<'info, TaskQueueConfig>,
+
     pub system_program: Program<'info, System>,
 }
 
 // GuildPerformance accounts are passed as remaining_accounts to determine the risk level chosen by the guild majority
 
 pub fn handler(ctx: Context<ProcessRound>) -> Result<RunTaskReturnV0> {
+    pay_crank_reward(
+        &ctx.accounts.fee_vault,
+        &ctx.accounts.task_queue.to_account_info(),
+        &ctx.accounts.system_program,
+        ctx.bumps.fee_vault,
+    )?;
+    ctx.accounts.task_queue_config.record_execution(ctx.accounts.expedition.id)?;
+    
     let current_time = Clock::get()?.unix_timestamp;
     let clock = Clock::get()?;
     let expedition_id = ctx.accounts.expedition.id;
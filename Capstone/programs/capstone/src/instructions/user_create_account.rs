@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use solana_sysvar::{clock::Clock, Sysvar};
 
 use crate::user_account::UserAccount;
+use crate::state::GlobalGameState;
 use crate::errors::ErrorCode;
 
 #[derive(Accounts)]
@@ -17,7 +18,14 @@ pub struct CreateUserAccount<'info> {
         bump
     )]
     pub user_account: Account<'info, UserAccount>,
-    
+
+    #[account(
+        seeds = [b"global_game_state"],
+        bump = global_game_state.bump,
+        constraint = !global_game_state.paused @ ErrorCode::GamePaused
+    )]
+    pub global_game_state: Account<'info, GlobalGameState>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{UserAccount, Expedition, ExpeditionStatus, GuildPerformance};
+use crate::state::{UserAccount, Expedition, GlobalGameState, ExpeditionStatus, GuildPerformance};
 use crate::constants::{USER_SEED, EXPEDITION_SEED, GUILD_SEED};
 use crate::errors::ErrorCode;
 
@@ -37,6 +37,13 @@ pub struct JoinExpedition<'info> {
     )]
     pub guild_performance: Account<'info, GuildPerformance>,
 
+    #[account(
+        seeds = [b"global_game_state"],
+        bump = global_game_state.bump,
+        constraint = !global_game_state.paused @ ErrorCode::GamePaused
+    )]
+    pub global_game_state: Account<'info, GlobalGameState>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{UserAccount, Expedition, ExpeditionStatus, GuildVote, GuildPerformance, UserExpeditionParticipation};
+use crate::state::{UserAccount, Expedition, GlobalGameState, ExpeditionStatus, GuildVote, GuildPerformance, UserExpeditionParticipation};
 use crate::constants::{USER_SEED, EXPEDITION_SEED, GUILD_SEED};
 use crate::errors::ErrorCode;
 
@@ -66,6 +66,13 @@ pub struct SubmitVote<'info> {
     )]
     pub user_participation: Account<'info, UserExpeditionParticipation>,
 
+    #[account(
+        seeds = [b"global_game_state"],
+        bump = global_game_state.bump,
+        constraint = !global_game_state.paused @ ErrorCode::GamePaused
+    )]
+    pub global_game_state: Account<'info, GlobalGameState>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -7,6 +7,7 @@ use tuktuk_program;
 // Import all modules
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod state;
 pub mod instructions;
 pub mod utils;
@@ -30,6 +31,18 @@ pub mod wasteland_runners {
     pub fn initialize_game(ctx: Context<InitializeGame>) -> Result<()> {
         instructions::initialize_game(ctx)
     }
+
+    // token-2022 SCRAP w/ metadata, transfer_fee_basis_points = 0 skips the transfer fee extension
+    pub fn initialize_game_token_2022(
+        ctx: Context<InitializeGameToken2022>,
+        name: String,
+        symbol: String,
+        uri: String,
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64,
+    ) -> Result<()> {
+        instructions::initialize_game_token_2022(ctx, name, symbol, uri, transfer_fee_basis_points, maximum_fee)
+    }
     
     // ============= stuff users can do  =============
     pub fn create_user_account(
@@ -75,6 +88,86 @@ pub mod wasteland_runners {
         instructions::claim_rewards::handler(ctx)
     }
 
+    pub fn harvest_scrap_fees<'info>(ctx: Context<'_, '_, '_, 'info, HarvestScrapFees<'info>>) -> Result<()> {
+        instructions::harvest_scrap_fees(ctx)
+    }
+
+    // ============= admin stuff =============
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Option<Pubkey>) -> Result<()> {
+        instructions::propose_authority(ctx, new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority(ctx)
+    }
+
+    pub fn pause_game(ctx: Context<PauseGame>) -> Result<()> {
+        instructions::pause_game(ctx)
+    }
+
+    pub fn unpause_game(ctx: Context<AdminGameSettings>) -> Result<()> {
+        instructions::unpause_game(ctx)
+    }
+
+    pub fn set_guardian(ctx: Context<AdminGameSettings>, guardian: Option<Pubkey>) -> Result<()> {
+        instructions::set_guardian(ctx, guardian)
+    }
+
+    pub fn set_task_queue(ctx: Context<AdminGameSettings>, task_queue: Pubkey) -> Result<()> {
+        instructions::set_task_queue(ctx, task_queue)
+    }
+
+    // one time upgrade of a game state created before the pause, authority transfer, tuktuk and season fields
+    pub fn migrate_game_state(ctx: Context<MigrateGameState>) -> Result<()> {
+        instructions::migrate_game_state(ctx)
+    }
+
+    // ============= tuktuk task queue config =============
+    pub fn initialize_task_queue_config(
+        ctx: Context<InitializeTaskQueueConfig>,
+        task_queue: Pubkey,
+        queue_name: String,
+        max_retries: Option<u8>,
+    ) -> Result<()> {
+        instructions::initialize_task_queue_config(ctx, task_queue, queue_name, max_retries)
+    }
+
+    pub fn set_task_queue_active(ctx: Context<ManageTaskQueueConfig>, is_active: bool) -> Result<()> {
+        instructions::set_task_queue_active(ctx, is_active)
+    }
+
+    pub fn record_crank_retry(ctx: Context<ManageTaskQueueConfig>) -> Result<()> {
+        instructions::record_crank_retry(ctx)
+    }
+
+    // ============= crank fee vault =============
+    pub fn fund_fee_vault(ctx: Context<FundFeeVault>, amount: u64) -> Result<()> {
+        instructions::fund_fee_vault(ctx, amount)
+    }
+
+    // ============= seasons =============
+    pub fn start_season(
+        ctx: Context<StartSeason>,
+        season_id: u64,
+        start_time: i64,
+        end_time: i64,
+        prize_pool: u64,
+    ) -> Result<()> {
+        instructions::start_season(ctx, season_id, start_time, end_time, prize_pool)
+    }
+
+    pub fn tuktuk_crank_finalize_season<'info>(ctx: Context<'_, '_, '_, 'info, FinalizeSeason<'info>>) -> Result<tuktuk_program::RunTaskReturnV0> {
+        instructions::tuktuk_crank_finalize_season::handler(ctx)
+    }
+
+    pub fn set_guild_leader(ctx: Context<SetGuildLeader>, guild_id: u64, leader: Pubkey) -> Result<()> {
+        instructions::set_guild_leader(ctx, guild_id, leader)
+    }
+
+    pub fn withdraw_guild_treasury(ctx: Context<WithdrawGuildTreasury>, guild_id: u64, amount: u64) -> Result<()> {
+        instructions::withdraw_guild_treasury(ctx, guild_id, amount)
+    }
+
     // ============= magicblock vrf callback =============
     
     // pub fn magicblock_vrf_callback(ctx: Context<MagicblockVrfCallback>, randomness: [u8; 32]) -> Result<()> {
@@ -11,6 +11,27 @@ pub struct GlobalGameState {
     pub base_reward_per_expedition: u64,   // 8 bytes
     pub scrap_mint: Pubkey,                // 32 bytes - SCRAP token mint address
     pub total_rewards_distributed: u64,    // 8 bytes - total rewards distributed across all expeditions
+    pub pending_authority: Option<Pubkey>, // 33 bytes - proposed new authority, must accept before it takes over
+    pub guardian: Option<Pubkey>,          // 33 bytes - optional key that can pause but never unpause
+    pub paused: bool,                      // 1 byte - emergency stop for every user and crank instruction
+    pub task_queue: Pubkey,                // 32 bytes - the game's tuktuk task queue
+    pub queue_authority: Pubkey,           // 32 bytes - tuktuk custom signer for task_queue, must sign every crank
+    pub current_season_id: u64,            // 8 bytes - latest season started, 0 = no season yet
+    pub season_active: bool,               // 1 byte - true between start_season and finalize_season
     pub bump: u8                           // 1 byte
+    // total: 245 bytes + 8 discriminator = 253 bytes
+}
+
+// the layout before pending_authority, guardian, paused, the task queue and seasons, only read by migrate_game_state
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct LegacyGlobalGameState {
+    pub authority: Pubkey,
+    pub next_expedition_id: u64,
+    pub next_expedition_time: i64,
+    pub expedition_interval: i64,
+    pub base_reward_per_expedition: u64,
+    pub scrap_mint: Pubkey,
+    pub total_rewards_distributed: u64,
+    pub bump: u8
     // total: 105 bytes + 8 discriminator = 113 bytes
-}
\ No newline at end of file
+}
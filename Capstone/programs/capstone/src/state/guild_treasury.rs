@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct GuildTreasury {
+    pub guild_id: u64,                     // 8 bytes - discord server id
+    pub leader: Pubkey,                    // 32 bytes - the only wallet that can withdraw the guild's season prizes
+    pub bump: u8                           // 1 byte
+    // Total: 41 bytes + 8 discriminator = 49 bytes
+}
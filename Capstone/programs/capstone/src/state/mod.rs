@@ -7,6 +7,9 @@ pub mod expedition_round;
 pub mod guild_vote;
 pub mod task_queue_config;
 pub mod user_expedition_participation;
+pub mod season;
+pub mod season_summary;
+pub mod guild_treasury;
 
 pub use user_account::*;
 pub use expedition::*;
@@ -16,4 +19,7 @@ pub use guild_performance::*;
 pub use expedition_round::*;
 pub use guild_vote::*;
 pub use user_expedition_participation::*;
-pub use task_queue_config::*;
\ No newline at end of file
+pub use task_queue_config::*;
+pub use season::*;
+pub use season_summary::*;
+pub use guild_treasury::*;
\ No newline at end of file
@@ -6,5 +6,6 @@ use anchor_lang::prelude::*;
 pub struct RewardPool {
     pub authority: Pubkey,
     pub scrap_mint: Pubkey,
+    pub season_reserve: u64, // SCRAP set aside for season prize pools, claims can't touch it
     pub bump: u8
 }
\ No newline at end of file
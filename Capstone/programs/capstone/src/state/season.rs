@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_SEASON_GUILDS;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct SeasonGuildScore {
+    pub guild_id: u64,                     // 8 bytes - discord server id
+    pub score: u64,                        // 8 bytes - sum of settled guild scores this season
+    // Total: 16 bytes
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Season {
+    pub id: u64,                           // 8 bytes - season number, starts at 1
+    pub start_time: i64,                   // 8 bytes - unix timestamp
+    pub end_time: i64,                     // 8 bytes - unix timestamp, finalize_season can run after this
+    pub prize_pool: u64,                   // 8 bytes - SCRAP reserved from the RewardPool for this season
+    pub expeditions_counted: u64,          // 8 bytes - expeditions that fed the leaderboard
+    pub leaderboard: [SeasonGuildScore; MAX_SEASON_GUILDS], // 160 bytes - MAX_SEASON_GUILDS entries, unsorted
+    pub guild_count: u8,                   // 1 byte - used entries in leaderboard
+    pub bump: u8                           // 1 byte
+    // Total: 202 bytes + 8 discriminator = 210 bytes
+}
+
+impl Season {
+    pub fn is_live(&self, now: i64) -> bool {
+        now >= self.start_time && now < self.end_time
+    }
+
+    /// Add a settled expedition score to a guild's running season total
+    /// When the leaderboard is full a new guild only gets in by beating the lowest entry
+    pub fn record_guild_score(&mut self, guild_id: u64, score: u64) {
+        let used = self.guild_count as usize;
+
+        if let Some(entry) = self.leaderboard[..used].iter_mut().find(|e| e.guild_id == guild_id) {
+            entry.score = entry.score.saturating_add(score);
+            return;
+        }
+
+        if used < MAX_SEASON_GUILDS {
+            self.leaderboard[used] = SeasonGuildScore { guild_id, score };
+            self.guild_count += 1;
+            return;
+        }
+
+        if let Some(lowest) = self.leaderboard.iter_mut().min_by_key(|e| e.score) {
+            if score > lowest.score {
+                msg!("Guild {} replaces guild {} on the season leaderboard", guild_id, lowest.guild_id);
+                *lowest = SeasonGuildScore { guild_id, score };
+            }
+        }
+    }
+
+    /// Leaderboard entries sorted by score, highest first
+    pub fn ranked(&self) -> Vec<SeasonGuildScore> {
+        let mut ranked = self.leaderboard[..self.guild_count as usize].to_vec();
+        ranked.sort_by(|a, b| b.score.cmp(&a.score));
+        ranked
+    }
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use crate::state::SeasonGuildScore;
+
+#[account]
+#[derive(InitSpace)]
+pub struct SeasonSummary {
+    pub season_id: u64,                    // 8 bytes - links to the (closed) season
+    pub start_time: i64,                   // 8 bytes
+    pub end_time: i64,                     // 8 bytes
+    pub finalized_at: i64,                 // 8 bytes - when finalize_season ran
+    pub prize_pool: u64,                   // 8 bytes - SCRAP reserved for the season
+    pub total_paid: u64,                   // 8 bytes - SCRAP actually paid out, rest goes back to the pool
+    pub expeditions_counted: u64,          // 8 bytes
+    pub winners: [SeasonGuildScore; 3],    // 48 bytes - top guilds in rank order
+    pub payouts: [u64; 3],                 // 24 bytes - SCRAP paid to each winner's treasury
+    pub winner_count: u8,                  // 1 byte
+    pub bump: u8                           // 1 byte
+    // Total: 130 bytes + 8 discriminator = 138 bytes
+}
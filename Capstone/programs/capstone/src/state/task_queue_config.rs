@@ -31,9 +31,11 @@ pub struct TaskQueueConfig {
 }
 
 impl TaskQueueConfig {
+    pub const MAX_QUEUE_NAME_LEN: usize = 32;
+
     pub const SPACE: usize = 8 + // discriminator
         32 + // authority
-        32 + // queue_name (max string length)
+        4 + Self::MAX_QUEUE_NAME_LEN + // queue_name (4 byte length prefix + max string length)
         8 + // current_expedition_id
         8 + // last_execution
         1 + // is_active
@@ -43,4 +45,13 @@ impl TaskQueueConfig {
         
     pub const DEFAULT_MAX_RETRIES: u8 = 3;
     pub const QUEUE_NAME: &'static str = "expedition_queue";
+
+    /// Called by every crank that runs successfully
+    /// A successful step clears any retries recorded for the previous failed attempt
+    pub fn record_execution(&mut self, expedition_id: u64) -> Result<()> {
+        self.last_execution = Clock::get()?.unix_timestamp;
+        self.current_expedition_id = expedition_id;
+        self.retry_count = 0;
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::constants::{CRANK_REWARD, FEE_VAULT_LOW_BALANCE, FEE_VAULT_SEED, QUEUE_AUTHORITY_SEED, TUKTUK_PROGRAM_ID};
+use crate::events::{CrankRewardPaid, FeeVaultLowBalance};
+
+/// Derive the tuktuk custom signer for the game's task queue
+/// Tuktuk signs for ["custom", task_queue, signer_seeds...] when it runs a task that was
+/// queued with those signer_seeds, so only our task queue can produce this signer
+pub fn queue_authority_key(task_queue: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"custom", task_queue.as_ref(), QUEUE_AUTHORITY_SEED],
+        &TUKTUK_PROGRAM_ID,
+    ).0
+}
+
+/// Refill the task queue with CRANK_REWARD from the fee vault after a crank runs
+/// The task queue balance is what tuktuk uses to pay crank turners and fund follow-up tasks
+/// Never fails the crank - if the vault is dry we skip the payment and emit a low balance event
+pub fn pay_crank_reward<'info>(
+    fee_vault: &SystemAccount<'info>,
+    task_queue: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    fee_vault_bump: u8,
+) -> Result<()> {
+    // keep the vault rent exempt, system transfers fail otherwise
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    let available = fee_vault.lamports().saturating_sub(rent_exempt_minimum);
+
+    if available < CRANK_REWARD {
+        msg!("Fee vault has {} lamports available, skipping crank reward", available);
+        emit!(FeeVaultLowBalance {
+            balance: fee_vault.lamports(),
+            threshold: FEE_VAULT_LOW_BALANCE,
+        });
+        return Ok(());
+    }
+
+    let seeds = &[FEE_VAULT_SEED, &[fee_vault_bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        Transfer {
+            from: fee_vault.to_account_info(),
+            to: task_queue.clone(),
+        },
+        signer_seeds,
+    );
+    transfer(cpi_ctx, CRANK_REWARD)?;
+
+    let balance = fee_vault.lamports();
+    emit!(CrankRewardPaid {
+        task_queue: task_queue.key(),
+        amount: CRANK_REWARD,
+        fee_vault_balance: balance,
+    });
+
+    if balance < FEE_VAULT_LOW_BALANCE {
+        emit!(FeeVaultLowBalance {
+            balance,
+            threshold: FEE_VAULT_LOW_BALANCE,
+        });
+    }
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_helpers::transfer_tokens;
 use crate::state::{Expedition, UserExpeditionParticipation};
 use crate::errors::ErrorCode;
 
@@ -9,11 +10,12 @@ pub fn distribute_rewards_equally<'info>(
     expedition: &Expedition,
     participant_count: u64,
     reward_amount: u64,
-    from_account: &Account<'info, TokenAccount>,
+    from_account: &InterfaceAccount<'info, TokenAccount>,
     from_authority: &AccountInfo<'info>,
-    token_program: &Program<'info, Token>,
+    scrap_mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
     participants: &[UserExpeditionParticipation],
-    participant_token_accounts: &[Account<'info, TokenAccount>],
+    participant_token_accounts: &[InterfaceAccount<'info, TokenAccount>],
 ) -> Result<u64> {
     // Validate we have participants
     if participant_count == 0 {
@@ -48,15 +50,15 @@ pub fn distribute_rewards_equally<'info>(
         let participant_token_account = &participant_token_accounts[i];
         
         // Transfer tokens to participant
-        let cpi_accounts = Transfer {
-            from: from_account.to_account_info(),
-            to: participant_token_account.to_account_info(),
-            authority: from_authority.to_account_info(),
-        };
-        
-        let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
-        
-        token::transfer(cpi_ctx, reward_per_participant)?;
+        transfer_tokens(
+            from_account,
+            participant_token_account,
+            reward_per_participant,
+            scrap_mint,
+            from_authority,
+            token_program,
+            None,
+        )?;
         
         msg!(
             "Distributed {} tokens to participant {}",
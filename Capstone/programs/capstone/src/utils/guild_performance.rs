@@ -5,18 +5,28 @@ use crate::constants::{HIGH_RISK_POINTS, MEDIUM_RISK_POINTS, LOW_RISK_POINTS};
 impl GuildPerformance {
     /// Calculates the guild's score based on their performance
     pub fn calculate_score(&self) -> u64 {
+        Self::score_from(
+            self.total_risk_points,
+            self.successful_rounds,
+            self.total_rounds_participated,
+        )
+    }
+
+    /// Same formula as calculate_score, for when we only have the raw stats
+    /// (distribute_rewards writes GuildPerformance bytes directly)
+    pub fn score_from(total_risk_points: u32, successful_rounds: u8, total_rounds_participated: u8) -> u64 {
         // Base score from risk points (total risk taken)
-        let base_score = self.total_risk_points as u64;
+        let base_score = total_risk_points as u64;
         
         // Success rate bonus (successful rounds / total rounds)
-        let success_rate = if self.total_rounds_participated > 0 {
-            (self.successful_rounds as u64 * 100) / self.total_rounds_participated as u64
+        let success_rate = if total_rounds_participated > 0 {
+            (successful_rounds as u64 * 100) / total_rounds_participated as u64
         } else {
             0
         };
         
         // Participation bonus (10 points per round participated)
-        let participation_bonus = self.total_rounds_participated as u64 * 10;
+        let participation_bonus = total_rounds_participated as u64 * 10;
         
         // Apply success rate multiplier
         let multiplier = 100 + success_rate; // 100% base + success rate bonus
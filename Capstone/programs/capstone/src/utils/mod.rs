@@ -3,7 +3,9 @@
 pub mod guild_performance;
 pub mod calculate_guild_score;
 pub mod distribute_rewards;
+pub mod crank_rewards;
 
 pub use guild_performance::*;
 pub use calculate_guild_score::*;
-pub use distribute_rewards::*;
\ No newline at end of file
+pub use distribute_rewards::*;
+pub use crank_rewards::*;
\ No newline at end of file
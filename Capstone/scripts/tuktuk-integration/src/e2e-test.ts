@@ -38,6 +38,11 @@ const colors = {
 const PROGRAM_ID = new PublicKey("81zfvbGkhnSgAg24xtC7f24N3TKfanNs7426RkzBUmsx");
 const TUKTUK_PROGRAM = new PublicKey("tuktukUrfhXT6ZT77QTU8RQtvgL967uRuVagWF57zVA");
 
+// Cranks only accept tuktuk's custom signer for the game's queue, ["custom", task_queue, "queue_authority"]
+// so every task is queued with this as its signer seed (see admin_set_task_queue.rs)
+const QUEUE_AUTHORITY_SEED = Buffer.from("queue_authority");
+const FEE_VAULT_FUNDING = 50_000_000; // 0.05 SOL, covers the 0.0001 SOL crank reward (CRANK_REWARD) for a full run
+
 // NOTE: VRF functionality has been mocked for testing
 // In production, this would integrate with MagicBlock VRF for secure randomness
 
@@ -57,6 +62,9 @@ const DISCRIMINATORS = {
   COMPLETE_EXPEDITION: [32, 204, 49, 108, 223, 63, 209, 10],
   DISTRIBUTE_REWARDS: [87, 211, 212, 214, 55, 202, 49, 174],
   CLAIM_REWARDS: [4, 144, 132, 71, 116, 23, 151, 80],
+  SET_TASK_QUEUE: [229, 6, 187, 41, 95, 8, 247, 1],
+  INITIALIZE_TASK_QUEUE_CONFIG: [39, 98, 191, 137, 171, 200, 55, 176],
+  FUND_FEE_VAULT: [225, 179, 89, 103, 113, 104, 241, 182],
 };
 
 // Test state
@@ -76,6 +84,9 @@ class EndToEndTest {
   private tuktukProgram?: Program<Tuktuk>;
   private taskQueue?: PublicKey;
   private gameStatePDA: PublicKey;
+  private feeVaultPDA: PublicKey;
+  private taskQueueConfigPDA: PublicKey;
+  private queueAuthorityPDA?: PublicKey;
   private expeditionId: number = 0;
   private expeditionPDA?: PublicKey;
   private userAccountPDA?: PublicKey;
@@ -117,6 +128,15 @@ class EndToEndTest {
       PROGRAM_ID
     );
 
+    [this.feeVaultPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault")],
+      PROGRAM_ID
+    );
+    [this.taskQueueConfigPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("task_queue")],
+      PROGRAM_ID
+    );
+
     // DEBUG: Log the derived PDA and program ID
     console.log(`\n🔍 DEBUG: PDA Derivation Check`);
     console.log(`  Program ID: ${PROGRAM_ID.toBase58()}`);
@@ -147,7 +167,7 @@ class EndToEndTest {
       { name: "Wait for Round 3", description: "Verify round 3 completion", status: "pending" },
       { name: "Complete Expedition", description: "Queue complete_expedition task", status: "pending" },
       { name: "Wait for Completion", description: "Verify expedition completed", status: "pending" },
-      { name: "Distribute Rewards", description: "Queue distribute_rewards task with round/guild data", status: "pending" },
+      { name: "Distribute Rewards", description: "Execute distribute_rewards directly with round/guild data", status: "pending" },
       { name: "Wait for Rewards", description: "Verify rewards distributed", status: "pending" },
       { name: "Claim Rewards", description: "Claim individual SCRAP token rewards", status: "pending" },
       { name: "Verify Token Balance", description: "Check SCRAP token balance after claiming", status: "pending" },
@@ -499,6 +519,83 @@ class EndToEndTest {
     }
   }
 
+  // queue_authority, task_queue, fee_vault, task_queue_config - every tuktuk_crank_* instruction
+  // takes these right before system_program. queueAuthority is tuktuk's custom signer unless a crank is run by hand
+  private crankAccounts(queueAuthority: PublicKey = this.queueAuthorityPDA!) {
+    return [
+      { pubkey: queueAuthority, isSigner: true, isWritable: false },
+      { pubkey: this.taskQueue!, isSigner: false, isWritable: true },
+      { pubkey: this.feeVaultPDA, isSigner: false, isWritable: true },
+      { pubkey: this.taskQueueConfigPDA, isSigner: false, isWritable: true },
+    ];
+  }
+
+  // Point the game at this run's task queue and make sure the fee vault can pay crank rewards
+  private async linkGameToTaskQueue() {
+    [this.queueAuthorityPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("custom"), this.taskQueue!.toBuffer(), QUEUE_AUTHORITY_SEED],
+      TUKTUK_PROGRAM
+    );
+    console.log(`  Crank queue authority: ${this.queueAuthorityPDA.toBase58()}`);
+
+    const tx = new Transaction();
+    const taskQueueConfig = await this.connection.getAccountInfo(this.taskQueueConfigPDA);
+
+    if (!taskQueueConfig) {
+      // first run against this deployment, the config init also links the queue
+      const name = Buffer.from(TASK_QUEUE_NAME);
+      const nameLen = Buffer.alloc(4);
+      nameLen.writeUInt32LE(name.length);
+      tx.add(new TransactionInstruction({
+        programId: PROGRAM_ID,
+        keys: [
+          { pubkey: this.wallet.publicKey, isSigner: true, isWritable: true },
+          { pubkey: this.gameStatePDA, isSigner: false, isWritable: true },
+          { pubkey: this.taskQueueConfigPDA, isSigner: false, isWritable: true },
+          { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ],
+        data: Buffer.concat([
+          Buffer.from(DISCRIMINATORS.INITIALIZE_TASK_QUEUE_CONFIG),
+          this.taskQueue!.toBuffer(),
+          nameLen,
+          name,
+          Buffer.from([0]), // max_retries: None
+        ]),
+      }));
+    } else {
+      tx.add(new TransactionInstruction({
+        programId: PROGRAM_ID,
+        keys: [
+          { pubkey: this.wallet.publicKey, isSigner: true, isWritable: false },
+          { pubkey: this.gameStatePDA, isSigner: false, isWritable: true },
+        ],
+        data: Buffer.concat([
+          Buffer.from(DISCRIMINATORS.SET_TASK_QUEUE),
+          this.taskQueue!.toBuffer(),
+        ]),
+      }));
+    }
+
+    const feeVaultBalance = await this.connection.getBalance(this.feeVaultPDA);
+    if (feeVaultBalance < FEE_VAULT_FUNDING) {
+      const amount = Buffer.alloc(8);
+      amount.writeBigUInt64LE(BigInt(FEE_VAULT_FUNDING));
+      tx.add(new TransactionInstruction({
+        programId: PROGRAM_ID,
+        keys: [
+          { pubkey: this.wallet.publicKey, isSigner: true, isWritable: true },
+          { pubkey: this.feeVaultPDA, isSigner: false, isWritable: true },
+          { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ],
+        data: Buffer.concat([Buffer.from(DISCRIMINATORS.FUND_FEE_VAULT), amount]),
+      }));
+    }
+
+    const sig = await this.provider.sendAndConfirm(tx);
+    console.log(`${colors.green}✅ Game linked to task queue${colors.reset}`);
+    console.log(`  ${colors.cyan}https://solscan.io/tx/${sig}?cluster=devnet${colors.reset}`);
+  }
+
   private async queueInstruction(
     instruction: TransactionInstruction,
     description: string
@@ -513,7 +610,7 @@ class EndToEndTest {
       }
 
       // Use the SDK's compileTransaction function - destructure the result like SDK examples do
-      const { transaction, remainingAccounts } = compileTransaction([instruction], []);
+      const { transaction, remainingAccounts } = compileTransaction([instruction], [[QUEUE_AUTHORITY_SEED]]);
       
       // Queue the task using the program instance
       const queueTaskBuilder = await queueTask(this.tuktukProgram, {
@@ -560,7 +657,7 @@ class EndToEndTest {
       }
 
       // Use the SDK's compileTransaction function
-      const { transaction, remainingAccounts } = compileTransaction([instruction], []);
+      const { transaction, remainingAccounts } = compileTransaction([instruction], [[QUEUE_AUTHORITY_SEED]]);
       
       // Combine the instruction's remaining accounts with our additional ones
       const allRemainingAccounts = [
@@ -653,7 +750,10 @@ class EndToEndTest {
         console.log(`${colors.green}✅ Queue authority added${colors.reset}`);
         await this.sleep(2000);
       }
-      
+
+      // Cranks are gated on this queue's custom signer, link it before queueing anything
+      await this.linkGameToTaskQueue();
+
       this.updateStep(0, 'success');
 
       // Step 2: Check Balance
@@ -744,13 +844,14 @@ class EndToEndTest {
       console.log('=====================================\n');
       
       // CRITICAL FIX: Accounts must be in the correct order matching the on-chain instruction!
-      // The on-chain instruction expects: authority, expedition, global_game_state, system_program
+      // The on-chain instruction expects: global_game_state, expedition, payer, crank accounts, system_program
       const createExpeditionIx = new TransactionInstruction({
         programId: PROGRAM_ID,
         keys: [
           { pubkey: this.gameStatePDA, isSigner: false, isWritable: true },        // global_game_state first
           { pubkey: this.expeditionPDA, isSigner: false, isWritable: true },       // expedition SECOND
-          { pubkey: this.wallet.publicKey, isSigner: true, isWritable: true },      // authority third
+          { pubkey: this.wallet.publicKey, isSigner: true, isWritable: true },      // payer third
+          ...this.crankAccounts(),                                                 // queue_authority, task_queue, fee_vault, task_queue_config
           { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program LAST
         ],
         data: instructionData,
@@ -845,6 +946,7 @@ class EndToEndTest {
           keys: [
             { pubkey: this.wallet.publicKey, isSigner: true, isWritable: true },
             { pubkey: this.userAccountPDA, isSigner: false, isWritable: true },
+            { pubkey: this.gameStatePDA, isSigner: false, isWritable: false },
             { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
           ],
           data: userAccountData,
@@ -895,6 +997,7 @@ class EndToEndTest {
           { pubkey: this.userAccountPDA, isSigner: false, isWritable: true },
           { pubkey: this.expeditionPDA!, isSigner: false, isWritable: true },
           { pubkey: this.guildPerformancePDA!, isSigner: false, isWritable: true },
+          { pubkey: this.gameStatePDA, isSigner: false, isWritable: false },
           { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
         ],
         data: joinData,
@@ -918,6 +1021,8 @@ class EndToEndTest {
         programId: PROGRAM_ID,
         keys: [
           { pubkey: this.expeditionPDA!, isSigner: false, isWritable: true },
+          { pubkey: this.gameStatePDA, isSigner: false, isWritable: false },
+          ...this.crankAccounts(),
           { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
         ],
         data: Buffer.from(DISCRIMINATORS.START_EXPEDITION),
@@ -1004,6 +1109,7 @@ class EndToEndTest {
             { pubkey: this.guildPerformancePDA!, isSigner: false, isWritable: true },
             { pubkey: guildVotePDA, isSigner: false, isWritable: true },
             { pubkey: userParticipationPDA, isSigner: false, isWritable: true },
+            { pubkey: this.gameStatePDA, isSigner: false, isWritable: false },
             { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
           ],
           data: voteData,
@@ -1050,6 +1156,7 @@ class EndToEndTest {
             { pubkey: roundPDA, isSigner: false, isWritable: true },
             { pubkey: this.gameStatePDA, isSigner: false, isWritable: true },
             { pubkey: this.wallet.publicKey, isSigner: true, isWritable: true }, // payer account (FIXED)
+            ...this.crankAccounts(),
             { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
           ],
           data: Buffer.from(DISCRIMINATORS.PROCESS_ROUND),
@@ -1137,6 +1244,7 @@ class EndToEndTest {
         keys: [
           { pubkey: this.expeditionPDA!, isSigner: false, isWritable: true },
           { pubkey: this.gameStatePDA, isSigner: false, isWritable: true },
+          ...this.crankAccounts(),
           { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
         ],
         data: Buffer.from(DISCRIMINATORS.COMPLETE_EXPEDITION),
@@ -1332,23 +1440,28 @@ class EndToEndTest {
       ];
       console.log(`  Total remaining accounts: ${remainingAccounts.length} (${expeditionRoundPDAs.length} ExpeditionRounds + 1 GuildPerformance)`);
 
-      // CRITICAL FIX: Keep main accounts separate from remaining accounts
-      // The tuktuk SDK needs remaining accounts passed separately, not in the instruction keys
+      // CRITICAL FIX: Execute distribute_rewards directly instead of through task queue
+      // The TukTuk task queue system loses remaining accounts during serialization
+      // Without tuktuk there's no queue custom signer, so the wallet signs as queue_authority -
+      // distribute_rewards accepts the game authority in its place (this wallet initialized the game)
       const distributeRewardsIx = new TransactionInstruction({
         programId: PROGRAM_ID,
         keys: [
           { pubkey: this.expeditionPDA!, isSigner: false, isWritable: true },
           { pubkey: this.gameStatePDA, isSigner: false, isWritable: true },
+          ...this.crankAccounts(this.wallet.publicKey),
+          { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },              // season (None, no season running)
           { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-          // DO NOT add remaining accounts here - they must be passed separately
+          // Add ExpeditionRound AND GuildPerformance accounts as remaining accounts
+          ...remainingAccounts,
         ],
         data: Buffer.from(DISCRIMINATORS.DISTRIBUTE_REWARDS),
       });
 
-      console.log(`\n${colors.cyan}🔍 DEBUG: Queueing distribute_rewards with:${colors.reset}`);
-      console.log(`  Main accounts: 3 (expedition, game_state, system_program)`);
-      console.log(`  Remaining accounts to pass separately: ${remainingAccounts.length}`);
-      console.log(`  Main instruction accounts: ${distributeRewardsIx.keys.length}`);
+      console.log(`\n${colors.cyan}🔍 DEBUG: Executing distribute_rewards with:${colors.reset}`);
+      console.log(`  Main accounts: 8 (expedition, game_state, crank accounts, season, system_program)`);
+      console.log(`  Remaining accounts: ${remainingAccounts.length}`);
+      console.log(`  Total instruction accounts: ${distributeRewardsIx.keys.length}`);
       remainingAccounts.forEach((acc, idx) => {
         const accountType = idx < expeditionRoundPDAs.length ? 'ExpeditionRound' : 'GuildPerformance';
         console.log(`    Remaining ${idx} (${accountType}): ${acc.pubkey.toBase58()} (${acc.isWritable ? 'writable' : 'readonly'})`);
@@ -1368,36 +1481,17 @@ class EndToEndTest {
         console.log(`  ${colors.red}Account not found!${colors.reset}`);
       }
 
-      // CRITICAL FIX: Execute distribute_rewards directly instead of through task queue
-      // The TukTuk task queue system loses remaining accounts during serialization
       console.log(`\n${colors.cyan}🔧 EXECUTING DIRECTLY: TukTuk cannot handle remaining accounts${colors.reset}`);
       console.log(`  Bypassing task queue to ensure ExpeditionRound accounts are passed correctly`);
-      
-      // Create the complete instruction with all accounts
-      const completeDistributeRewardsIx = new TransactionInstruction({
-        programId: PROGRAM_ID,
-        keys: [
-          { pubkey: this.expeditionPDA!, isSigner: false, isWritable: true },
-          { pubkey: this.gameStatePDA, isSigner: false, isWritable: true },
-          { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-          // Add ExpeditionRound AND GuildPerformance accounts as remaining accounts
-          ...remainingAccounts.map(acc => ({
-            pubkey: acc.pubkey,
-            isSigner: acc.isSigner,
-            isWritable: acc.isWritable  // Use the actual writable flag from remainingAccounts
-          }))
-        ],
-        data: Buffer.from(DISCRIMINATORS.DISTRIBUTE_REWARDS),
-      });
 
       // Execute directly using Anchor provider
-      const { blockhash } = await this.connection.getLatestBlockhash();
+      const { blockhash, lastValidBlockHeight } = await this.connection.getLatestBlockhash();
       const transaction = new Transaction({
         feePayer: this.wallet.publicKey,
         blockhash,
-        lastValidBlockHeight: (await this.connection.getLatestBlockhash()).lastValidBlockHeight
+        lastValidBlockHeight
       });
-      transaction.add(completeDistributeRewardsIx);
+      transaction.add(distributeRewardsIx);
       transaction.sign(this.wallet);
 
       const distributeSig = await this.connection.sendRawTransaction(transaction.serialize(), {
@@ -1408,12 +1502,12 @@ class EndToEndTest {
       console.log(`  ${colors.green}Reward distribution executed directly: ${distributeSig}${colors.reset}`);
       console.log(`  ${colors.cyan}https://solscan.io/tx/${distributeSig}?cluster=devnet${colors.reset}`);
       this.updateStep(distributeRewardsIndex, 'success', distributeSig);
-      
+
       // Wait for confirmation
       await this.connection.confirmTransaction({
         signature: distributeSig,
         blockhash,
-        lastValidBlockHeight: (await this.connection.getLatestBlockhash()).lastValidBlockHeight
+        lastValidBlockHeight
       });
       console.log(`  ${colors.green}✅ Transaction confirmed${colors.reset}`);
 
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::token::create_ata_if_needed;
+
+// Loading typed accounts out of remaining_accounts, with the checks Anchor's constraints would do.
+// Each caller passes its own error so a bad account fails with that program's error code.
+
+// Load a program account from remaining_accounts, it has to be the one at `expected`
+pub fn load_account<'info, T>(
+    account_info: &'info AccountInfo<'info>,
+    expected: &Pubkey,
+    error: impl Into<Error>,
+) -> Result<Account<'info, T>>
+where
+    T: AccountSerialize + AccountDeserialize + Owner + Clone,
+{
+    if account_info.key != expected {
+        return Err(error.into());
+    }
+
+    Account::try_from(account_info)
+}
+
+// Load a mint from remaining_accounts
+// It has to be the expected mint and belong to the token program the instruction was called with
+pub fn load_mint<'info>(
+    mint_info: &'info AccountInfo<'info>,
+    expected: &Pubkey,
+    token_program: &Pubkey,
+    error: impl Into<Error>,
+) -> Result<InterfaceAccount<'info, Mint>> {
+    if mint_info.key != expected || mint_info.owner != token_program {
+        return Err(error.into());
+    }
+
+    InterfaceAccount::try_from(mint_info)
+}
+
+// Load authority's ATA for mint from remaining_accounts, creating it first if it doesn't exist yet
+// (the remaining_accounts version of init_if_needed, payer covers the rent)
+#[allow(clippy::too_many_arguments)]
+pub fn init_ata_if_needed<'info>(
+    token_account_info: &'info AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    authority: &impl ToAccountInfo<'info>,
+    payer: &impl ToAccountInfo<'info>,
+    token_program: &impl ToAccountInfo<'info>,
+    associated_token_program: &impl ToAccountInfo<'info>,
+    system_program: &impl ToAccountInfo<'info>,
+    error: impl Into<Error>,
+) -> Result<InterfaceAccount<'info, TokenAccount>> {
+    let authority = authority.to_account_info();
+    let token_program = token_program.to_account_info();
+
+    let expected = get_associated_token_address_with_program_id(authority.key, &mint.key(), token_program.key);
+    if *token_account_info.key != expected {
+        return Err(error.into());
+    }
+
+    if token_account_info.data_is_empty() {
+        create_ata_if_needed(
+            payer,
+            token_account_info,
+            &authority,
+            mint,
+            &token_program,
+            associated_token_program,
+            system_program,
+        )?;
+    }
+
+    InterfaceAccount::try_from(token_account_info)
+}
@@ -0,0 +1,12 @@
+// Helpers the vault, escrow, capstone, marketplace and staking programs all need, so each of them
+// stops carrying its own copy of the same token CPIs.
+// Everything works for both the SPL Token and the Token-2022 program, including the transfer fee
+// bookkeeping Token-2022 needs (grossing up deposits, harvesting withheld fees before a close).
+
+pub mod accounts;
+pub mod math;
+pub mod token;
+
+pub use accounts::*;
+pub use math::*;
+pub use token::*;
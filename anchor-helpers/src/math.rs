@@ -0,0 +1,14 @@
+// 10_000 basis points = 100%
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+// bps of amount, rounded down. The product goes through u128 so it can't overflow,
+// None only when bps is above 100% and the result doesn't fit in a u64
+pub fn bps_of(amount: u64, bps: u16) -> Option<u64> {
+    let result = (amount as u128) * (bps as u128) / (BPS_DENOMINATOR as u128);
+    u64::try_from(result).ok()
+}
+
+// fees, royalties and splits can't take more than the whole amount
+pub fn is_valid_bps(bps: u16) -> bool {
+    bps <= BPS_DENOMINATOR
+}
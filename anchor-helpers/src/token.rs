@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{create, create_idempotent, Create};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint as MintState,
+};
+use anchor_spl::token_interface::{
+    close_account, harvest_withheld_tokens_to_mint, mint_to, transfer_checked, CloseAccount,
+    HarvestWithheldTokensToMint, Mint, MintTo, TransferChecked,
+};
+
+// Every helper takes the seeds of the PDA that signs as owning_pda_seeds, or None when
+// the authority is a real signer. Only one PDA ever signs these, so that's all the seeds there are.
+fn cpi_context<'a, 'b, 'c, 'info, T: ToAccountMetas + ToAccountInfos<'info>>(
+    program: AccountInfo<'info>,
+    accounts: T,
+    signer_seeds: &'a Option<[&'b [&'c [u8]]; 1]>,
+) -> CpiContext<'a, 'b, 'c, 'info, T> {
+    match signer_seeds {
+        Some(seeds) => CpiContext::new_with_signer(program, accounts, seeds),
+        None => CpiContext::new(program, accounts),
+    }
+}
+
+// Transfer tokens from one account to another with transfer_checked
+// If transferring from a token account owned by a PDA, owning_pda_seeds must be provided.
+pub fn transfer_tokens<'info>(
+    from: &impl ToAccountInfo<'info>,
+    to: &impl ToAccountInfo<'info>,
+    amount: u64,
+    mint: &InterfaceAccount<'info, Mint>,
+    authority: &impl ToAccountInfo<'info>,
+    token_program: &impl ToAccountInfo<'info>,
+    owning_pda_seeds: Option<&[&[u8]]>,
+) -> Result<()> {
+    let transfer_accounts = TransferChecked {
+        from: from.to_account_info(),
+        mint: mint.to_account_info(),
+        to: to.to_account_info(),
+        authority: authority.to_account_info(),
+    };
+    let signer_seeds = owning_pda_seeds.map(|seeds| [seeds]);
+
+    transfer_checked(
+        cpi_context(token_program.to_account_info(), transfer_accounts, &signer_seeds),
+        amount,
+        mint.decimals,
+    )
+}
+
+// Close a token account and send the rent to the specified destination
+// If the token account is owned by a PDA, owning_pda_seeds must be provided.
+pub fn close_token_account<'info>(
+    token_account: &impl ToAccountInfo<'info>,
+    destination: &impl ToAccountInfo<'info>,
+    authority: &impl ToAccountInfo<'info>,
+    token_program: &impl ToAccountInfo<'info>,
+    owning_pda_seeds: Option<&[&[u8]]>,
+) -> Result<()> {
+    let close_accounts = CloseAccount {
+        account: token_account.to_account_info(),
+        destination: destination.to_account_info(),
+        authority: authority.to_account_info(),
+    };
+    let signer_seeds = owning_pda_seeds.map(|seeds| [seeds]);
+
+    close_account(cpi_context(token_program.to_account_info(), close_accounts, &signer_seeds))
+}
+
+// Mint new tokens into a token account
+// If the mint authority is a PDA, owning_pda_seeds must be provided.
+pub fn mint_tokens<'info>(
+    mint: &impl ToAccountInfo<'info>,
+    to: &impl ToAccountInfo<'info>,
+    amount: u64,
+    authority: &impl ToAccountInfo<'info>,
+    token_program: &impl ToAccountInfo<'info>,
+    owning_pda_seeds: Option<&[&[u8]]>,
+) -> Result<()> {
+    let mint_accounts = MintTo {
+        mint: mint.to_account_info(),
+        to: to.to_account_info(),
+        authority: authority.to_account_info(),
+    };
+    let signer_seeds = owning_pda_seeds.map(|seeds| [seeds]);
+
+    mint_to(cpi_context(token_program.to_account_info(), mint_accounts, &signer_seeds), amount)
+}
+
+fn ata_accounts<'info>(
+    payer: &impl ToAccountInfo<'info>,
+    associated_token: &impl ToAccountInfo<'info>,
+    authority: &impl ToAccountInfo<'info>,
+    mint: &impl ToAccountInfo<'info>,
+    token_program: &impl ToAccountInfo<'info>,
+    system_program: &impl ToAccountInfo<'info>,
+) -> Create<'info> {
+    Create {
+        payer: payer.to_account_info(),
+        associated_token: associated_token.to_account_info(),
+        authority: authority.to_account_info(),
+        mint: mint.to_account_info(),
+        system_program: system_program.to_account_info(),
+        token_program: token_program.to_account_info(),
+    }
+}
+
+// Create authority's ATA for mint, payer covers the rent. Fails if it already exists
+pub fn create_ata<'info>(
+    payer: &impl ToAccountInfo<'info>,
+    associated_token: &impl ToAccountInfo<'info>,
+    authority: &impl ToAccountInfo<'info>,
+    mint: &impl ToAccountInfo<'info>,
+    token_program: &impl ToAccountInfo<'info>,
+    associated_token_program: &impl ToAccountInfo<'info>,
+    system_program: &impl ToAccountInfo<'info>,
+) -> Result<()> {
+    let accounts = ata_accounts(payer, associated_token, authority, mint, token_program, system_program);
+    create(CpiContext::new(associated_token_program.to_account_info(), accounts))
+}
+
+// Same as create_ata, but an ATA that's already there is left alone
+pub fn create_ata_if_needed<'info>(
+    payer: &impl ToAccountInfo<'info>,
+    associated_token: &impl ToAccountInfo<'info>,
+    authority: &impl ToAccountInfo<'info>,
+    mint: &impl ToAccountInfo<'info>,
+    token_program: &impl ToAccountInfo<'info>,
+    associated_token_program: &impl ToAccountInfo<'info>,
+    system_program: &impl ToAccountInfo<'info>,
+) -> Result<()> {
+    let accounts = ata_accounts(payer, associated_token, authority, mint, token_program, system_program);
+    create_idempotent(CpiContext::new(associated_token_program.to_account_info(), accounts))
+}
+
+fn transfer_fee_config(mint: &AccountInfo) -> Result<Option<TransferFeeConfig>> {
+    let mint_data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&mint_data)?;
+    Ok(mint_state.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+// How much has to be sent so the receiver ends up with exactly net_amount
+// Classic SPL mints and Token-2022 mints without the transfer fee extension just return net_amount
+pub fn amount_with_transfer_fee(mint: &InterfaceAccount<Mint>, net_amount: u64) -> Result<u64> {
+    let Some(transfer_fee_config) = transfer_fee_config(&mint.to_account_info())? else {
+        return Ok(net_amount);
+    };
+
+    let fee = transfer_fee_config
+        .calculate_inverse_epoch_fee(Clock::get()?.epoch, net_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    net_amount
+        .checked_add(fee)
+        .ok_or(ProgramError::ArithmeticOverflow.into())
+}
+
+// Token-2022 won't close an account that still holds withheld transfer fees, and any account that
+// received a transfer fee mint does. Moves them to the mint first (anyone can harvest, no authority
+// signs), so the mint has to be writable. Mints without the transfer fee extension are left alone
+pub fn harvest_withheld_fees<'info>(
+    token_account: &impl ToAccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &impl ToAccountInfo<'info>,
+) -> Result<()> {
+    let mint = mint.to_account_info();
+    if transfer_fee_config(&mint)?.is_none() {
+        return Ok(());
+    }
+
+    let accounts = HarvestWithheldTokensToMint {
+        token_program_id: token_program.to_account_info(),
+        mint,
+    };
+    harvest_withheld_tokens_to_mint(
+        CpiContext::new(token_program.to_account_info(), accounts),
+        vec![token_account.to_account_info()],
+    )
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use anchor_helpers::{init_ata_if_needed, load_account, load_mint};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use common::{account, balance, wallet, Env, TOKEN_PROGRAMS};
+
+declare_id!("He1persTest111111111111111111111111111111111");
+
+#[account]
+#[derive(Debug)]
+pub struct Counter {
+    pub count: u64,
+}
+
+#[error_code]
+pub enum TestError {
+    #[msg("Wrong remaining account")]
+    WrongAccount,
+}
+
+fn counter(count: u64, owner: Pubkey) -> &'static AccountInfo<'static> {
+    let mut data = Counter::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&count.to_le_bytes());
+    account(Pubkey::new_unique(), 1_000_000, data, owner, false)
+}
+
+#[test]
+fn load_account_reads_the_expected_account() {
+    let info = counter(7, crate::ID);
+
+    let loaded = load_account::<Counter>(info, info.key, TestError::WrongAccount).unwrap();
+
+    assert_eq!(loaded.count, 7);
+}
+
+#[test]
+fn load_account_rejects_a_different_address() {
+    let info = counter(7, crate::ID);
+
+    let err = load_account::<Counter>(info, &Pubkey::new_unique(), TestError::WrongAccount).unwrap_err();
+
+    assert_eq!(err, TestError::WrongAccount.into());
+}
+
+#[test]
+fn load_account_rejects_an_account_owned_by_another_program() {
+    let info = counter(7, system_program::ID);
+
+    let err = load_account::<Counter>(info, info.key, TestError::WrongAccount).unwrap_err();
+
+    assert_eq!(err, ErrorCode::AccountOwnedByWrongProgram.into());
+}
+
+#[test]
+fn load_mint_reads_mints_of_either_token_program() {
+    for program in TOKEN_PROGRAMS {
+        let env = Env::new(program);
+        let mint = env.mint(wallet().key, 9);
+        let info = mint.to_account_info();
+        let info: &'static AccountInfo<'static> = Box::leak(Box::new(info));
+
+        let loaded = load_mint(info, &mint.key(), &program, TestError::WrongAccount).unwrap();
+
+        assert_eq!(loaded.decimals, 9);
+    }
+}
+
+#[test]
+fn load_mint_rejects_a_different_mint() {
+    let env = Env::new(spl_token::ID);
+    let mint = env.mint(wallet().key, 9);
+    let info: &'static AccountInfo<'static> = Box::leak(Box::new(mint.to_account_info()));
+
+    let err = load_mint(info, &Pubkey::new_unique(), &spl_token::ID, TestError::WrongAccount).unwrap_err();
+
+    assert_eq!(err, TestError::WrongAccount.into());
+}
+
+#[test]
+fn load_mint_rejects_a_mint_of_the_other_token_program() {
+    let env = Env::new(spl_token_2022::ID);
+    let mint = env.mint(wallet().key, 9);
+    let info: &'static AccountInfo<'static> = Box::leak(Box::new(mint.to_account_info()));
+
+    // a Token-2022 mint when the instruction was called with SPL Token
+    let err = load_mint(info, &mint.key(), &spl_token::ID, TestError::WrongAccount).unwrap_err();
+
+    assert_eq!(err, TestError::WrongAccount.into());
+}
+
+#[test]
+fn init_ata_if_needed_creates_a_missing_ata() {
+    for program in TOKEN_PROGRAMS {
+        let env = Env::new(program);
+        let mint = env.mint(wallet().key, 6);
+        let owner = wallet();
+        let ata = env.empty_ata(&mint, owner.key);
+
+        let loaded = init_ata_if_needed(
+            ata,
+            &mint,
+            owner,
+            env.payer,
+            env.token_program,
+            env.associated_token_program,
+            env.system_program,
+            TestError::WrongAccount,
+        )
+        .unwrap();
+
+        assert_eq!(ata.owner, &program);
+        assert_eq!(loaded.mint, mint.key());
+        assert_eq!(loaded.owner, *owner.key);
+        assert_eq!(loaded.amount, 0);
+    }
+}
+
+#[test]
+fn init_ata_if_needed_loads_an_existing_ata() {
+    for program in TOKEN_PROGRAMS {
+        let env = Env::new(program);
+        let mint = env.mint(wallet().key, 6);
+        let owner = wallet();
+        let ata = env.token_account(&mint, owner.key, 42);
+        let payer_before = env.payer.lamports();
+
+        let loaded = init_ata_if_needed(
+            ata,
+            &mint,
+            owner,
+            env.payer,
+            env.token_program,
+            env.associated_token_program,
+            env.system_program,
+            TestError::WrongAccount,
+        )
+        .unwrap();
+
+        assert_eq!(loaded.amount, 42);
+        assert_eq!(balance(ata), 42);
+        assert_eq!(env.payer.lamports(), payer_before);
+    }
+}
+
+#[test]
+fn init_ata_if_needed_rejects_someone_elses_ata() {
+    for program in TOKEN_PROGRAMS {
+        let env = Env::new(program);
+        let mint = env.mint(wallet().key, 6);
+        let owner = wallet();
+        let other_ata = env.empty_ata(&mint, wallet().key);
+
+        let err = init_ata_if_needed(
+            other_ata,
+            &mint,
+            owner,
+            env.payer,
+            env.token_program,
+            env.associated_token_program,
+            env.system_program,
+            TestError::WrongAccount,
+        )
+        .unwrap_err();
+
+        assert_eq!(err, TestError::WrongAccount.into());
+        assert!(other_ata.data_is_empty());
+    }
+}
@@ -0,0 +1,325 @@
+// The helpers only ever CPI into the token programs, the associated token program and the system
+// program, so instead of deploying a program to host them the tests call them directly and the
+// syscall stubs route every invoke_signed into those programs' native processors.
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::sync::Once;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use anchor_lang::system_program;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::associated_token::spl_associated_token_account;
+use anchor_spl::token::spl_token;
+use anchor_spl::token::spl_token::state::{Account as SplAccount, AccountState, Mint as SplMint};
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::{TransferFee, TransferFeeConfig};
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+// the program the helpers run inside of, PDA authorities are derived from it
+pub const CALLER: Pubkey = Pubkey::new_from_array([7; 32]);
+
+pub const TOKEN_PROGRAMS: [Pubkey; 2] = [spl_token::ID, spl_token_2022::ID];
+
+// system program error for an account that's already allocated or owned
+const ACCOUNT_ALREADY_IN_USE: u32 = 0;
+
+thread_local! {
+    static CALL_STACK: RefCell<Vec<Pubkey>> = RefCell::new(vec![CALLER]);
+    static RETURN_DATA: RefCell<Option<(Pubkey, Vec<u8>)>> = const { RefCell::new(None) };
+}
+
+struct Runtime;
+
+impl SyscallStubs for Runtime {
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        // PDAs can only sign for the program that's making the call
+        let caller = CALL_STACK.with(|stack| *stack.borrow().last().unwrap());
+        let pda_signers = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &caller))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+
+        let mut accounts = Vec::with_capacity(instruction.accounts.len());
+        for meta in &instruction.accounts {
+            let info = account_infos
+                .iter()
+                .find(|info| *info.key == meta.pubkey)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+            if meta.is_signer && !info.is_signer && !pda_signers.contains(info.key) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if meta.is_writable && !info.is_writable {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            let mut info = info.clone();
+            info.is_signer = meta.is_signer;
+            info.is_writable = meta.is_writable;
+            accounts.push(info);
+        }
+
+        CALL_STACK.with(|stack| stack.borrow_mut().push(instruction.program_id));
+        let result = process(&instruction.program_id, &accounts, &instruction.data);
+        CALL_STACK.with(|stack| stack.borrow_mut().pop());
+
+        result
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        0
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Clock) = Clock::default() };
+        0
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        RETURN_DATA.with(|data| data.borrow().clone())
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        let program_id = CALL_STACK.with(|stack| *stack.borrow().last().unwrap());
+        RETURN_DATA.with(|return_data| *return_data.borrow_mut() = Some((program_id, data.to_vec())));
+    }
+}
+
+fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if *program_id == system_program::ID {
+        process_system(accounts, data)
+    } else if *program_id == spl_token::ID {
+        spl_token::processor::Processor::process(program_id, accounts, data)
+    } else if *program_id == spl_token_2022::ID {
+        spl_token_2022::processor::Processor::process(program_id, accounts, data)
+    } else if *program_id == spl_associated_token_account::ID {
+        spl_associated_token_account::processor::process_instruction(program_id, accounts, data)
+    } else {
+        Err(ProgramError::IncorrectProgramId)
+    }
+}
+
+// Just the system instructions the associated token program uses to create an account
+fn process_system(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let u64_at = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+    let pubkey_at = |offset: usize| Pubkey::try_from(&data[offset..offset + 32]).unwrap();
+
+    match u32::from_le_bytes(data[..4].try_into().unwrap()) {
+        // CreateAccount { lamports, space, owner }
+        0 => {
+            if accounts[1].lamports() > 0 {
+                return Err(ProgramError::Custom(ACCOUNT_ALREADY_IN_USE));
+            }
+            move_lamports(&accounts[0], &accounts[1], u64_at(4))?;
+            allocate(&accounts[1], u64_at(12))?;
+            accounts[1].assign(&pubkey_at(20));
+            Ok(())
+        }
+        // Assign { owner }
+        1 => {
+            accounts[0].assign(&pubkey_at(4));
+            Ok(())
+        }
+        // Transfer { lamports }
+        2 => move_lamports(&accounts[0], &accounts[1], u64_at(4)),
+        // Allocate { space }
+        8 => allocate(&accounts[0], u64_at(4)),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    let remaining = from.lamports().checked_sub(lamports).ok_or(ProgramError::InsufficientFunds)?;
+    **from.try_borrow_mut_lamports()? = remaining;
+    **to.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+fn allocate(account: &AccountInfo, space: u64) -> ProgramResult {
+    if !account.data_is_empty() || *account.owner != system_program::ID {
+        return Err(ProgramError::Custom(ACCOUNT_ALREADY_IN_USE));
+    }
+    *account.try_borrow_mut_data()? = Box::leak(vec![0; space as usize].into_boxed_slice());
+    Ok(())
+}
+
+// Accounts live for the whole test run, the helpers want &'info AccountInfo<'info> like the
+// remaining_accounts they normally get
+pub fn account(
+    key: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+    owner: Pubkey,
+    is_signer: bool,
+) -> &'static AccountInfo<'static> {
+    Box::leak(Box::new(AccountInfo::new(
+        Box::leak(Box::new(key)),
+        is_signer,
+        true,
+        Box::leak(Box::new(lamports)),
+        Box::leak(data.into_boxed_slice()),
+        Box::leak(Box::new(owner)),
+        false,
+        0,
+    )))
+}
+
+fn rent_exempt(data: &[u8]) -> u64 {
+    Rent::default().minimum_balance(data.len())
+}
+
+pub struct Env {
+    pub token_program: &'static AccountInfo<'static>,
+    pub associated_token_program: &'static AccountInfo<'static>,
+    pub system_program: &'static AccountInfo<'static>,
+    pub payer: &'static AccountInfo<'static>,
+}
+
+impl Env {
+    pub fn new(token_program: Pubkey) -> Self {
+        static RUNTIME: Once = Once::new();
+        RUNTIME.call_once(|| {
+            set_syscall_stubs(Box::new(Runtime));
+        });
+
+        let program = |id: Pubkey| {
+            let mut info = account(id, 1, vec![], Pubkey::default(), false).clone();
+            info.executable = true;
+            info.is_writable = false;
+            &*Box::leak(Box::new(info))
+        };
+
+        Env {
+            token_program: program(token_program),
+            associated_token_program: program(spl_associated_token_account::ID),
+            system_program: program(system_program::ID),
+            payer: wallet(),
+        }
+    }
+
+    pub fn mint(&self, authority: &Pubkey, decimals: u8) -> InterfaceAccount<'static, Mint> {
+        let mut data = vec![0; SplMint::LEN];
+        SplMint::pack(
+            SplMint {
+                mint_authority: Some(*authority).into(),
+                decimals,
+                is_initialized: true,
+                ..SplMint::default()
+            },
+            &mut data,
+        )
+        .unwrap();
+
+        let mint = account(Pubkey::new_unique(), rent_exempt(&data), data, *self.token_program.key, false);
+        InterfaceAccount::try_from(mint).unwrap()
+    }
+
+    // a Token-2022 mint that takes fee_bps of every transfer, capped at maximum_fee
+    pub fn fee_mint(&self, authority: &Pubkey, decimals: u8, fee_bps: u16, maximum_fee: u64) -> InterfaceAccount<'static, Mint> {
+        let len = ExtensionType::try_calculate_account_len::<MintState>(&[ExtensionType::TransferFeeConfig]).unwrap();
+        let mut data = vec![0; len];
+        let mut state = StateWithExtensionsMut::<MintState>::unpack_uninitialized(&mut data).unwrap();
+
+        let transfer_fee = TransferFee {
+            epoch: 0.into(),
+            maximum_fee: maximum_fee.into(),
+            transfer_fee_basis_points: fee_bps.into(),
+        };
+        let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+        config.older_transfer_fee = transfer_fee;
+        config.newer_transfer_fee = transfer_fee;
+
+        state.base = MintState {
+            mint_authority: Some(*authority).into(),
+            decimals,
+            is_initialized: true,
+            ..MintState::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+
+        let mint = account(Pubkey::new_unique(), rent_exempt(&data), data, spl_token_2022::ID, false);
+        InterfaceAccount::try_from(mint).unwrap()
+    }
+
+    // authority's ATA for mint created through the associated token program, so it carries
+    // whatever extensions the mint requires
+    pub fn create_ata(&self, mint: &InterfaceAccount<'static, Mint>, authority: &'static AccountInfo<'static>) -> &'static AccountInfo<'static> {
+        let ata = self.empty_ata(mint, authority.key);
+        anchor_helpers::create_ata(
+            self.payer,
+            ata,
+            authority,
+            mint,
+            self.token_program,
+            self.associated_token_program,
+            self.system_program,
+        )
+        .unwrap();
+        ata
+    }
+
+    // authority's ATA for mint, already initialized with amount in it
+    pub fn token_account(
+        &self,
+        mint: &InterfaceAccount<'static, Mint>,
+        authority: &Pubkey,
+        amount: u64,
+    ) -> &'static AccountInfo<'static> {
+        let mut data = vec![0; SplAccount::LEN];
+        SplAccount::pack(
+            SplAccount {
+                mint: mint.key(),
+                owner: *authority,
+                amount,
+                state: AccountState::Initialized,
+                ..SplAccount::default()
+            },
+            &mut data,
+        )
+        .unwrap();
+
+        let key = self.ata_address(mint, authority);
+        account(key, rent_exempt(&data), data, *self.token_program.key, false)
+    }
+
+    // authority's ATA for mint before anyone created it
+    pub fn empty_ata(&self, mint: &InterfaceAccount<'static, Mint>, authority: &Pubkey) -> &'static AccountInfo<'static> {
+        account(self.ata_address(mint, authority), 0, vec![], system_program::ID, false)
+    }
+
+    pub fn ata_address(&self, mint: &InterfaceAccount<'static, Mint>, authority: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(authority, &mint.key(), self.token_program.key)
+    }
+}
+
+// a signer with some SOL, like the user calling the instruction
+pub fn wallet() -> &'static AccountInfo<'static> {
+    account(Pubkey::new_unique(), 10_000_000_000, vec![], system_program::ID, true)
+}
+
+// a PDA of CALLER that only signs when the helper gets its seeds
+pub fn pda(seed: &'static [u8]) -> (&'static AccountInfo<'static>, u8) {
+    let (key, bump) = Pubkey::find_program_address(&[seed], &CALLER);
+    (account(key, 0, vec![], system_program::ID, false), bump)
+}
+
+pub fn balance(token_account: &'static AccountInfo<'static>) -> u64 {
+    InterfaceAccount::<TokenAccount>::try_from(token_account).unwrap().amount
+}
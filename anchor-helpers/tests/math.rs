@@ -0,0 +1,29 @@
+use anchor_helpers::{bps_of, is_valid_bps, BPS_DENOMINATOR};
+
+#[test]
+fn bps_of_rounds_down() {
+    assert_eq!(bps_of(1_000_000, 250), Some(25_000)); // 2.5%
+    assert_eq!(bps_of(399, 250), Some(9)); // 9.975 -> 9
+    assert_eq!(bps_of(1_000, BPS_DENOMINATOR), Some(1_000));
+    assert_eq!(bps_of(1_000, 0), Some(0));
+}
+
+#[test]
+fn bps_of_does_not_overflow_on_large_amounts() {
+    // amount * bps is bigger than u64::MAX here, the result isn't
+    assert_eq!(bps_of(u64::MAX, 5_000), Some(u64::MAX / 2));
+    assert_eq!(bps_of(u64::MAX, BPS_DENOMINATOR), Some(u64::MAX));
+}
+
+#[test]
+fn bps_of_over_100_percent_can_overflow() {
+    assert_eq!(bps_of(100, 20_000), Some(200));
+    assert_eq!(bps_of(u64::MAX, 20_000), None);
+}
+
+#[test]
+fn valid_bps_is_at_most_100_percent() {
+    assert!(is_valid_bps(0));
+    assert!(is_valid_bps(BPS_DENOMINATOR));
+    assert!(!is_valid_bps(BPS_DENOMINATOR + 1));
+}
@@ -0,0 +1,288 @@
+mod common;
+
+use anchor_helpers::{
+    amount_with_transfer_fee, close_token_account, create_ata, create_ata_if_needed, harvest_withheld_fees,
+    mint_tokens, transfer_tokens,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token::error::TokenError;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::error::TokenError as Token2022Error;
+use anchor_spl::token_interface::TokenAccount;
+use common::{balance, pda, wallet, Env, TOKEN_PROGRAMS};
+
+fn token_error(error: TokenError) -> Error {
+    ProgramError::Custom(error as u32).into()
+}
+
+#[test]
+fn transfer_tokens_moves_tokens_between_accounts() {
+    for program in TOKEN_PROGRAMS {
+        let env = Env::new(program);
+        let owner = wallet();
+        let mint = env.mint(owner.key, 6);
+        let from = env.token_account(&mint, owner.key, 100);
+        let to = env.token_account(&mint, wallet().key, 0);
+
+        transfer_tokens(from, to, 40, &mint, owner, env.token_program, None).unwrap();
+
+        assert_eq!(balance(from), 60);
+        assert_eq!(balance(to), 40);
+    }
+}
+
+#[test]
+fn transfer_tokens_fails_without_enough_tokens() {
+    for program in TOKEN_PROGRAMS {
+        let env = Env::new(program);
+        let owner = wallet();
+        let mint = env.mint(owner.key, 6);
+        let from = env.token_account(&mint, owner.key, 100);
+        let to = env.token_account(&mint, wallet().key, 0);
+
+        let err = transfer_tokens(from, to, 101, &mint, owner, env.token_program, None).unwrap_err();
+
+        assert_eq!(err, token_error(TokenError::InsufficientFunds));
+        assert_eq!(balance(from), 100);
+    }
+}
+
+#[test]
+fn transfer_tokens_signs_for_a_pda_owner() {
+    for program in TOKEN_PROGRAMS {
+        let env = Env::new(program);
+        let (vault, bump) = pda(b"vault");
+        let mint = env.mint(wallet().key, 6);
+        let from = env.token_account(&mint, vault.key, 100);
+        let to = env.token_account(&mint, wallet().key, 0);
+
+        // without the seeds the vault never signs
+        let err = transfer_tokens(from, to, 25, &mint, vault, env.token_program, None).unwrap_err();
+        assert_eq!(err, ProgramError::MissingRequiredSignature.into());
+
+        let seeds: &[&[u8]] = &[b"vault", &[bump]];
+        transfer_tokens(from, to, 25, &mint, vault, env.token_program, Some(seeds)).unwrap();
+
+        assert_eq!(balance(from), 75);
+        assert_eq!(balance(to), 25);
+    }
+}
+
+#[test]
+fn close_token_account_sends_the_rent_to_the_destination() {
+    for program in TOKEN_PROGRAMS {
+        let env = Env::new(program);
+        let (vault, bump) = pda(b"vault");
+        let mint = env.mint(wallet().key, 6);
+        let token_account = env.token_account(&mint, vault.key, 0);
+        let destination = wallet();
+        let rent = token_account.lamports();
+        let destination_before = destination.lamports();
+
+        let seeds: &[&[u8]] = &[b"vault", &[bump]];
+        close_token_account(token_account, destination, vault, env.token_program, Some(seeds)).unwrap();
+
+        assert_eq!(token_account.lamports(), 0);
+        assert_eq!(*token_account.owner, system_program::ID);
+        assert_eq!(destination.lamports(), destination_before + rent);
+    }
+}
+
+#[test]
+fn close_token_account_with_tokens_left_fails() {
+    for program in TOKEN_PROGRAMS {
+        let env = Env::new(program);
+        let owner = wallet();
+        let mint = env.mint(owner.key, 6);
+        let token_account = env.token_account(&mint, owner.key, 1);
+
+        let err = close_token_account(token_account, wallet(), owner, env.token_program, None).unwrap_err();
+
+        assert_eq!(err, token_error(TokenError::NonNativeHasBalance));
+        assert_eq!(balance(token_account), 1);
+    }
+}
+
+#[test]
+fn mint_tokens_signs_for_a_pda_mint_authority() {
+    for program in TOKEN_PROGRAMS {
+        let env = Env::new(program);
+        let (mint_authority, bump) = pda(b"mint_authority");
+        let mint = env.mint(mint_authority.key, 9);
+        let to = env.token_account(&mint, wallet().key, 0);
+
+        let err = mint_tokens(&mint, to, 500, mint_authority, env.token_program, None).unwrap_err();
+        assert_eq!(err, ProgramError::MissingRequiredSignature.into());
+
+        let seeds: &[&[u8]] = &[b"mint_authority", &[bump]];
+        mint_tokens(&mint, to, 500, mint_authority, env.token_program, Some(seeds)).unwrap();
+
+        assert_eq!(balance(to), 500);
+    }
+}
+
+#[test]
+fn mint_tokens_from_the_wrong_authority_fails() {
+    for program in TOKEN_PROGRAMS {
+        let env = Env::new(program);
+        let mint = env.mint(wallet().key, 9);
+        let to = env.token_account(&mint, wallet().key, 0);
+
+        let err = mint_tokens(&mint, to, 500, wallet(), env.token_program, None).unwrap_err();
+
+        assert_eq!(err, token_error(TokenError::OwnerMismatch));
+        assert_eq!(balance(to), 0);
+    }
+}
+
+#[test]
+fn create_ata_if_needed_leaves_an_existing_ata_alone() {
+    for program in TOKEN_PROGRAMS {
+        let env = Env::new(program);
+        let (mint_authority, bump) = pda(b"mint_authority");
+        let mint = env.mint(mint_authority.key, 6);
+        let owner = wallet();
+        let ata = env.empty_ata(&mint, owner.key);
+
+        create_ata_if_needed(
+            env.payer,
+            ata,
+            owner,
+            &mint,
+            env.token_program,
+            env.associated_token_program,
+            env.system_program,
+        )
+        .unwrap();
+
+        assert_eq!(ata.owner, env.token_program.key);
+        let created = InterfaceAccount::<TokenAccount>::try_from(ata).unwrap();
+        assert_eq!(created.mint, mint.key());
+        assert_eq!(created.owner, *owner.key);
+
+        let seeds: &[&[u8]] = &[b"mint_authority", &[bump]];
+        mint_tokens(&mint, ata, 10, mint_authority, env.token_program, Some(seeds)).unwrap();
+
+        create_ata_if_needed(
+            env.payer,
+            ata,
+            owner,
+            &mint,
+            env.token_program,
+            env.associated_token_program,
+            env.system_program,
+        )
+        .unwrap();
+        assert_eq!(balance(ata), 10);
+
+        // create_ata on the other hand refuses an ATA that's already there
+        let err = create_ata(
+            env.payer,
+            ata,
+            owner,
+            &mint,
+            env.token_program,
+            env.associated_token_program,
+            env.system_program,
+        )
+        .unwrap_err();
+        assert_eq!(err, ProgramError::IllegalOwner.into());
+    }
+}
+
+#[test]
+fn create_ata_if_needed_adds_immutable_owner_on_token_2022() {
+    let env = Env::new(spl_token_2022::ID);
+    let mint = env.mint(wallet().key, 6);
+    let owner = wallet();
+    let ata = env.empty_ata(&mint, owner.key);
+
+    create_ata_if_needed(
+        env.payer,
+        ata,
+        owner,
+        &mint,
+        env.token_program,
+        env.associated_token_program,
+        env.system_program,
+    )
+    .unwrap();
+
+    // 165 byte base account, the account type byte and the ImmutableOwner extension header
+    assert_eq!(ata.data_len(), 170);
+    assert_eq!(InterfaceAccount::<TokenAccount>::try_from(ata).unwrap().owner, *owner.key);
+}
+
+#[test]
+fn amount_with_transfer_fee_leaves_fee_less_mints_alone() {
+    for program in TOKEN_PROGRAMS {
+        let env = Env::new(program);
+        let mint = env.mint(wallet().key, 6);
+
+        assert_eq!(amount_with_transfer_fee(&mint, 1_000).unwrap(), 1_000);
+    }
+}
+
+#[test]
+fn amount_with_transfer_fee_delivers_exactly_the_net_amount() {
+    let env = Env::new(spl_token_2022::ID);
+    let mint_authority = wallet();
+    // 1.5%, capped well above anything sent here
+    let mint = env.fee_mint(mint_authority.key, 6, 150, 1_000_000);
+    let owner = wallet();
+    let from = env.create_ata(&mint, owner);
+    let to = env.create_ata(&mint, wallet());
+    mint_tokens(&mint, from, 100_000, mint_authority, env.token_program, None).unwrap();
+
+    for net_amount in [1, 999, 1_000, 12_345] {
+        let to_before = balance(to);
+        let gross_amount = amount_with_transfer_fee(&mint, net_amount).unwrap();
+        assert!(gross_amount > net_amount);
+
+        transfer_tokens(from, to, gross_amount, &mint, owner, env.token_program, None).unwrap();
+
+        assert_eq!(balance(to) - to_before, net_amount);
+    }
+}
+
+#[test]
+fn harvest_withheld_fees_lets_a_fee_mint_account_close() {
+    let env = Env::new(spl_token_2022::ID);
+    let mint_authority = wallet();
+    let mint = env.fee_mint(mint_authority.key, 6, 100, 1_000_000);
+    let (vault, bump) = pda(b"vault");
+    let vault_ata = env.create_ata(&mint, vault);
+    let user = wallet();
+    let user_ata = env.create_ata(&mint, user);
+    mint_tokens(&mint, user_ata, 10_000, mint_authority, env.token_program, None).unwrap();
+
+    // deposit and withdraw everything, the fee on the deposit stays withheld in the vault
+    let seeds: &[&[u8]] = &[b"vault", &[bump]];
+    transfer_tokens(user_ata, vault_ata, 10_000, &mint, user, env.token_program, None).unwrap();
+    let vault_balance = balance(vault_ata);
+    transfer_tokens(vault_ata, user_ata, vault_balance, &mint, vault, env.token_program, Some(seeds)).unwrap();
+    assert_eq!(balance(vault_ata), 0);
+
+    let err = close_token_account(vault_ata, user, vault, env.token_program, Some(seeds)).unwrap_err();
+    assert_eq!(err, ProgramError::Custom(Token2022Error::AccountHasWithheldTransferFees as u32).into());
+
+    harvest_withheld_fees(vault_ata, &mint, env.token_program).unwrap();
+    close_token_account(vault_ata, user, vault, env.token_program, Some(seeds)).unwrap();
+
+    assert_eq!(vault_ata.lamports(), 0);
+}
+
+#[test]
+fn harvest_withheld_fees_does_nothing_for_fee_less_mints() {
+    for program in TOKEN_PROGRAMS {
+        let env = Env::new(program);
+        let mint = env.mint(wallet().key, 6);
+        let owner = wallet();
+        let token_account = env.token_account(&mint, owner.key, 5);
+
+        harvest_withheld_fees(token_account, &mint, env.token_program).unwrap();
+
+        assert_eq!(balance(token_account), 5);
+    }
+}
@@ -1,216 +1,119 @@
-#[cfg(test)]
-mod tests {
-    use solana_client::rpc_client::RpcClient;
-    use solana_program::{pubkey::{self, Pubkey}, system_instruction::transfer};
-    use solana_program::hash::hash;
-    use solana_sdk::{
-        message::Message,
-        signature::{read_keypair_file, Keypair, Signer},
-        transaction::Transaction,
-        system_program,
-        instruction::{AccountMeta, Instruction},
+use solana_client::rpc_client::RpcClient;
+use solana_program::{pubkey::Pubkey, system_instruction::transfer};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    signature::{Keypair, Signature, Signer},
+    system_program,
+    transaction::Transaction,
+};
+use std::{error::Error, str::FromStr};
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+pub const DEVNET_URL: &str = "https://api.devnet.solana.com";
+
+// Turbin3 prereq program and the accounts `submit` needs
+const TURBIN3_PREREQ_PROGRAM: &str = "TRBZyQHB3m68FGeVsqTK39Wm4xejadjVhP5MAZaKWDM";
+const COLLECTION: &str = "5ebsp5RChCGK7ssRZMVMufgVZhd2kFbNaotcZ5UvytN2";
+const MPL_CORE_PROGRAM: &str = "CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d";
+const SUBMIT_RS_DISCRIMINATOR: [u8; 8] = [77, 124, 82, 163, 21, 133, 181, 206];
+
+pub fn keygen() -> Keypair {
+    Keypair::new()
+}
+
+// Phantom style base58 private key -> the byte array solana wallet files hold
+pub fn base58_to_wallet(base58: &str) -> Result<Vec<u8>> {
+    let bytes = bs58::decode(base58.trim()).into_vec()?;
+    Keypair::from_bytes(&bytes)?; // make sure it really is a keypair before anyone saves it
+    Ok(bytes)
+}
+
+// wallet file bytes -> base58 private key
+pub fn wallet_to_base58(wallet: &[u8]) -> Result<String> {
+    Keypair::from_bytes(wallet)?;
+    Ok(bs58::encode(wallet).into_string())
+}
+
+pub fn airdrop(client: &RpcClient, to: &Pubkey, lamports: u64) -> Result<Signature> {
+    Ok(client.request_airdrop(to, lamports)?)
+}
+
+pub fn transfer_sol(client: &RpcClient, from: &Keypair, to: &Pubkey, lamports: u64) -> Result<Signature> {
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[transfer(&from.pubkey(), to, lamports)],
+        Some(&from.pubkey()),
+        &[from],
+        recent_blockhash,
+    );
+
+    Ok(client.send_and_confirm_transaction(&transaction)?)
+}
+
+// Sends the whole balance, minus exactly the fee the cluster charges for this transfer.
+// Returns the signature and how many lamports were sent
+pub fn sweep(client: &RpcClient, from: &Keypair, to: &Pubkey) -> Result<(Signature, u64)> {
+    let recent_blockhash = client.get_latest_blockhash()?;
+    let balance = client.get_balance(&from.pubkey())?;
+
+    // The fee only depends on the message shape, so price it with the whole balance and send balance - fee
+    let message = Message::new_with_blockhash(
+        &[transfer(&from.pubkey(), to, balance)],
+        Some(&from.pubkey()),
+        &recent_blockhash,
+    );
+    let fee = client.get_fee_for_message(&message)?;
+    let lamports = balance
+        .checked_sub(fee)
+        .filter(|lamports| *lamports > 0)
+        .ok_or_else(|| format!("balance of {balance} lamports doesn't cover the {fee} lamport fee"))?;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[transfer(&from.pubkey(), to, lamports)],
+        Some(&from.pubkey()),
+        &[from],
+        recent_blockhash,
+    );
+
+    Ok((client.send_and_confirm_transaction(&transaction)?, lamports))
+}
+
+// Calls submit_rs on the Turbin3 prereq program, which mints the completion NFT into the collection
+pub fn submit(client: &RpcClient, signer: &Keypair) -> Result<Signature> {
+    let mint = Keypair::new();
+    let turbin3_prereq_program = Pubkey::from_str(TURBIN3_PREREQ_PROGRAM)?;
+    let collection = Pubkey::from_str(COLLECTION)?;
+    let mpl_core_program = Pubkey::from_str(MPL_CORE_PROGRAM)?;
+
+    let signer_pubkey = signer.pubkey();
+    let (prereq_pda, _bump) = Pubkey::find_program_address(&[b"prereqs", signer_pubkey.as_ref()], &turbin3_prereq_program);
+    let (authority, _authority_bump) = Pubkey::find_program_address(&[b"collection", collection.as_ref()], &turbin3_prereq_program);
+
+    let instruction = Instruction {
+        program_id: turbin3_prereq_program,
+        accounts: vec![
+            AccountMeta::new(signer_pubkey, true),                      // user signer
+            AccountMeta::new(prereq_pda, false),                        // PDA account
+            AccountMeta::new(mint.pubkey(), true),                      // mint keypair
+            AccountMeta::new(collection, false),                        // collection
+            AccountMeta::new_readonly(authority, false),                // authority (PDA)
+            AccountMeta::new_readonly(mpl_core_program, false),         // mpl core program
+            AccountMeta::new_readonly(system_program::id(), false),     // system program
+        ],
+        data: SUBMIT_RS_DISCRIMINATOR.to_vec(),
     };
-    use std::{fs::read, str::FromStr};
-
-    const RPC_URL: &str = "https://turbine-solanad-4cde.devnet.rpcpool.com/9a9da9cf-6db1-47dc-839a-55aca5c9c80a";
-
-    
-    #[test]
-    fn keygen() {
-        let kp = Keypair::new();
-
-        println!("You've generated a new Solana wallet: {}", kp.pubkey().to_string());
-        println!("");
-        println!("To save your wallet, copy and paste the following into a JSON file:");
-        println!("{:?}", kp.to_bytes());
-    }
-    
-    #[test]
-    fn claim_airdrop() {
-        let keypair = read_keypair_file("dev-wallet.json").expect("Couldn't find wallet file.");
-
-        let client = RpcClient::new(RPC_URL);
-
-        match client.request_airdrop(&keypair.pubkey(), 2_000_000_000u64){
-            Ok(sig) => {
-                println!("Success! Check ur TX here:");
-                println!("https://explorer.solana.com/tx/{}?cluster=devnet", sig);
-            }
-            Err(err) => {
-                println!("Airdrop failed: {}", err);
-            }
-        }
-    }
-    
-    #[test]
-    fn transfer_sol() {
-
-        // Load your devnet keypair from file
-        let keypair = read_keypair_file("dev-wallet.json").expect("Couldn't find wallet file");
-
-
-        // Generate a signature from the keypair
-        let pubkey = keypair.pubkey();
-        let message_bytes = b"I verify my Solana Keypair!";
-        let sig = keypair.sign_message(message_bytes);
-        let sig_hashed = hash(sig.as_ref());
-
-        match sig.verify(&pubkey.to_bytes(), &sig_hashed.to_bytes()) {
-            true => println!("Signature verified"),
-            false => println!("Verification failed"),
-            }
-        
-        // Step 4: Define the destination (Turbin3) address
-        let to_pubkey = Pubkey::from_str("4Ts3DXtN8EvFLn47BsPvFU25ghwiy7DmrYmPyzcaJYtg").unwrap();
-        
-        // Step 5: Connect to devnet
-        let rpc_client = RpcClient::new(RPC_URL);
-
-        // Step 6: Fetch recent blockhash
-        let recent_blockhash = rpc_client
-            .get_latest_blockhash()
-            .expect("Failed to get recent blockhash");
-
-        // Step 7: Create and sign the transaction
-        let transaction = Transaction::new_signed_with_payer(
-            &[transfer(&keypair.pubkey(), &to_pubkey, 1_000_000)],
-            Some(&keypair.pubkey()),
-            &vec![&keypair],
-            recent_blockhash,
-        );
-
-        // Step 8: Send the transaction and print tx
-        let signature = rpc_client
-        .send_and_confirm_transaction(&transaction)
-        .expect("Failed to send transaction");
-
-        println!("Success! Check out your TX here: https://explorer.solana.com/tx/{}/?cluster=devnet", signature);
-    }
-
-    
-    #[test]
-    fn empty_wallet() {
-            // Load your devnet keypair from file
-        let keypair = read_keypair_file("dev-wallet.json").expect("Couldn't find wallet file");
-        
-        // Define the destination (Turbin3) address
-        let to_pubkey = Pubkey::from_str("4Ts3DXtN8EvFLn47BsPvFU25ghwiy7DmrYmPyzcaJYtg").unwrap();
-        
-        // Connect to devnet
-        let rpc_client = RpcClient::new(RPC_URL);
-
-        // Fetch recent blockhash
-        let recent_blockhash = rpc_client
-            .get_latest_blockhash()
-            .expect("Failed to get recent blockhash");
-
-        // Step 2: Get current balance
-        let balance = rpc_client
-            .get_balance(&keypair.pubkey())
-            .expect("Failed to get balance");
-
-        // Step 3: Build a mock transaction to calculate fee
-        let message = Message::new_with_blockhash(
-            &[transfer(&keypair.pubkey(), &to_pubkey, balance)],
-            Some(&keypair.pubkey()),
-            &recent_blockhash,
-        );
-
-        // Step 4: Estimate transaction fee
-        let fee = rpc_client
-            .get_fee_for_message(&message)
-            .expect("Failed to get fee calculator");
-
-        // Step 5: Create final transaction with balance minus fee
-        let transaction = Transaction::new_signed_with_payer(
-            &[transfer(&keypair.pubkey(), &to_pubkey, balance - fee)],
-            Some(&keypair.pubkey()),
-            &vec![&keypair],
-            recent_blockhash,
-        );       
-
-        // Step 6: Send transaction and verify
-        let signature = rpc_client
-            .send_and_confirm_transaction(&transaction)
-            .expect("Failed to send final transaction");
-            
-            
-        println!("Success! Entire balance transferred: https://explorer.solana.com/tx/{}/?cluster=devnet", signature);
-
-    }
-
-    #[test]
-    fn submit_rs() {
-
-        // 1. create solana client
-        let rpc_client = RpcClient::new(RPC_URL);
-
-        // 2. load signer keypair
-        let signer = read_keypair_file("turbin3-wallet.json")
-            .expect("Couldn't find wallet file");
-
-        // 3. Define program and account public keys
-        let mint = Keypair::new();
-        let turbin3_prereq_program = Pubkey::from_str("TRBZyQHB3m68FGeVsqTK39Wm4xejadjVhP5MAZaKWDM").unwrap();
-        let collection = Pubkey::from_str("5ebsp5RChCGK7ssRZMVMufgVZhd2kFbNaotcZ5UvytN2").unwrap();
-        let mpl_core_program = Pubkey::from_str("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d").unwrap();
-
-        let system_program = system_program::id();
-
-        // 4. Get the PDA (Program Derived Address)
-
-        let signer_pubkey = signer.pubkey();
-        let seeds = &[b"prereqs", signer_pubkey.as_ref()];
-        let (prereq_pda, _bump) = Pubkey::find_program_address(seeds, &turbin3_prereq_program);
-
-        // 4.1 define authority dont forget!!!
-
-        let authority_seeds = &[b"collection", collection.as_ref()];
-        let (authority, _authority_bump) = Pubkey::find_program_address(authority_seeds, &turbin3_prereq_program);
-
-        // 5. Prepare the instruction data (discriminator)
-        let data = vec![77, 124, 82, 163, 21, 133, 181, 206];
-
-        // 6. Define the accounts metadata
-        let accounts = vec![
-            AccountMeta::new(signer.pubkey(), true), // user signer
-            AccountMeta::new(prereq_pda, false), // PDA account
-            AccountMeta::new(mint.pubkey(), true), // mint keypair
-            AccountMeta::new(collection, false), // collection
-            AccountMeta::new_readonly(authority, false), // authority (PDA)
-            AccountMeta::new_readonly(mpl_core_program, false), // mpl core program
-            AccountMeta::new_readonly(system_program, false), // system program
-        ];
-
-
-        // 7. get recent blockhash
-        let blockhash = rpc_client
-            .get_latest_blockhash()
-            .expect("Failed to get recent blockhash");
-
-        // 8. Build instruction
-        let instruction = Instruction {
-            program_id: turbin3_prereq_program,
-            accounts,
-            data,
-        };
-
-        // 9. create and sign tx
-        let transaction = Transaction::new_signed_with_payer(
-            &[instruction], 
-            Some(&signer.pubkey()),
-            &[&signer, &mint],
-            blockhash,
-        );
-
-        // 10. send and confirm tx
-
-        let signature = rpc_client
-            .send_and_confirm_transaction(&transaction)
-            .expect("Failed to send tx");
-
-        println!("Check out your TX here:\nhttps://explorer.solana.com/tx/{}/?cluster=devnet", signature);
-
-    }
-
-}
\ No newline at end of file
+
+    let blockhash = client.get_latest_blockhash()?;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&signer_pubkey),
+        &[signer, &mint],
+        blockhash,
+    );
+
+    Ok(client.send_and_confirm_transaction(&transaction)?)
+}
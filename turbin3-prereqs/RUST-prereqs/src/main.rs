@@ -0,0 +1,104 @@
+use clap::{Parser, Subcommand};
+use rust_prereqs::{airdrop, base58_to_wallet, keygen, submit, sweep, transfer_sol, wallet_to_base58, Result, DEVNET_URL};
+use solana_client::rpc_client::RpcClient;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, write_keypair_file, Keypair, Signer};
+use std::path::{Path, PathBuf};
+
+/// Turbin3 prereq wallet tools
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    /// RPC URL of the cluster to talk to
+    #[arg(long, global = true, default_value = DEVNET_URL)]
+    url: String,
+
+    /// Wallet file (JSON byte array) that signs and pays
+    #[arg(long, short, global = true, default_value = "dev-wallet.json")]
+    keypair: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate a new wallet
+    Keygen {
+        /// Save the wallet here instead of printing its bytes
+        #[arg(long)]
+        outfile: Option<PathBuf>,
+    },
+    /// Convert a base58 private key (Phantom export) into wallet file bytes
+    Base58ToWallet { base58: String },
+    /// Print the --keypair wallet's private key as base58
+    WalletToBase58,
+    /// Request an airdrop to the --keypair wallet
+    Airdrop {
+        #[arg(long, default_value_t = 2_000_000_000)]
+        lamports: u64,
+    },
+    /// Send lamports from the --keypair wallet
+    Transfer {
+        to: Pubkey,
+        #[arg(long)]
+        lamports: u64,
+    },
+    /// Send the --keypair wallet's whole balance, minus exactly the transaction fee
+    Sweep { to: Pubkey },
+    /// Submit the Rust prereq to the Turbin3 program, signed by --keypair
+    Submit,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Keygen { outfile } => {
+            let kp = keygen();
+            println!("You've generated a new Solana wallet: {}", kp.pubkey());
+            match outfile {
+                Some(path) => {
+                    write_keypair_file(&kp, &path)?;
+                    println!("Saved to {}", path.display());
+                }
+                None => {
+                    println!("To save your wallet, copy and paste the following into a JSON file:");
+                    println!("{:?}", kp.to_bytes());
+                }
+            }
+        }
+        Command::Base58ToWallet { base58 } => {
+            println!("{:?}", base58_to_wallet(&base58)?);
+        }
+        Command::WalletToBase58 => {
+            println!("{}", wallet_to_base58(&load_keypair(&cli.keypair)?.to_bytes())?);
+        }
+        Command::Airdrop { lamports } => {
+            let keypair = load_keypair(&cli.keypair)?;
+            let signature = airdrop(&RpcClient::new(cli.url), &keypair.pubkey(), lamports)?;
+            println!("Success! Airdrop requested: {signature}");
+        }
+        Command::Transfer { to, lamports } => {
+            let keypair = load_keypair(&cli.keypair)?;
+            let signature = transfer_sol(&RpcClient::new(cli.url), &keypair, &to, lamports)?;
+            println!("Success! Sent {lamports} lamports: {signature}");
+        }
+        Command::Sweep { to } => {
+            let keypair = load_keypair(&cli.keypair)?;
+            let (signature, lamports) = sweep(&RpcClient::new(cli.url), &keypair, &to)?;
+            println!("Success! Entire balance of {lamports} lamports transferred: {signature}");
+        }
+        Command::Submit => {
+            let keypair = load_keypair(&cli.keypair)?;
+            let signature = submit(&RpcClient::new(cli.url), &keypair)?;
+            println!("Success! Prereq submitted: {signature}");
+        }
+    }
+
+    Ok(())
+}
+
+fn load_keypair(path: &Path) -> Result<Keypair> {
+    read_keypair_file(path).map_err(|err| format!("Couldn't read wallet file {}: {err}", path.display()).into())
+}
@@ -0,0 +1,130 @@
+// A local JSON-RPC server that answers the handful of methods the wallet flows use, so the tests
+// never touch devnet. It records every request and decodes the transactions it is sent.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::{json, Value};
+use solana_sdk::{hash::Hash, signature::Signature, transaction::Transaction};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+pub const BLOCKHASH: Hash = Hash::new_from_array([7; 32]);
+
+struct State {
+    balance: u64,
+    fee: u64,
+    requests: Vec<Value>,
+    transactions: Vec<Transaction>,
+}
+
+pub struct LocalRpc {
+    url: String,
+    state: Arc<Mutex<State>>,
+}
+
+impl LocalRpc {
+    // every wallet has `balance` lamports and every message costs `fee`
+    pub fn start(balance: u64, fee: u64) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(State { balance, fee, requests: Vec::new(), transactions: Vec::new() }));
+
+        let server_state = state.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let state = server_state.clone();
+                thread::spawn(move || serve(stream, state));
+            }
+        });
+
+        Self { url, state }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    // params of every request for `method`, oldest first
+    pub fn requests(&self, method: &str) -> Vec<Value> {
+        let state = self.state.lock().unwrap();
+        state.requests.iter().filter(|request| request["method"] == method).map(|request| request["params"].clone()).collect()
+    }
+
+    pub fn transactions(&self) -> Vec<Transaction> {
+        self.state.lock().unwrap().transactions.clone()
+    }
+}
+
+// one connection, kept alive for as many requests as the client sends
+fn serve(stream: TcpStream, state: Arc<Mutex<State>>) {
+    let mut writer = stream.try_clone().unwrap();
+    let mut reader = BufReader::new(stream);
+
+    loop {
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                return;
+            }
+            let line = line.trim_end().to_ascii_lowercase();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(length) = line.strip_prefix("content-length:") {
+                content_length = length.trim().parse().unwrap();
+            }
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        let request: Value = serde_json::from_slice(&body).unwrap();
+
+        let result = respond(&request, &mut state.lock().unwrap());
+        let response = json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string();
+        write!(
+            writer,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            response.len(),
+            response
+        )
+        .unwrap();
+    }
+}
+
+fn respond(request: &Value, state: &mut State) -> Value {
+    state.requests.push(request.clone());
+    let context = json!({ "slot": 1 });
+
+    match request["method"].as_str().unwrap() {
+        "getVersion" => json!({ "solana-core": "1.18.26", "feature-set": 0 }),
+        "getLatestBlockhash" => json!({
+            "context": context,
+            "value": { "blockhash": BLOCKHASH.to_string(), "lastValidBlockHeight": 1_000 },
+        }),
+        "isBlockhashValid" => json!({ "context": context, "value": true }),
+        "getBalance" => json!({ "context": context, "value": state.balance }),
+        "getFeeForMessage" => json!({ "context": context, "value": state.fee }),
+        "requestAirdrop" => json!(Signature::new_unique().to_string()),
+        "sendTransaction" => {
+            let encoded = request["params"][0].as_str().unwrap();
+            let transaction: Transaction = bincode::deserialize(&STANDARD.decode(encoded).unwrap()).unwrap();
+            let signature = transaction.signatures[0];
+            state.transactions.push(transaction);
+            json!(signature.to_string())
+        }
+        "getSignatureStatuses" => {
+            let statuses: Vec<Value> = request["params"][0]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|_| json!({ "slot": 1, "confirmations": null, "err": null, "status": { "Ok": null }, "confirmationStatus": "finalized" }))
+                .collect();
+            json!({ "context": context, "value": statuses })
+        }
+        _ => Value::Null,
+    }
+}
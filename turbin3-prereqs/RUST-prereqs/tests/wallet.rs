@@ -0,0 +1,122 @@
+mod common;
+
+use common::{LocalRpc, BLOCKHASH};
+use rust_prereqs::{airdrop, base58_to_wallet, keygen, sweep, transfer_sol, wallet_to_base58};
+use solana_client::rpc_client::RpcClient;
+use solana_program::{pubkey::Pubkey, system_instruction::SystemInstruction, system_program};
+use solana_sdk::{
+    signature::{read_keypair_file, Signer},
+    transaction::Transaction,
+};
+use std::{env, process::Command};
+
+const BALANCE: u64 = 1_000_000;
+const FEE: u64 = 5_000;
+
+// the lamports a transfer transaction moves, after checking it's a single signed system transfer between the two wallets
+fn transferred(transaction: &Transaction, from: &Pubkey, to: &Pubkey) -> u64 {
+    transaction.verify().unwrap();
+    assert_eq!(transaction.message.recent_blockhash, BLOCKHASH);
+    assert_eq!(transaction.message.instructions.len(), 1);
+
+    let instruction = &transaction.message.instructions[0];
+    let keys = &transaction.message.account_keys;
+    assert_eq!(keys[instruction.program_id_index as usize], system_program::id());
+    assert_eq!(keys[instruction.accounts[0] as usize], *from);
+    assert_eq!(keys[instruction.accounts[1] as usize], *to);
+
+    match bincode::deserialize(&instruction.data).unwrap() {
+        SystemInstruction::Transfer { lamports } => lamports,
+        other => panic!("expected a transfer, got {other:?}"),
+    }
+}
+
+#[test]
+fn base58_round_trips_a_wallet() {
+    let kp = keygen();
+
+    let base58 = wallet_to_base58(&kp.to_bytes()).unwrap();
+    assert_eq!(base58_to_wallet(&base58).unwrap(), kp.to_bytes().to_vec());
+
+    assert!(base58_to_wallet("not base58 0OIl").is_err());
+    assert!(base58_to_wallet(&bs58::encode([1u8; 32]).into_string()).is_err()); // decodes, but too short to be a keypair
+}
+
+#[test]
+fn airdrop_requests_lamports_for_the_wallet() {
+    let rpc = LocalRpc::start(BALANCE, FEE);
+    let kp = keygen();
+
+    airdrop(&RpcClient::new(rpc.url()), &kp.pubkey(), 2_000_000_000).unwrap();
+
+    let requests = rpc.requests("requestAirdrop");
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0][0], kp.pubkey().to_string());
+    assert_eq!(requests[0][1], 2_000_000_000u64);
+}
+
+#[test]
+fn transfer_sends_the_requested_lamports() {
+    let rpc = LocalRpc::start(BALANCE, FEE);
+    let from = keygen();
+    let to = Pubkey::new_unique();
+
+    transfer_sol(&RpcClient::new(rpc.url()), &from, &to, 1_000).unwrap();
+
+    let transactions = rpc.transactions();
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(transferred(&transactions[0], &from.pubkey(), &to), 1_000);
+}
+
+#[test]
+fn sweep_sends_the_balance_minus_the_exact_fee() {
+    let rpc = LocalRpc::start(BALANCE, FEE);
+    let from = keygen();
+    let to = Pubkey::new_unique();
+
+    let (_, lamports) = sweep(&RpcClient::new(rpc.url()), &from, &to).unwrap();
+
+    assert_eq!(lamports, BALANCE - FEE);
+    assert_eq!(rpc.requests("getFeeForMessage").len(), 1);
+    let transactions = rpc.transactions();
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(transferred(&transactions[0], &from.pubkey(), &to), BALANCE - FEE);
+}
+
+#[test]
+fn sweep_refuses_a_balance_that_only_covers_the_fee() {
+    let rpc = LocalRpc::start(FEE, FEE);
+    let from = keygen();
+
+    assert!(sweep(&RpcClient::new(rpc.url()), &from, &Pubkey::new_unique()).is_err());
+    assert!(rpc.transactions().is_empty());
+}
+
+#[test]
+fn cli_uses_the_configured_url_and_keypair() {
+    let rpc = LocalRpc::start(BALANCE, FEE);
+    let wallet = env::temp_dir().join(format!("rust-prereqs-{}.json", Pubkey::new_unique()));
+
+    let keygen = Command::new(env!("CARGO_BIN_EXE_rust-prereqs"))
+        .args(["keygen", "--outfile", wallet.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(keygen.status.success());
+    let kp = read_keypair_file(&wallet).unwrap();
+
+    let to = Pubkey::new_unique();
+    let sweep = Command::new(env!("CARGO_BIN_EXE_rust-prereqs"))
+        .args(["--url", rpc.url(), "--keypair", wallet.to_str().unwrap(), "sweep", &to.to_string()])
+        .output()
+        .unwrap();
+    assert!(sweep.status.success(), "{}", String::from_utf8_lossy(&sweep.stderr));
+    assert_eq!(transferred(&rpc.transactions()[0], &kp.pubkey(), &to), BALANCE - FEE);
+
+    let base58 = Command::new(env!("CARGO_BIN_EXE_rust-prereqs"))
+        .args(["--keypair", wallet.to_str().unwrap(), "wallet-to-base58"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8(base58.stdout).unwrap().trim(), bs58::encode(kp.to_bytes()).into_string());
+
+    std::fs::remove_file(&wallet).unwrap();
+}
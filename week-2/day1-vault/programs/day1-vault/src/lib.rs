@@ -1,4 +1,12 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)] // anchor 0.31 #[program] still calls AccountInfo::realloc
+
+use anchor_helpers::{close_token_account, harvest_withheld_fees, is_valid_bps, transfer_tokens};
 use anchor_lang::{prelude::*, system_program::{Transfer, transfer}};
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
 
 declare_id!("BS7k9JPu7a9ZQaUAAikE4ZnuBri11V9DyQTdNuxUVyUq");
 
@@ -10,6 +18,16 @@ pub mod day1_vault {
         ctx.accounts.initialize(&ctx.bumps)
     }
 
+    pub fn lock_vault(
+        ctx: Context<LockVault>,
+        unlock_timestamp: Option<i64>,
+        target_balance: Option<u64>,
+        penalty_bps: u16,
+        beneficiary: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.lock_vault(unlock_timestamp, target_balance, penalty_bps, beneficiary)
+    }
+
     pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
         ctx.accounts.deposit(amount)
     }
@@ -18,11 +36,54 @@ pub mod day1_vault {
         ctx.accounts.withdraw(amount)
     }
 
-    pub fn close(ctx: Context<Close>) -> Result<()> {
-        ctx.accounts.close()
+    pub fn deposit_token(ctx: Context<DepositToken>, amount: u64) -> Result<()> {
+        ctx.accounts.deposit_token(amount)
+    }
+
+    pub fn withdraw_token(ctx: Context<WithdrawToken>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_token(amount)
+    }
+
+    pub fn configure_owners(ctx: Context<ConfigureVault>, owners: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        ctx.accounts.configure_owners(owners, threshold)
+    }
+
+    pub fn set_delegate(
+        ctx: Context<ConfigureVault>,
+        delegate: Pubkey,
+        max_per_period: u64,
+        period_seconds: i64,
+    ) -> Result<()> {
+        ctx.accounts.set_delegate(delegate, max_per_period, period_seconds)
+    }
+
+    pub fn delegate_withdraw(ctx: Context<DelegateWithdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.delegate_withdraw(amount)
+    }
+
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: ProposalAction) -> Result<()> {
+        ctx.accounts.create_proposal(action, &ctx.bumps)
+    }
+
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        ctx.accounts.approve_proposal()
+    }
+
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        ctx.accounts.execute_proposal()
+    }
+
+    // remaining accounts: [mint, vault token account, signer token account, token program] for every mint in vault_state.mints
+    // (the mint has to be writable, withheld transfer fees are harvested into it before the vault ATA closes)
+    pub fn close<'info>(ctx: Context<'_, '_, 'info, 'info, Close<'info>>) -> Result<()> {
+        ctx.accounts.close(ctx.remaining_accounts)
         // TODO: Implement close functionality
         // Should withdraw all remaining SOL and close account
     }
+
+    pub fn migrate_vault_state(ctx: Context<MigrateVaultState>) -> Result<()> {
+        ctx.accounts.migrate_vault_state()
+    }
 }
 
 // =============================================================================
@@ -56,12 +117,35 @@ pub struct Initialize<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct LockVault<'info> {
+    pub signer: Signer<'info>,
+
+    // mut because we write the lock settings into the state account
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref()],
+        bump = vault_state.state_bump,
+        constraint = !vault_state.is_multisig() @ VaultError::MultisigRequired,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    // read only, we need the balance to know if an existing goal is already met
+    #[account(
+        seeds = [b"vault", signer.key().as_ref()],
+        bump = vault_state.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+}
+
 #[derive(Accounts)]
 pub struct Deposit<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
+    // mut because reaching the savings goal is latched into the state
     #[account(
+        mut,
         seeds = [b"state", signer.key().as_ref()],
         bump = vault_state.state_bump,
     )]
@@ -82,9 +166,12 @@ pub struct Withdraw<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
+    // once the vault has an owner set, SOL only leaves through proposals or delegate allowances
     #[account(
+        mut,
         seeds = [b"state", signer.key().as_ref()],
         bump = vault_state.state_bump,
+        constraint = !vault_state.is_multisig() @ VaultError::MultisigRequired,
     )]
     pub vault_state: Account<'info, VaultState>,
 
@@ -94,7 +181,105 @@ pub struct Withdraw<'info> {
         bump = vault_state.vault_bump,
     )]
     pub vault: SystemAccount<'info>,
-    
+
+    // only needed for an early withdrawal, gets the penalty cut
+    #[account(
+        mut,
+        address = vault_state.beneficiary.unwrap_or_default() @ VaultError::InvalidBeneficiary,
+    )]
+    pub beneficiary: Option<SystemAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositToken<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    // mut because a new mint gets added to vault_state.mints
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    // the same SOL vault PDA is the authority of every vault token account
+    #[account(
+        seeds = [b"vault", signer.key().as_ref()],
+        bump = vault_state.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program,
+    )]
+    pub signer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // one ATA per mint, owned by the vault PDA, created on the first deposit of that mint
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // classic token program or token-2022, whichever the mint belongs to
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawToken<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref()],
+        bump = vault_state.state_bump,
+        constraint = !vault_state.is_multisig() @ VaultError::MultisigRequired,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        seeds = [b"vault", signer.key().as_ref()],
+        bump = vault_state.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program,
+    )]
+    pub signer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
@@ -108,6 +293,7 @@ pub struct Close<'info> {
         seeds = [b"state", signer.key().as_ref()],
         bump = vault_state.state_bump,
         close = signer,
+        constraint = !vault_state.is_multisig() @ VaultError::MultisigRequired,
     )]
     pub vault_state: Account<'info, VaultState>,
 
@@ -117,7 +303,182 @@ pub struct Close<'info> {
         bump = vault_state.vault_bump,
     )]
     pub vault: SystemAccount<'info>,
-    
+
+    // only needed for an early withdrawal, gets the penalty cut
+    #[account(
+        mut,
+        address = vault_state.beneficiary.unwrap_or_default() @ VaultError::InvalidBeneficiary,
+    )]
+    pub beneficiary: Option<SystemAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// vaults opened before locks, tokens and owner sets only stored the two bumps, this grows one to the current layout
+#[derive(Accounts)]
+pub struct MigrateVaultState<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: vault state in the old layout, so it's loaded by hand. Seeds and owner are checked here, discriminator and size in the handler
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub vault_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureVault<'info> {
+    pub signer: Signer<'info>,
+
+    // direct configuration is a single-owner thing, a team vault changes owners/delegates through proposals
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref()],
+        bump = vault_state.state_bump,
+        constraint = !vault_state.is_multisig() @ VaultError::MultisigRequired,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+#[derive(Accounts)]
+pub struct DelegateWithdraw<'info> {
+    #[account(mut)]
+    pub delegate: Signer<'info>,
+
+    /// CHECK: the wallet that created the vault, only used to derive the vault PDAs
+    pub creator: UncheckedAccount<'info>,
+
+    // mut because the delegate's spent_in_period is updated
+    #[account(
+        mut,
+        seeds = [b"state", creator.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", creator.key().as_ref()],
+        bump = vault_state.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    /// CHECK: the wallet that created the vault, only used to derive the vault PDAs
+    pub creator: UncheckedAccount<'info>,
+
+    // mut because proposal_count goes up
+    #[account(
+        mut,
+        seeds = [b"state", creator.key().as_ref()],
+        bump = vault_state.state_bump,
+        constraint = vault_state.owners.contains(&proposer.key()) @ VaultError::NotAnOwner,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    // one account per proposal, the id is the vault's running proposal_count
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [b"proposal", vault_state.key().as_ref(), vault_state.proposal_count.to_le_bytes().as_ref()],
+        bump,
+        space = Proposal::INIT_SPACE,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        constraint = vault_state.owners.contains(&owner.key()) @ VaultError::NotAnOwner,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", vault_state.key().as_ref(), proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = vault_state,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    pub executor: Signer<'info>,
+
+    /// CHECK: the wallet that created the vault, only used to derive the vault PDAs
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", creator.key().as_ref()],
+        bump = vault_state.state_bump,
+        constraint = vault_state.owners.contains(&executor.key()) @ VaultError::NotAnOwner,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", creator.key().as_ref()],
+        bump = vault_state.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    // executed proposals are closed, rent goes back to whoever proposed it
+    #[account(
+        mut,
+        seeds = [b"proposal", vault_state.key().as_ref(), proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = vault_state,
+        has_one = proposer,
+        close = proposer,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: only receives the proposal rent
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// CHECK: only needed for Withdraw proposals, checked against the proposal's recipient
+    #[account(mut)]
+    pub recipient: Option<UncheckedAccount<'info>>,
+
+    // only needed when a Withdraw proposal exits a locked vault early
+    #[account(
+        mut,
+        address = vault_state.beneficiary.unwrap_or_default() @ VaultError::InvalidBeneficiary,
+    )]
+    pub beneficiary: Option<SystemAccount<'info>>,
+
+    // the four below are only needed for WithdrawToken proposals, checked against the proposal in execute_proposal
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub recipient_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -130,6 +491,60 @@ impl<'info> Initialize<'info> {
         // TODO: Store bump values in vault_state
         self.vault_state.vault_bump = bumps.vault;
         self.vault_state.state_bump = bumps.vault_state;
+        // a fresh vault has no lock, lock_vault turns it into a savings vault
+        self.vault_state.unlock_timestamp = None;
+        self.vault_state.target_balance = None;
+        self.vault_state.penalty_bps = 0;
+        self.vault_state.beneficiary = None;
+        self.vault_state.mints = Vec::new();
+        self.vault_state.owners = Vec::new();
+        self.vault_state.threshold = 0;
+        self.vault_state.delegates = Vec::new();
+        self.vault_state.proposal_count = 0;
+        self.vault_state.goal_reached = false;
+        Ok(())
+    }
+}
+
+impl<'info> LockVault<'info> {
+    pub fn lock_vault(
+        &mut self,
+        unlock_timestamp: Option<i64>,
+        target_balance: Option<u64>,
+        penalty_bps: u16,
+        beneficiary: Option<Pubkey>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        // COMMITMENT RULE - you can't loosen a lock that is still running, otherwise the lock means nothing
+        require!(
+            !self.vault_state.is_locked(now, self.vault.lamports()),
+            VaultError::VaultLocked
+        );
+
+        // at least one condition, a lock with neither would never be locked
+        require!(
+            unlock_timestamp.is_some() || target_balance.is_some(),
+            VaultError::InvalidLockConfig
+        );
+        if let Some(unlock_timestamp) = unlock_timestamp {
+            require!(unlock_timestamp > now, VaultError::InvalidLockConfig);
+        }
+        if let Some(target_balance) = target_balance {
+            require!(target_balance > self.vault.lamports(), VaultError::InvalidLockConfig);
+        }
+
+        // penalty_bps = 0 means no early exit at all, anything else needs someone to receive the cut
+        require!(is_valid_bps(penalty_bps), VaultError::InvalidLockConfig);
+        require!(penalty_bps == 0 || beneficiary.is_some(), VaultError::InvalidBeneficiary);
+
+        self.vault_state.unlock_timestamp = unlock_timestamp;
+        self.vault_state.target_balance = target_balance;
+        self.vault_state.penalty_bps = penalty_bps;
+        self.vault_state.beneficiary = beneficiary;
+        // a new goal starts unmet, the require above made sure it's above the current balance
+        self.vault_state.goal_reached = false;
+
         Ok(())
     }
 }
@@ -148,6 +563,9 @@ impl<'info> Deposit<'info> {
 
         transfer(cpi_ctx, amount)?; // this is the literal Anchor SDK's CPI (Custom Program Interface) for transferring SOL
 
+        // GOAL LATCH - once the target is hit the vault stays unlocked, even after the balance drops again
+        self.vault_state.latch_goal(self.vault.lamports());
+
         Ok(())
     }
 }
@@ -157,6 +575,11 @@ impl<'info> Withdraw<'info> {
 
         let cpi_program = self.system_program.to_account_info(); // this is the cpi program (system_program) that will handle the transfer
 
+        // LOCK CHECK - before the unlock time / goal this either fails or costs the early withdrawal penalty
+        let now = Clock::get()?.unix_timestamp;
+        self.vault_state.latch_goal(self.vault.lamports());
+        let penalty = self.vault_state.early_withdrawal_penalty(now, self.vault.lamports(), amount)?;
+
         let cpi_accounts = Transfer { // this is the "envelope" that has the transfer details as a struct, this time the vault is the sender, and the signer is the recipient
             from: self.vault.to_account_info(),
             to: self.signer.to_account_info(),
@@ -175,18 +598,43 @@ impl<'info> Withdraw<'info> {
         // ENHANCED SHIPPING LABEL - Now with "proof of authorization" attached!
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, &seeds); // Unlike deposit's new(), new_with_signer() includes mathematical proof that we control the sending PDA
 
-        transfer(cpi_ctx, amount)?; // this is the literal Anchor SDK's CPI (Custom Program Interface) for transferring SOL from PDA
+        transfer(cpi_ctx, amount - penalty)?; // this is the literal Anchor SDK's CPI (Custom Program Interface) for transferring SOL from PDA
 
+        // PENALTY SHIPMENT - same vault signature, different destination
+        if penalty > 0 {
+            let beneficiary = self.beneficiary.as_ref().ok_or(VaultError::InvalidBeneficiary)?;
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.vault.to_account_info(),
+                    to: beneficiary.to_account_info(),
+                },
+                &seeds,
+            );
+            transfer(cpi_ctx, penalty)?;
+        }
 
         Ok(())
     }
 }
 
 impl<'info> Close<'info> {
-    pub fn close(&mut self) -> Result<()> {
+    pub fn close(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
         // VAULT CLOSURE OPERATION - This is the "business liquidation" process
         // Unlike withdraw (user-specified amount), close empties the entire vault before closing accounts
         
+        // LOCK CHECK - closing early is just an early withdrawal of everything
+        let now = Clock::get()?.unix_timestamp;
+        let balance = self.vault.lamports();
+        let penalty = self.vault_state.early_withdrawal_penalty(now, balance, balance)?;
+
+        // the early exit penalty only covers SOL, tokens stay put until the lock is over
+        // (checked on the lock itself, a tiny SOL balance would otherwise make the penalty 0 and free the tokens)
+        require!(
+            !self.vault_state.is_locked(now, balance) || self.vault_state.mints.is_empty(),
+            VaultError::VaultLocked
+        );
+
         let cpi_program = self.system_program.to_account_info(); // Same shipping service as withdraw and deposit
 
         let cpi_accounts = Transfer { // The "final liquidation envelope" - vault pays out everything to owner
@@ -204,6 +652,23 @@ impl<'info> Close<'info> {
         // PACKAGING FOR FINAL SHIPMENT - Same three-level nesting as withdraw
         let seeds = [&pda_signing_seeds[..]];       // Wrap our single PDA's seeds into the required format
 
+        // TOKEN SWEEP - every tracked mint is emptied into the owner's token account, then the vault ATA is closed for its rent
+        self.sweep_token_accounts(remaining_accounts, &pda_signing_seeds)?;
+
+        // PENALTY FIRST - beneficiary gets its cut before the owner is paid out
+        if penalty > 0 {
+            let beneficiary = self.beneficiary.as_ref().ok_or(VaultError::InvalidBeneficiary)?;
+            let penalty_ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.vault.to_account_info(),
+                    to: beneficiary.to_account_info(),
+                },
+                &seeds,
+            );
+            transfer(penalty_ctx, penalty)?;
+        }
+
         // FINAL SHIPPING LABEL - With mathematical proof of PDA ownership
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, &seeds);
 
@@ -215,6 +680,427 @@ impl<'info> Close<'info> {
         
         Ok(()) // Return success after complete vault closure
     }
+
+    fn sweep_token_accounts(
+        &self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        vault_seeds: &[&[u8]],
+    ) -> Result<()> {
+        let mints = &self.vault_state.mints;
+        require!(
+            remaining_accounts.len() == mints.len() * 4,
+            VaultError::MissingTokenAccounts
+        );
+
+        for (expected_mint, accounts) in mints.iter().zip(remaining_accounts.chunks(4)) {
+            let mint = InterfaceAccount::<Mint>::try_from(&accounts[0])?;
+            let vault_token_account = InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?;
+            let signer_token_account = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?;
+            let token_program = Interface::<TokenInterface>::try_from(&accounts[3])?;
+
+            require_keys_eq!(mint.key(), *expected_mint, VaultError::MissingTokenAccounts);
+            require_keys_eq!(*accounts[0].owner, token_program.key(), VaultError::MissingTokenAccounts);
+
+            // has to be the vault's own ATA, not some other account the vault happens to own
+            let expected_vault_ata = get_associated_token_address_with_program_id(
+                &self.vault.key(),
+                &mint.key(),
+                &token_program.key(),
+            );
+            require_keys_eq!(vault_token_account.key(), expected_vault_ata, VaultError::MissingTokenAccounts);
+            require_keys_eq!(signer_token_account.owner, self.signer.key(), VaultError::MissingTokenAccounts);
+
+            if vault_token_account.amount > 0 {
+                transfer_tokens(
+                    &vault_token_account,
+                    &signer_token_account,
+                    vault_token_account.amount,
+                    &mint,
+                    &self.vault.to_account_info(),
+                    &token_program,
+                    Some(vault_seeds),
+                )?;
+            }
+
+            // a Token-2022 fee mint leaves withheld fees behind that block the close
+            harvest_withheld_fees(&vault_token_account, &mint, &token_program)?;
+
+            close_token_account(
+                &vault_token_account,
+                &self.signer.to_account_info(),
+                &self.vault.to_account_info(),
+                &token_program,
+                Some(vault_seeds),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'info> MigrateVaultState<'info> {
+    pub fn migrate_vault_state(&mut self) -> Result<()> {
+        let vault_state_info = self.vault_state.to_account_info();
+
+        let legacy = {
+            let data = vault_state_info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == *VaultState::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            // anything longer is already in the current layout
+            require!(data.len() == LegacyVaultState::INIT_SPACE, VaultError::InvalidVaultState);
+            LegacyVaultState::deserialize(&mut &data[8..])?
+        };
+
+        // same defaults initialize gives a new vault: no lock, no tokens, no owners or delegates
+        let vault_state = VaultState {
+            vault_bump: legacy.vault_bump,
+            state_bump: legacy.state_bump,
+            unlock_timestamp: None,
+            target_balance: None,
+            penalty_bps: 0,
+            beneficiary: None,
+            mints: Vec::new(),
+            owners: Vec::new(),
+            threshold: 0,
+            delegates: Vec::new(),
+            proposal_count: 0,
+            goal_reached: false,
+        };
+
+        // the signer pays the rent for the extra space, then the account grows and gets the new layout written over it
+        let rent = Rent::get()?.minimum_balance(VaultState::INIT_SPACE);
+        let shortfall = rent.saturating_sub(vault_state_info.lamports());
+        if shortfall > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.signer.to_account_info(),
+                        to: vault_state_info.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+
+        vault_state_info.resize(VaultState::INIT_SPACE)?;
+        let mut data = vault_state_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data[..];
+        vault_state.try_serialize(&mut writer)?;
+
+        Ok(())
+    }
+}
+
+impl<'info> ConfigureVault<'info> {
+    pub fn configure_owners(&mut self, owners: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        validate_owner_set(&owners, threshold)?;
+
+        // from here on the creator key alone can't move funds anymore
+        self.vault_state.owners = owners;
+        self.vault_state.threshold = threshold;
+
+        Ok(())
+    }
+
+    pub fn set_delegate(&mut self, delegate: Pubkey, max_per_period: u64, period_seconds: i64) -> Result<()> {
+        self.vault_state.set_delegate(delegate, max_per_period, period_seconds)
+    }
+}
+
+impl<'info> DelegateWithdraw<'info> {
+    pub fn delegate_withdraw(&mut self, amount: u64) -> Result<()> {
+        // delegates never get the early exit, a locked vault is locked for them
+        let now = Clock::get()?.unix_timestamp;
+        self.vault_state.latch_goal(self.vault.lamports());
+        require!(
+            !self.vault_state.is_locked(now, self.vault.lamports()),
+            VaultError::VaultLocked
+        );
+
+        // ALLOWANCE CHECK - rolls the period forward if it's over, then checks the cap
+        let delegate_key = self.delegate.key();
+        let allowance = self
+            .vault_state
+            .delegates
+            .iter_mut()
+            .find(|d| d.delegate == delegate_key)
+            .ok_or(VaultError::NotADelegate)?;
+        allowance.spend(now, amount)?;
+
+        transfer_from_vault(
+            &self.system_program,
+            &self.vault,
+            &self.delegate.to_account_info(),
+            &self.creator.key(),
+            self.vault_state.vault_bump,
+            amount,
+        )
+    }
+}
+
+impl<'info> CreateProposal<'info> {
+    pub fn create_proposal(&mut self, action: ProposalAction, bumps: &CreateProposalBumps) -> Result<()> {
+        // same rules as the direct instructions, checked up front so a bad proposal can't collect approvals
+        match &action {
+            ProposalAction::Withdraw { amount, .. } => require!(*amount > 0, VaultError::InvalidProposal),
+            ProposalAction::WithdrawToken { mint, amount, .. } => {
+                require!(*amount > 0, VaultError::InvalidProposal);
+                require!(self.vault_state.mints.contains(mint), VaultError::InvalidProposal);
+            }
+            ProposalAction::SetOwners { owners, threshold } => validate_proposed_owner_set(owners, *threshold)?,
+            ProposalAction::SetDelegate { period_seconds, max_per_period, .. } => {
+                require!(*max_per_period == 0 || *period_seconds > 0, VaultError::InvalidProposal)
+            }
+        }
+
+        self.proposal.set_inner(Proposal {
+            vault_state: self.vault_state.key(),
+            id: self.vault_state.proposal_count,
+            proposer: self.proposer.key(),
+            action,
+            approvals: vec![self.proposer.key()], // proposing counts as approving
+            bump: bumps.proposal,
+        });
+
+        self.vault_state.proposal_count = self
+            .vault_state
+            .proposal_count
+            .checked_add(1)
+            .ok_or(VaultError::MathOverflow)?;
+
+        Ok(())
+    }
+}
+
+impl<'info> ApproveProposal<'info> {
+    pub fn approve_proposal(&mut self) -> Result<()> {
+        let owner = self.owner.key();
+        require!(!self.proposal.approvals.contains(&owner), VaultError::AlreadyApproved);
+
+        // approvals from owners that were removed since don't count anymore, drop them so they don't fill up the list
+        let owners = &self.vault_state.owners;
+        self.proposal.approvals.retain(|approval| owners.contains(approval));
+        require!(self.proposal.approvals.len() < MAX_VAULT_OWNERS, VaultError::AlreadyApproved);
+
+        self.proposal.approvals.push(owner);
+
+        Ok(())
+    }
+}
+
+impl<'info> ExecuteProposal<'info> {
+    pub fn execute_proposal(&mut self) -> Result<()> {
+        // only approvals from current owners count, the owner set may have changed since they were given
+        let approvals = self
+            .proposal
+            .approvals
+            .iter()
+            .filter(|a| self.vault_state.owners.contains(a))
+            .count();
+        require!(
+            approvals >= self.vault_state.threshold as usize,
+            VaultError::NotEnoughApprovals
+        );
+
+        match self.proposal.action.clone() {
+            ProposalAction::Withdraw { recipient, amount } => {
+                let recipient_account = self.recipient.as_ref().ok_or(VaultError::InvalidRecipient)?;
+                require_keys_eq!(recipient_account.key(), recipient, VaultError::InvalidRecipient);
+
+                // same lock rules as the single-owner withdraw, penalty included
+                let now = Clock::get()?.unix_timestamp;
+                self.vault_state.latch_goal(self.vault.lamports());
+                let penalty = self.vault_state.early_withdrawal_penalty(now, self.vault.lamports(), amount)?;
+
+                transfer_from_vault(
+                    &self.system_program,
+                    &self.vault,
+                    &recipient_account.to_account_info(),
+                    &self.creator.key(),
+                    self.vault_state.vault_bump,
+                    amount - penalty,
+                )?;
+
+                if penalty > 0 {
+                    let beneficiary = self.beneficiary.as_ref().ok_or(VaultError::InvalidBeneficiary)?;
+                    transfer_from_vault(
+                        &self.system_program,
+                        &self.vault,
+                        &beneficiary.to_account_info(),
+                        &self.creator.key(),
+                        self.vault_state.vault_bump,
+                        penalty,
+                    )?;
+                }
+            }
+            ProposalAction::WithdrawToken { mint, recipient, amount } => {
+                self.withdraw_token(mint, recipient, amount)?;
+            }
+            ProposalAction::SetOwners { owners, threshold } => {
+                // an empty owner set hands the vault back to its creator, who can then withdraw or close it
+                validate_proposed_owner_set(&owners, threshold)?;
+                self.vault_state.owners = owners;
+                self.vault_state.threshold = threshold;
+            }
+            ProposalAction::SetDelegate { delegate, max_per_period, period_seconds } => {
+                self.vault_state.set_delegate(delegate, max_per_period, period_seconds)?;
+            }
+        }
+
+        // IMPORTANT: the proposal account is closed by the `close = proposer` constraint, so it can't run twice
+        Ok(())
+    }
+
+    fn withdraw_token(&self, mint: Pubkey, recipient: Pubkey, amount: u64) -> Result<()> {
+        let mint_account = self.mint.as_ref().ok_or(VaultError::MissingTokenAccounts)?;
+        let vault_token_account = self.vault_token_account.as_ref().ok_or(VaultError::MissingTokenAccounts)?;
+        let recipient_token_account = self
+            .recipient_token_account
+            .as_ref()
+            .ok_or(VaultError::InvalidRecipient)?;
+        let token_program = self.token_program.as_ref().ok_or(VaultError::MissingTokenAccounts)?;
+
+        // same account checks the close sweep does, plus the recipient has to be the one the owners voted for
+        require_keys_eq!(mint_account.key(), mint, VaultError::MissingTokenAccounts);
+        require_keys_eq!(*mint_account.to_account_info().owner, token_program.key(), VaultError::MissingTokenAccounts);
+        let expected_vault_ata = get_associated_token_address_with_program_id(
+            &self.vault.key(),
+            &mint,
+            &token_program.key(),
+        );
+        require_keys_eq!(vault_token_account.key(), expected_vault_ata, VaultError::MissingTokenAccounts);
+        require_keys_eq!(recipient_token_account.owner, recipient, VaultError::InvalidRecipient);
+        require_keys_eq!(recipient_token_account.mint, mint, VaultError::InvalidRecipient);
+
+        // tokens follow the same lock as SOL, but there's no early exit for them
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            !self.vault_state.is_locked(now, self.vault.lamports()),
+            VaultError::VaultLocked
+        );
+
+        let creator = self.creator.key();
+        let pda_signing_seeds = [b"vault", creator.as_ref(), &[self.vault_state.vault_bump]];
+
+        transfer_tokens(
+            vault_token_account,
+            recipient_token_account,
+            amount,
+            mint_account,
+            &self.vault.to_account_info(),
+            token_program,
+            Some(&pda_signing_seeds),
+        )
+    }
+}
+
+impl<'info> DepositToken<'info> {
+    pub fn deposit_token(&mut self, amount: u64) -> Result<()> {
+        // remember the mint so close knows which token accounts to sweep
+        let mint = self.mint.key();
+        if !self.vault_state.mints.contains(&mint) {
+            require!(self.vault_state.mints.len() < MAX_VAULT_MINTS, VaultError::TooManyMints);
+            self.vault_state.mints.push(mint);
+        }
+
+        // signer owns the source account, so no PDA seeds needed
+        transfer_tokens(
+            &self.signer_token_account,
+            &self.vault_token_account,
+            amount,
+            &self.mint,
+            &self.signer.to_account_info(),
+            &self.token_program,
+            None,
+        )
+    }
+}
+
+impl<'info> WithdrawToken<'info> {
+    pub fn withdraw_token(&mut self, amount: u64) -> Result<()> {
+        // tokens follow the same lock as SOL, but there's no early exit for them
+        let now = Clock::get()?.unix_timestamp;
+        self.vault_state.latch_goal(self.vault.lamports());
+        require!(
+            !self.vault_state.is_locked(now, self.vault.lamports()),
+            VaultError::VaultLocked
+        );
+
+        // same vault PDA signature as the SOL withdraw
+        let pda_signing_seeds = [
+            b"vault",
+            self.signer.key.as_ref(),
+            &[self.vault_state.vault_bump],
+        ];
+
+        transfer_tokens(
+            &self.vault_token_account,
+            &self.signer_token_account,
+            amount,
+            &self.mint,
+            &self.vault.to_account_info(),
+            &self.token_program,
+            Some(&pda_signing_seeds),
+        )
+    }
+}
+
+// =============================================================================
+// HELPERS - SOL out of the vault PDA, the token CPIs come from anchor_helpers
+// =============================================================================
+
+// Send lamports out of the vault PDA, signed with the creator's vault seeds
+pub fn transfer_from_vault<'info>(
+    system_program: &Program<'info, System>,
+    vault: &SystemAccount<'info>,
+    to: &AccountInfo<'info>,
+    creator: &Pubkey,
+    vault_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let pda_signing_seeds = [b"vault", creator.as_ref(), &[vault_bump]];
+    let seeds = [&pda_signing_seeds[..]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        Transfer {
+            from: vault.to_account_info(),
+            to: to.clone(),
+        },
+        &seeds,
+    );
+
+    transfer(cpi_ctx, amount)
+}
+
+// m-of-n sanity: no duplicates, fits in the account, threshold between 1 and n
+pub fn validate_owner_set(owners: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !owners.is_empty() && owners.len() <= MAX_VAULT_OWNERS,
+        VaultError::InvalidOwnerSet
+    );
+    require!(
+        threshold >= 1 && threshold as usize <= owners.len(),
+        VaultError::InvalidOwnerSet
+    );
+
+    for (i, owner) in owners.iter().enumerate() {
+        require!(!owners[i + 1..].contains(owner), VaultError::InvalidOwnerSet);
+    }
+
+    Ok(())
+}
+
+// SetOwners proposals can also empty the owner set (threshold 0), which turns the vault back into a single-owner one
+pub fn validate_proposed_owner_set(owners: &[Pubkey], threshold: u8) -> Result<()> {
+    if owners.is_empty() && threshold == 0 {
+        return Ok(());
+    }
+
+    validate_owner_set(owners, threshold)
 }
 
 // =============================================================================
@@ -225,8 +1111,218 @@ impl<'info> Close<'info> {
 pub struct VaultState {
     pub vault_bump: u8,
     pub state_bump: u8,
+    pub unlock_timestamp: Option<i64>, // savings lock: no withdrawals before this unix timestamp
+    pub target_balance: Option<u64>,   // savings goal: no withdrawals until the vault holds this many lamports
+    pub penalty_bps: u16,              // early withdrawal penalty, 0 = early withdrawals are not allowed
+    pub beneficiary: Option<Pubkey>,   // who gets the early withdrawal penalty
+    pub mints: Vec<Pubkey>,            // every mint the vault has a token account for, up to MAX_VAULT_MINTS
+    pub owners: Vec<Pubkey>,           // m-of-n owner set, empty = plain single-owner vault
+    pub threshold: u8,                 // approvals needed to execute a proposal
+    pub delegates: Vec<DelegateAllowance>, // wallets that can withdraw SOL up to a per-period cap
+    pub proposal_count: u64,           // next proposal id
+    pub goal_reached: bool,            // set the first time the vault holds target_balance, never cleared by withdrawals
 }
 
-impl Space for VaultState {
+// the original bumps-only VaultState, only read by migrate_vault_state
+#[derive(AnchorDeserialize)]
+pub struct LegacyVaultState {
+    pub vault_bump: u8,
+    pub state_bump: u8,
+}
+
+impl Space for LegacyVaultState {
     const INIT_SPACE: usize = 8 + 1 + 1; // anchor discriminator + vault_bump + state_bump
 }
+
+pub const MAX_VAULT_MINTS: usize = 8;
+pub const MAX_VAULT_OWNERS: usize = 5;
+pub const MAX_VAULT_DELEGATES: usize = 4;
+
+impl Space for VaultState {
+    // anchor discriminator + vault_bump + state_bump + unlock_timestamp + target_balance + penalty_bps + beneficiary + mints
+    // + owners + threshold + delegates + proposal_count + goal_reached
+    const INIT_SPACE: usize = 8 + 1 + 1 + (1 + 8) + (1 + 8) + 2 + (1 + 32) + (4 + 32 * MAX_VAULT_MINTS)
+        + (4 + 32 * MAX_VAULT_OWNERS) + 1 + (4 + DelegateAllowance::SIZE * MAX_VAULT_DELEGATES) + 8 + 1;
+}
+
+impl VaultState {
+    pub fn is_multisig(&self) -> bool {
+        !self.owners.is_empty()
+    }
+
+    // add, update or (max_per_period = 0) remove a delegate
+    pub fn set_delegate(&mut self, delegate: Pubkey, max_per_period: u64, period_seconds: i64) -> Result<()> {
+        let existing = self.delegates.iter().position(|d| d.delegate == delegate);
+
+        if max_per_period == 0 {
+            let index = existing.ok_or(VaultError::NotADelegate)?;
+            self.delegates.remove(index);
+            return Ok(());
+        }
+
+        require!(period_seconds > 0, VaultError::InvalidAllowance);
+
+        match existing {
+            Some(index) => {
+                // changing the cap keeps what was already spent this period
+                let allowance = &mut self.delegates[index];
+                allowance.max_per_period = max_per_period;
+                allowance.period_seconds = period_seconds;
+            }
+            None => {
+                require!(self.delegates.len() < MAX_VAULT_DELEGATES, VaultError::TooManyDelegates);
+                self.delegates.push(DelegateAllowance {
+                    delegate,
+                    max_per_period,
+                    period_seconds,
+                    period_start: 0,
+                    spent_in_period: 0,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // remember that the goal was met, withdrawing afterwards must not lock the vault again
+    pub fn latch_goal(&mut self, balance: u64) {
+        if self.target_balance.is_some_and(|target| balance >= target) {
+            self.goal_reached = true;
+        }
+    }
+
+    // the lock is over as soon as either condition is met (time passed OR goal reached), whichever comes first
+    pub fn is_locked(&self, now: i64, balance: u64) -> bool {
+        let time_reached = self.unlock_timestamp.map(|t| now >= t);
+        let goal_reached = self
+            .target_balance
+            .map(|target| self.goal_reached || balance >= target);
+
+        match (time_reached, goal_reached) {
+            (None, None) => false,
+            (Some(time), None) => !time,
+            (None, Some(goal)) => !goal,
+            (Some(time), Some(goal)) => !(time || goal),
+        }
+    }
+
+    // how much of `amount` goes to the beneficiary, errors if the vault is locked and has no penalty option
+    pub fn early_withdrawal_penalty(&self, now: i64, balance: u64, amount: u64) -> Result<u64> {
+        if !self.is_locked(now, balance) {
+            return Ok(0);
+        }
+
+        require!(self.penalty_bps > 0, VaultError::VaultLocked);
+
+        // rounded up, so splitting an early exit into dust withdrawals can't get the penalty down to 0
+        let penalty = (amount as u128)
+            .checked_mul(self.penalty_bps as u128)
+            .ok_or(VaultError::MathOverflow)?
+            .div_ceil(10_000);
+
+        Ok(penalty as u64)
+    }
+}
+
+// "the bot can withdraw up to 1 SOL per day" = max_per_period 1 SOL, period_seconds 86400
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct DelegateAllowance {
+    pub delegate: Pubkey,
+    pub max_per_period: u64,   // lamports
+    pub period_seconds: i64,
+    pub period_start: i64,     // start of the current period, 0 = never used
+    pub spent_in_period: u64,  // lamports withdrawn since period_start
+}
+
+impl DelegateAllowance {
+    pub const SIZE: usize = 32 + 8 + 8 + 8 + 8;
+
+    pub fn spend(&mut self, now: i64, amount: u64) -> Result<()> {
+        // new period, start counting from zero again
+        if now >= self.period_start.saturating_add(self.period_seconds) {
+            self.period_start = now;
+            self.spent_in_period = 0;
+        }
+
+        let spent = self
+            .spent_in_period
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        require!(spent <= self.max_per_period, VaultError::AllowanceExceeded);
+
+        self.spent_in_period = spent;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum ProposalAction {
+    Withdraw { recipient: Pubkey, amount: u64 },
+    WithdrawToken { mint: Pubkey, recipient: Pubkey, amount: u64 }, // recipient is the wallet that owns the destination token account
+    SetOwners { owners: Vec<Pubkey>, threshold: u8 },
+    SetDelegate { delegate: Pubkey, max_per_period: u64, period_seconds: i64 },
+}
+
+impl ProposalAction {
+    // biggest variant is SetOwners: owners vec + threshold
+    pub const MAX_SIZE: usize = 1 + (4 + 32 * MAX_VAULT_OWNERS) + 1;
+}
+
+#[account]
+pub struct Proposal {
+    pub vault_state: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: ProposalAction,
+    pub approvals: Vec<Pubkey>, // owners that approved, the proposer is always the first one
+    pub bump: u8,
+}
+
+impl Space for Proposal {
+    // anchor discriminator + vault_state + id + proposer + action + approvals + bump
+    const INIT_SPACE: usize = 8 + 32 + 8 + 32 + ProposalAction::MAX_SIZE + (4 + 32 * MAX_VAULT_OWNERS) + 1;
+}
+
+// =============================================================================
+// ERRORS - What can go wrong
+// =============================================================================
+
+#[error_code]
+pub enum VaultError {
+    #[msg("Vault is locked until the unlock time or savings goal is reached")]
+    VaultLocked,
+    #[msg("Lock needs a future unlock time or a goal above the current balance, and penalty_bps <= 10000")]
+    InvalidLockConfig,
+    #[msg("Beneficiary is missing or does not match the vault's beneficiary")]
+    InvalidBeneficiary,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Vault already holds the maximum number of mints")]
+    TooManyMints,
+    #[msg("Close needs [mint, vault token account, owner token account, token program] for every vault mint")]
+    MissingTokenAccounts,
+    #[msg("This vault has an owner set, use a proposal instead")]
+    MultisigRequired,
+    #[msg("Owners must be 1 to 5 unique keys and the threshold between 1 and the number of owners")]
+    InvalidOwnerSet,
+    #[msg("Signer is not one of the vault owners")]
+    NotAnOwner,
+    #[msg("Signer is not a delegate of this vault")]
+    NotADelegate,
+    #[msg("Vault already has the maximum number of delegates")]
+    TooManyDelegates,
+    #[msg("Delegate allowance needs a period longer than zero seconds")]
+    InvalidAllowance,
+    #[msg("Withdrawal is over the delegate's allowance for this period")]
+    AllowanceExceeded,
+    #[msg("Proposal action is invalid")]
+    InvalidProposal,
+    #[msg("Owner already approved this proposal")]
+    AlreadyApproved,
+    #[msg("Proposal does not have enough approvals yet")]
+    NotEnoughApprovals,
+    #[msg("Recipient account is missing or does not match the proposal")]
+    InvalidRecipient,
+    #[msg("Vault state is not in the old bumps-only layout")]
+    InvalidVaultState,
+}
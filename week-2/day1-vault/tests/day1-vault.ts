@@ -1,16 +1,650 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Day1Vault } from "../target/types/day1_vault";
+import { expect } from "chai";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+  getMintLen,
+  getOrCreateAssociatedTokenAccount,
+  getTransferFeeConfig,
+  mintTo,
+} from "@solana/spl-token";
 
 describe("day1-vault", () => {
   // Configure the client to use the local cluster.
   anchor.setProvider(anchor.AnchorProvider.env());
 
   const program = anchor.workspace.day1Vault as Program<Day1Vault>;
+  const provider = anchor.getProvider();
+
+  // every scenario below gets its own wallet, and so its own vault
+  const newUser = async () => {
+    const user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      10 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+    await program.methods
+      .initialize()
+      .accounts({ signer: user.publicKey })
+      .signers([user])
+      .rpc();
+    return user;
+  };
 
   it("Is initialized!", async () => {
     // Add your test here.
     const tx = await program.methods.initialize().rpc();
     console.log("Your transaction signature", tx);
   });
+
+  it("Blocks withdrawals while the savings lock is running", async () => {
+    const oneHourFromNow = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+
+    await program.methods
+      .deposit(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL))
+      .rpc();
+    await program.methods.lockVault(oneHourFromNow, null, 0, null).rpc();
+
+    try {
+      await program.methods
+        .withdraw(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 2))
+        .accounts({ beneficiary: null })
+        .rpc();
+      expect.fail("withdraw should fail while the vault is locked");
+    } catch (err) {
+      expect(err.toString()).to.include("VaultLocked");
+    }
+  });
+
+  it("Stays unlocked after the savings goal is reached, even once the balance drops", async () => {
+    const user = await newUser();
+    const sol = anchor.web3.LAMPORTS_PER_SOL;
+
+    await program.methods
+      .deposit(new anchor.BN(sol))
+      .accounts({ signer: user.publicKey })
+      .signers([user])
+      .rpc();
+    await program.methods
+      .lockVault(null, new anchor.BN(2 * sol), 0, null)
+      .accounts({ signer: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    try {
+      await program.methods
+        .withdraw(new anchor.BN(sol / 2))
+        .accounts({ signer: user.publicKey, beneficiary: null })
+        .signers([user])
+        .rpc();
+      expect.fail("withdraw should fail before the goal is reached");
+    } catch (err) {
+      expect(err.toString()).to.include("VaultLocked");
+    }
+
+    await program.methods
+      .deposit(new anchor.BN(sol))
+      .accounts({ signer: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    // the second withdraw takes the balance back under the target, the vault must not lock again
+    for (let i = 0; i < 2; i++) {
+      await program.methods
+        .withdraw(new anchor.BN(sol / 2))
+        .accounts({ signer: user.publicKey, beneficiary: null })
+        .signers([user])
+        .rpc();
+    }
+
+    const [vaultState] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("state"), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const state = await program.account.vaultState.fetch(vaultState);
+    expect(state.goalReached).to.equal(true);
+  });
+
+  it("Only migrates vault states still in the old bumps-only layout", async () => {
+    const user = await newUser();
+
+    // a vault opened by this version is already in the current layout
+    try {
+      await program.methods
+        .migrateVaultState()
+        .accounts({ signer: user.publicKey })
+        .signers([user])
+        .rpc();
+      expect.fail("migrate should fail on a current vault state");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidVaultState");
+    }
+  });
+
+  it("Rounds the early withdrawal penalty up so dust withdrawals still pay it", async () => {
+    const user = await newUser();
+    const beneficiary = await newUser();
+    const oneHourFromNow = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+
+    await program.methods
+      .deposit(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL))
+      .accounts({ signer: user.publicKey })
+      .signers([user])
+      .rpc();
+    // 1% penalty, a 50 lamport withdrawal would round down to 0
+    await program.methods
+      .lockVault(oneHourFromNow, null, 100, beneficiary.publicKey)
+      .accounts({ signer: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    const before = await provider.connection.getBalance(beneficiary.publicKey);
+    await program.methods
+      .withdraw(new anchor.BN(50))
+      .accounts({ signer: user.publicKey, beneficiary: beneficiary.publicKey })
+      .signers([user])
+      .rpc();
+    const after = await provider.connection.getBalance(beneficiary.publicKey);
+
+    expect(after - before).to.equal(1);
+  });
+
+  // a mint with 1000 tokens in the user's ATA, on whichever token program
+  const newMint = async (
+    user: anchor.web3.Keypair,
+    tokenProgram: anchor.web3.PublicKey
+  ) => {
+    const mint = await createMint(
+      provider.connection,
+      user,
+      user.publicKey,
+      null,
+      6,
+      undefined,
+      undefined,
+      tokenProgram
+    );
+    const ata = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      user,
+      mint,
+      user.publicKey,
+      false,
+      undefined,
+      undefined,
+      tokenProgram
+    );
+    await mintTo(
+      provider.connection,
+      user,
+      mint,
+      ata.address,
+      user,
+      1000,
+      [],
+      undefined,
+      tokenProgram
+    );
+    return mint;
+  };
+
+  const vaultAddress = (user: anchor.web3.Keypair) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+  const tokenBalance = async (
+    owner: anchor.web3.PublicKey,
+    mint: anchor.web3.PublicKey,
+    tokenProgram: anchor.web3.PublicKey
+  ) => {
+    const ata = getAssociatedTokenAddressSync(mint, owner, true, tokenProgram);
+    const account = await getAccount(
+      provider.connection,
+      ata,
+      undefined,
+      tokenProgram
+    );
+    return Number(account.amount);
+  };
+
+  it("Deposits and withdraws SPL and Token-2022 tokens", async () => {
+    const user = await newUser();
+    const vault = vaultAddress(user);
+
+    for (const tokenProgram of [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID]) {
+      const mint = await newMint(user, tokenProgram);
+
+      await program.methods
+        .depositToken(new anchor.BN(500))
+        .accounts({ signer: user.publicKey, mint, tokenProgram })
+        .signers([user])
+        .rpc();
+      expect(await tokenBalance(vault, mint, tokenProgram)).to.equal(500);
+
+      await program.methods
+        .withdrawToken(new anchor.BN(200))
+        .accounts({ signer: user.publicKey, mint, tokenProgram })
+        .signers([user])
+        .rpc();
+      expect(await tokenBalance(vault, mint, tokenProgram)).to.equal(300);
+      expect(await tokenBalance(user.publicKey, mint, tokenProgram)).to.equal(
+        700
+      );
+    }
+  });
+
+  it("Sweeps every token account on close and rejects malformed groups", async () => {
+    const user = await newUser();
+    const other = await newUser();
+    const vault = vaultAddress(user);
+
+    const mints = [
+      { mint: await newMint(user, TOKEN_PROGRAM_ID), tokenProgram: TOKEN_PROGRAM_ID },
+      {
+        mint: await newMint(user, TOKEN_2022_PROGRAM_ID),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      },
+    ];
+    for (const { mint, tokenProgram } of mints) {
+      await program.methods
+        .depositToken(new anchor.BN(400))
+        .accounts({ signer: user.publicKey, mint, tokenProgram })
+        .signers([user])
+        .rpc();
+    }
+
+    // [mint, vault ATA, signer token account, token program] per tracked mint
+    const group = (
+      mint: anchor.web3.PublicKey,
+      tokenProgram: anchor.web3.PublicKey,
+      recipient: anchor.web3.PublicKey
+    ) => [
+      // writable, withheld transfer fees get harvested into it
+      { pubkey: mint, isSigner: false, isWritable: true },
+      {
+        pubkey: getAssociatedTokenAddressSync(mint, vault, true, tokenProgram),
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: getAssociatedTokenAddressSync(mint, recipient, true, tokenProgram),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: tokenProgram, isSigner: false, isWritable: false },
+    ];
+    const groups = (recipient: anchor.web3.PublicKey) =>
+      mints.flatMap(({ mint, tokenProgram }) =>
+        group(mint, tokenProgram, recipient)
+      );
+
+    // the second group is cut short
+    try {
+      await program.methods
+        .close()
+        .accounts({ signer: user.publicKey, beneficiary: null })
+        .remainingAccounts(groups(user.publicKey).slice(0, 7))
+        .signers([user])
+        .rpc();
+      expect.fail("close should fail with an incomplete group");
+    } catch (err) {
+      expect(err.toString()).to.include("MissingTokenAccounts");
+    }
+
+    // full groups, but the tokens would go to someone else's accounts
+    for (const { mint, tokenProgram } of mints) {
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        other,
+        mint,
+        other.publicKey,
+        false,
+        undefined,
+        undefined,
+        tokenProgram
+      );
+    }
+    try {
+      await program.methods
+        .close()
+        .accounts({ signer: user.publicKey, beneficiary: null })
+        .remainingAccounts(groups(other.publicKey))
+        .signers([user])
+        .rpc();
+      expect.fail("close should fail when the signer doesn't own the recipient");
+    } catch (err) {
+      expect(err.toString()).to.include("MissingTokenAccounts");
+    }
+
+    await program.methods
+      .close()
+      .accounts({ signer: user.publicKey, beneficiary: null })
+      .remainingAccounts(groups(user.publicKey))
+      .signers([user])
+      .rpc();
+
+    for (const { mint, tokenProgram } of mints) {
+      expect(await tokenBalance(user.publicKey, mint, tokenProgram)).to.equal(
+        1000
+      );
+      const vaultAta = getAssociatedTokenAddressSync(
+        mint,
+        vault,
+        true,
+        tokenProgram
+      );
+      expect(await provider.connection.getAccountInfo(vaultAta)).to.equal(null);
+    }
+  });
+
+  it("Harvests withheld transfer fees so a fee mint's vault account can close", async () => {
+    const user = await newUser();
+    const vault = vaultAddress(user);
+    const tokenProgram = TOKEN_2022_PROGRAM_ID;
+
+    // Token-2022 mint with a 1% transfer fee, 1000 tokens in the user's ATA
+    const mintKeypair = anchor.web3.Keypair.generate();
+    const mint = mintKeypair.publicKey;
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    await anchor.web3.sendAndConfirmTransaction(
+      provider.connection,
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.createAccount({
+          fromPubkey: user.publicKey,
+          newAccountPubkey: mint,
+          space: mintLen,
+          lamports:
+            await provider.connection.getMinimumBalanceForRentExemption(mintLen),
+          programId: tokenProgram,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          mint,
+          user.publicKey,
+          user.publicKey,
+          100,
+          BigInt(1_000_000),
+          tokenProgram
+        ),
+        createInitializeMintInstruction(mint, 6, user.publicKey, null, tokenProgram)
+      ),
+      [user, mintKeypair]
+    );
+    const userAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      user,
+      mint,
+      user.publicKey,
+      false,
+      undefined,
+      undefined,
+      tokenProgram
+    );
+    await mintTo(
+      provider.connection,
+      user,
+      mint,
+      userAta.address,
+      user,
+      1000,
+      [],
+      undefined,
+      tokenProgram
+    );
+
+    // 5 of the 500 stay withheld in the vault ATA
+    await program.methods
+      .depositToken(new anchor.BN(500))
+      .accounts({ signer: user.publicKey, mint, tokenProgram })
+      .signers([user])
+      .rpc();
+    expect(await tokenBalance(vault, mint, tokenProgram)).to.equal(495);
+
+    const vaultAta = getAssociatedTokenAddressSync(mint, vault, true, tokenProgram);
+    await program.methods
+      .close()
+      .accounts({ signer: user.publicKey, beneficiary: null })
+      .remainingAccounts([
+        { pubkey: mint, isSigner: false, isWritable: true },
+        { pubkey: vaultAta, isSigner: false, isWritable: true },
+        { pubkey: userAta.address, isSigner: false, isWritable: true },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
+      ])
+      .signers([user])
+      .rpc();
+
+    // the sweep of 495 pays a 5 token fee on the way out
+    expect(await tokenBalance(user.publicKey, mint, tokenProgram)).to.equal(990);
+    expect(await provider.connection.getAccountInfo(vaultAta)).to.equal(null);
+    const feeConfig = getTransferFeeConfig(
+      await getMint(provider.connection, mint, undefined, tokenProgram)
+    );
+    expect(Number(feeConfig.withheldAmount)).to.equal(5);
+  });
+
+  const stateAddress = (user: anchor.web3.Keypair) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("state"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+  // proposes `action` on the creator's vault and returns the proposal address
+  const propose = async (
+    creator: anchor.web3.Keypair,
+    proposer: anchor.web3.Keypair,
+    action: any
+  ) => {
+    const vaultState = stateAddress(creator);
+    const { proposalCount } = await program.account.vaultState.fetch(
+      vaultState
+    );
+    const [proposal] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("proposal"),
+        vaultState.toBuffer(),
+        proposalCount.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    await program.methods
+      .createProposal(action)
+      .accountsPartial({
+        proposer: proposer.publicKey,
+        creator: creator.publicKey,
+        vaultState,
+        proposal,
+      })
+      .signers([proposer])
+      .rpc();
+    return proposal;
+  };
+
+  const approve = (
+    creator: anchor.web3.Keypair,
+    proposal: anchor.web3.PublicKey,
+    owner: anchor.web3.Keypair
+  ) =>
+    program.methods
+      .approveProposal()
+      .accountsPartial({
+        owner: owner.publicKey,
+        vaultState: stateAddress(creator),
+        proposal,
+      })
+      .signers([owner])
+      .rpc();
+
+  const execute = (
+    creator: anchor.web3.Keypair,
+    proposal: anchor.web3.PublicKey,
+    executor: anchor.web3.Keypair,
+    proposer: anchor.web3.PublicKey,
+    extra: Record<string, anchor.web3.PublicKey | null> = {}
+  ) =>
+    program.methods
+      .executeProposal()
+      .accountsPartial({
+        executor: executor.publicKey,
+        creator: creator.publicKey,
+        vaultState: stateAddress(creator),
+        vault: vaultAddress(creator),
+        proposal,
+        proposer,
+        recipient: null,
+        beneficiary: null,
+        mint: null,
+        vaultTokenAccount: null,
+        recipientTokenAccount: null,
+        tokenProgram: null,
+        ...extra,
+      })
+      .signers([executor])
+      .rpc();
+
+  it("Lets the owners withdraw tokens and hand a multisig vault back to its creator", async () => {
+    const user = await newUser();
+    const alice = await newUser();
+    const bob = anchor.web3.Keypair.generate();
+    const vault = vaultAddress(user);
+    const mint = await newMint(user, TOKEN_PROGRAM_ID);
+
+    await program.methods
+      .depositToken(new anchor.BN(600))
+      .accounts({ signer: user.publicKey, mint, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([user])
+      .rpc();
+    await program.methods
+      .configureOwners([alice.publicKey, bob.publicKey], 2)
+      .accounts({ signer: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    // the creator key alone can't move tokens anymore
+    try {
+      await program.methods
+        .withdrawToken(new anchor.BN(100))
+        .accounts({ signer: user.publicKey, mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([user])
+        .rpc();
+      expect.fail("withdraw_token should fail on a multisig vault");
+    } catch (err) {
+      expect(err.toString()).to.include("MultisigRequired");
+    }
+
+    const aliceAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      alice,
+      mint,
+      alice.publicKey
+    );
+    const withdrawal = await propose(user, alice, {
+      withdrawToken: {
+        mint,
+        recipient: alice.publicKey,
+        amount: new anchor.BN(100),
+      },
+    });
+    await approve(user, withdrawal, bob);
+    await execute(user, withdrawal, alice, alice.publicKey, {
+      mint,
+      vaultTokenAccount: getAssociatedTokenAddressSync(mint, vault, true),
+      recipientTokenAccount: aliceAta.address,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+    expect(await tokenBalance(alice.publicKey, mint, TOKEN_PROGRAM_ID)).to.equal(
+      100
+    );
+
+    // an empty owner set gives the vault back, after which the creator can close it
+    const handBack = await propose(user, alice, {
+      setOwners: { owners: [], threshold: 0 },
+    });
+    await approve(user, handBack, bob);
+    await execute(user, handBack, alice, alice.publicKey);
+
+    await program.methods
+      .close()
+      .accounts({ signer: user.publicKey, beneficiary: null })
+      .remainingAccounts([
+        { pubkey: mint, isSigner: false, isWritable: true },
+        {
+          pubkey: getAssociatedTokenAddressSync(mint, vault, true),
+          isSigner: false,
+          isWritable: true,
+        },
+        {
+          pubkey: getAssociatedTokenAddressSync(mint, user.publicKey),
+          isSigner: false,
+          isWritable: true,
+        },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      ])
+      .signers([user])
+      .rpc();
+    expect(await tokenBalance(user.publicKey, mint, TOKEN_PROGRAM_ID)).to.equal(
+      900
+    );
+  });
+
+  it("Drops approvals from removed owners so new owners can still approve", async () => {
+    const user = await newUser();
+    const alice = await newUser();
+    const oldOwners = [...Array(4)].map(() => anchor.web3.Keypair.generate());
+    const newOwners = [...Array(4)].map(() => anchor.web3.Keypair.generate());
+
+    await program.methods
+      .deposit(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL))
+      .accounts({ signer: user.publicKey })
+      .signers([user])
+      .rpc();
+    await program.methods
+      .configureOwners(
+        [alice, ...oldOwners].map((owner) => owner.publicKey),
+        5
+      )
+      .accounts({ signer: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    // four approvals on a withdrawal, then three of those owners are replaced
+    const withdrawal = await propose(user, alice, {
+      withdraw: {
+        recipient: alice.publicKey,
+        amount: new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 10),
+      },
+    });
+    for (const owner of oldOwners.slice(0, 3)) {
+      await approve(user, withdrawal, owner);
+    }
+
+    const rotation = await propose(user, alice, {
+      setOwners: {
+        owners: [alice, ...newOwners].map((owner) => owner.publicKey),
+        threshold: 5,
+      },
+    });
+    for (const owner of oldOwners) {
+      await approve(user, rotation, owner);
+    }
+    await execute(user, rotation, alice, alice.publicKey);
+
+    // the withdrawal list is full of stale approvals, the new owners still fit
+    for (const owner of newOwners) {
+      await approve(user, withdrawal, owner);
+    }
+    await execute(user, withdrawal, alice, alice.publicKey, {
+      recipient: alice.publicKey,
+    });
+  });
 });
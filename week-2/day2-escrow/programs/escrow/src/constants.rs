@@ -2,3 +2,15 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+// most takers a private offer can be restricted to
+#[constant]
+pub const MAX_ALLOWED_TAKERS: u32 = 5;
+
+// open offers one OfferBook can index
+#[constant]
+pub const MAX_BOOK_ENTRIES: u32 = 32;
+
+// most (mint, amount) pairs on each side of a bundle offer
+#[constant]
+pub const MAX_BUNDLE_LEGS: u32 = 4;
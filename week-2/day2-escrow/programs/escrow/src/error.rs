@@ -10,4 +10,24 @@ pub enum ErrorCode {
     InvalidTokenMint,
     #[msg("Invalid amount")]
     InvalidAmount,
+    #[msg("Offer has expired")]
+    OfferExpired,
+    #[msg("Offer has not expired yet")]
+    OfferNotExpired,
+    #[msg("Expiry must be in the future")]
+    InvalidExpiry,
+    #[msg("Too many allowed takers")]
+    TooManyAllowedTakers,
+    #[msg("Taker is not allowed to take this offer")]
+    TakerNotAllowed,
+    #[msg("Fill amount is more than what is left in the offer")]
+    FillExceedsRemaining,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Offers do not cross")]
+    OffersDoNotCross,
+    #[msg("Bundle legs are empty, too many, zero or repeat a mint")]
+    InvalidBundle,
+    #[msg("Bundle accounts are missing or in the wrong order")]
+    InvalidBundleAccounts,
 }
@@ -0,0 +1,129 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_helpers::{amount_with_transfer_fee, init_ata_if_needed, transfer_tokens};
+use super::shared::load_bundle_mint;
+use crate::state::{BundleLeg, BundleOffer};
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::TokenInterface,
+};
+
+/*
+bundle offers trade up to MAX_BUNDLE_LEGS (mint, amount) pairs for up to MAX_BUNDLE_LEGS pairs.
+the number of mints isn't fixed, so their accounts come in through remaining_accounts,
+3 per offered leg, in the same order as `offered`:
+
+    [mint, maker token account, vault (ATA of the bundle offer PDA)]
+
+the vaults are created here, every mint has to belong to `token_program`. there is only one token program
+per bundle, load_bundle_mint rejects a mint owned by the other one, so a bundle can't mix SPL Token and
+Token-2022 mints. take and refund are called with the same token program the bundle was made with.
+*/
+
+pub const MAKE_BUNDLE_ACCOUNTS_PER_LEG: usize = 3;
+
+#[derive(Accounts)]
+#[instruction(id: u64, offered: Vec<BundleLeg>, wanted: Vec<BundleLeg>)]
+pub struct MakeBundleOffer<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        init,
+        payer = maker,
+        space = BundleOffer::DISCRIMINATOR.len() + BundleOffer::INIT_SPACE,
+        seeds = [b"bundle_offer", maker.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bundle_offer: Account<'info, BundleOffer>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+
+//=======================IMPLEMENT METHODS========================//
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MakeBundleOffer<'info>>,
+    id: u64,
+    offered: Vec<BundleLeg>,
+    wanted: Vec<BundleLeg>,
+) -> Result<()> {
+
+//=======================ERROR HANDLING========================//
+
+    // Validation: both sides non-empty, small enough, non-zero and no mint used twice
+    require!(
+        BundleOffer::legs_are_valid(&offered, &wanted),
+        ErrorCode::InvalidBundle
+    );
+
+    // Validation: one group of accounts per offered leg, nothing extra
+    require!(
+        ctx.remaining_accounts.len() == offered.len() * MAKE_BUNDLE_ACCOUNTS_PER_LEG,
+        ErrorCode::InvalidBundleAccounts
+    );
+
+//==========================TRANSFER TOKENS========================//
+
+    let maker = ctx.accounts.maker.to_account_info();
+    let bundle_offer = ctx.accounts.bundle_offer.to_account_info();
+
+    for (leg, accounts) in offered
+        .iter()
+        .zip(ctx.remaining_accounts.chunks(MAKE_BUNDLE_ACCOUNTS_PER_LEG))
+    {
+        let mint = load_bundle_mint(&accounts[0], leg, &ctx.accounts.token_program)?;
+        let maker_token_account = init_ata_if_needed(
+            &accounts[1],
+            &mint,
+            &maker,
+            &maker,
+            &ctx.accounts.token_program,
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.system_program,
+            ErrorCode::InvalidBundleAccounts,
+        )?;
+        let vault = init_ata_if_needed(
+            &accounts[2],
+            &mint,
+            &bundle_offer,
+            &maker,
+            &ctx.accounts.token_program,
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.system_program,
+            ErrorCode::InvalidBundleAccounts,
+        )?;
+
+        // same as make_offer: the maker pays any transfer fee so the vault holds exactly leg.amount
+        let deposit = amount_with_transfer_fee(&mint, leg.amount)?;
+        require!(
+            maker_token_account.amount >= deposit,
+            ErrorCode::InsufficientMakerBalance
+        );
+
+        transfer_tokens(
+            &maker_token_account,
+            &vault,
+            deposit,
+            &mint,
+            &maker,
+            &ctx.accounts.token_program,
+            None,
+        )?;
+    }
+
+    ctx.accounts.bundle_offer.set_inner(BundleOffer {
+        id,
+        maker: ctx.accounts.maker.key(),
+        offered,
+        wanted,
+        bump: ctx.bumps.bundle_offer,
+    });
+
+    Ok(())
+}
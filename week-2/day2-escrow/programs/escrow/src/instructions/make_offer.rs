@@ -1,7 +1,8 @@
 #![allow(unexpected_cfgs)]
 
-use super::shared::transfer_tokens;
-use crate::state::Offer;
+use anchor_helpers::{amount_with_transfer_fee, transfer_tokens};
+use crate::state::{Offer, OfferBook, OfferBookEntry};
+use crate::constants::MAX_ALLOWED_TAKERS;
 use crate::error::ErrorCode;
 use anchor_lang::prelude::*;
 use anchor_spl::{
@@ -37,6 +38,16 @@ pub struct MakeOffer<'info> {
     )]
     pub offer_details: Account<'info, Offer>, // this is where we save details of the entire offer we defined in offer.rs
 
+    // index of open offers for this mint pair, the first offer for a pair creates it
+    #[account(
+        init_if_needed,
+        payer = maker,
+        space = OfferBook::DISCRIMINATOR.len() + OfferBook::INIT_SPACE,
+        seeds = [b"offer_book", token_mint_a.key().as_ref(), token_mint_b.key().as_ref()],
+        bump
+    )]
+    pub offer_book: Account<'info, OfferBook>,
+
 
     #[account(
         init,
@@ -61,6 +72,8 @@ pub fn handler(
     id: u64, // unique id for the offer
     token_a_offered_amount: u64,
     token_b_wanted_amount: u64,
+    expires_at: Option<i64>, // None = offer never expires
+    allowed_takers: Vec<Pubkey>, // empty = public offer
 ) -> Result<()> {
 
 //=======================ERROR HANDLING========================//
@@ -83,13 +96,35 @@ pub fn handler(
         ErrorCode::InvalidAmount
     );
 
+    // Validation: an expiry has to be in the future, otherwise nobody could ever take the offer
+    if let Some(expires_at) = expires_at {
+        require!(
+            expires_at > Clock::get()?.unix_timestamp,
+            ErrorCode::InvalidExpiry
+        );
+    }
+
+    // Validation: the allowlist has to fit in the offer account
+    require!(
+        allowed_takers.len() <= MAX_ALLOWED_TAKERS as usize,
+        ErrorCode::TooManyAllowedTakers
+    );
+
+    // Validation: the maker can cover the deposit, including the transfer fee if token A has one
+    // so the vault ends up holding exactly token_a_offered_amount
+    let token_a_deposit = amount_with_transfer_fee(&ctx.accounts.token_mint_a, token_a_offered_amount)?;
+    require!(
+        ctx.accounts.maker_token_account_a.amount >= token_a_deposit,
+        ErrorCode::InsufficientMakerBalance
+    );
+
 //==========================TRANSFER TOKENS========================//
 
     // now we move the tokens from the maker's ATA account -----> vault
     transfer_tokens(  // transfer_tokens function from shared.rs, invokes the function.
         &ctx.accounts.maker_token_account_a,
         &ctx.accounts.vault,
-        &token_a_offered_amount,
+        token_a_deposit,
         &ctx.accounts.token_mint_a,
         &ctx.accounts.maker.to_account_info(),
         &ctx.accounts.token_program,
@@ -104,9 +139,30 @@ pub fn handler(
         maker: ctx.accounts.maker.key(),
         token_mint_a: ctx.accounts.token_mint_a.key(),
         token_mint_b: ctx.accounts.token_mint_b.key(),
+        token_a_offered_amount,
         token_b_wanted_amount,  // this is the amount of token B the maker wants to receive.
+        token_a_remaining: token_a_offered_amount, // nothing filled yet
+        token_b_received: 0,
+        expires_at,
+        allowed_takers,
         bump: ctx.bumps.offer_details,
     });
+
+    // list the offer in the book so takers/matchers can find it without knowing maker + id
+    let offer_book = &mut ctx.accounts.offer_book;
+    if offer_book.token_mint_a == Pubkey::default() {
+        offer_book.token_mint_a = ctx.accounts.token_mint_a.key();
+        offer_book.token_mint_b = ctx.accounts.token_mint_b.key();
+        offer_book.bump = ctx.bumps.offer_book;
+    }
+    let listed = offer_book.insert(
+        OfferBookEntry::from_offer(ctx.accounts.offer_details.key(), &ctx.accounts.offer_details),
+        Clock::get()?.unix_timestamp,
+    );
+    if !listed {
+        msg!("Offer book is full of better priced offers, this offer is open but not listed");
+    }
+
     Ok(())
 }
 
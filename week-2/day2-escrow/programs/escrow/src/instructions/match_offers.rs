@@ -0,0 +1,274 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_helpers::{close_token_account, harvest_withheld_fees, transfer_tokens};
+use crate::state::{Offer, OfferBook};
+
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+/*
+settles two crossing offers against each other, straight vault to maker, nobody else holds funds
+
+offer_1 sells token A for token B (ask = token B per token A)
+offer_2 sells token B for token A (bid = how much token B it gives per token A)
+
+they cross when offer_2's bid >= offer_1's ask. the trade happens at offer_1's price,
+offer_2 never pays more than its own limit. anyone can send this, e.g. a bot reading the two books.
+
+both legs come out of the vaults, so with a transfer fee mint the receiving maker gets the fill minus that fee
+(same as a taker receiving token A from the vault in take_offer)
+*/
+
+#[derive(Accounts)]
+#[instruction(id_1: u64, id_2: u64)]
+pub struct MatchOffers<'info> {
+    #[account(mut)]
+    pub matcher: Signer<'info>,
+
+/// CHECK: Validated through has_one contraint and PDA derivation using maker's key.
+    #[account(mut)]
+    pub maker_1: AccountInfo<'info>,
+
+/// CHECK: Validated through has_one contraint and PDA derivation using maker's key.
+    #[account(mut)]
+    pub maker_2: AccountInfo<'info>,
+
+    #[account(mut)] // writable so a transfer fee mint can take the fees withheld on a vault before it closes
+    pub token_mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub token_mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    // sells A for B
+    #[account(
+        mut,
+        constraint = offer_1.maker == maker_1.key() @ ErrorCode::OffersDoNotCross,
+        constraint = offer_1.token_mint_a == token_mint_a.key() @ ErrorCode::InvalidTokenMint,
+        constraint = offer_1.token_mint_b == token_mint_b.key() @ ErrorCode::InvalidTokenMint,
+        seeds = [b"offer", maker_1.key().as_ref(), id_1.to_le_bytes().as_ref()],
+        bump = offer_1.bump
+    )]
+    pub offer_1: Box<Account<'info, Offer>>,
+
+    // sells B for A
+    #[account(
+        mut,
+        constraint = offer_2.maker == maker_2.key() @ ErrorCode::OffersDoNotCross,
+        constraint = offer_2.token_mint_a == token_mint_b.key() @ ErrorCode::InvalidTokenMint,
+        constraint = offer_2.token_mint_b == token_mint_a.key() @ ErrorCode::InvalidTokenMint,
+        seeds = [b"offer", maker_2.key().as_ref(), id_2.to_le_bytes().as_ref()],
+        bump = offer_2.bump
+    )]
+    pub offer_2: Box<Account<'info, Offer>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = offer_1,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint_b,
+        associated_token::authority = offer_2,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_2: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // maker_1 gets token B
+    #[account(
+        init_if_needed,
+        payer = matcher,
+        associated_token::mint = token_mint_b,
+        associated_token::authority = maker_1,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_1_token_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // maker_2 gets token A
+    #[account(
+        init_if_needed,
+        payer = matcher,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = maker_2,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_2_token_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"offer_book", token_mint_a.key().as_ref(), token_mint_b.key().as_ref()],
+        bump = offer_book_ab.bump
+    )]
+    pub offer_book_ab: Box<Account<'info, OfferBook>>,
+
+    #[account(
+        mut,
+        seeds = [b"offer_book", token_mint_b.key().as_ref(), token_mint_a.key().as_ref()],
+        bump = offer_book_ba.bump
+    )]
+    pub offer_book_ba: Box<Account<'info, OfferBook>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+
+//=======================IMPLEMENT HANDLER METHODS========================//
+
+pub fn handler(
+    ctx: Context<MatchOffers>,
+    id_1: u64,
+    id_2: u64,
+) -> Result<()> {
+    let offer_1 = &ctx.accounts.offer_1;
+    let offer_2 = &ctx.accounts.offer_2;
+
+    // Validation: both offers have to be live, and each maker has to be allowed to take the other one
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        !offer_1.is_expired(now) && !offer_2.is_expired(now),
+        ErrorCode::OfferExpired
+    );
+    require!(
+        offer_1.can_be_taken_by(&offer_2.maker) && offer_2.can_be_taken_by(&offer_1.maker),
+        ErrorCode::TakerNotAllowed
+    );
+
+    // Validation: bid (o2 B for w2 A) >= ask (b1 B for a1 A)  ->  o2 * a1 >= b1 * w2
+    let bid = offer_2.token_a_offered_amount as u128 * offer_1.token_a_offered_amount as u128;
+    let ask = offer_1.token_b_wanted_amount as u128 * offer_2.token_b_wanted_amount as u128;
+    require!(bid >= ask, ErrorCode::OffersDoNotCross);
+
+    // how much A changes hands: all of offer_1, or as much as offer_2's remaining B buys at offer_1's price
+    let affordable_a = (offer_2.token_a_remaining as u128 * offer_1.token_a_offered_amount as u128)
+        .checked_div(offer_1.token_b_wanted_amount as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let token_a_amount = u64::try_from(affordable_a.min(offer_1.token_a_remaining as u128))
+        .map_err(|_| ErrorCode::MathOverflow)?;
+    require!(token_a_amount > 0, ErrorCode::OffersDoNotCross);
+
+    // B paid for it at offer_1's price. both sides use the running totals, so a match can pay 0 B
+    // when offer_1's earlier fills already rounded up past it
+    let token_b_amount = offer_1
+        .token_b_owed(token_a_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        token_b_amount <= offer_2.token_a_remaining,
+        ErrorCode::OffersDoNotCross
+    );
+
+    // offer_2 must get at least what its own price asks for that much B (guards the rounding)
+    let token_a_wanted_by_offer_2 = offer_2
+        .token_b_owed(token_b_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        token_a_wanted_by_offer_2 <= token_a_amount,
+        ErrorCode::OffersDoNotCross
+    );
+
+    let id_1_bytes = id_1.to_le_bytes();
+    let maker_1_key = ctx.accounts.maker_1.key();
+    let offer_1_seeds = &[
+        b"offer",
+        maker_1_key.as_ref(),
+        &id_1_bytes,
+        &[ctx.accounts.offer_1.bump]];
+    let offer_1_signer = Some(&offer_1_seeds[..]);
+
+    let id_2_bytes = id_2.to_le_bytes();
+    let maker_2_key = ctx.accounts.maker_2.key();
+    let offer_2_seeds = &[
+        b"offer",
+        maker_2_key.as_ref(),
+        &id_2_bytes,
+        &[ctx.accounts.offer_2.bump]];
+    let offer_2_signer = Some(&offer_2_seeds[..]);
+
+    // vault_1 (A) ----> maker_2
+    transfer_tokens(
+        &ctx.accounts.vault_1.to_account_info(),
+        &ctx.accounts.maker_2_token_account_a.to_account_info(),
+        token_a_amount,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.offer_1.to_account_info(),
+        &ctx.accounts.token_program,
+        offer_1_signer,
+    )?;
+
+    // vault_2 (B) ----> maker_1
+    transfer_tokens(
+        &ctx.accounts.vault_2.to_account_info(),
+        &ctx.accounts.maker_1_token_account_b.to_account_info(),
+        token_b_amount,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.offer_2.to_account_info(),
+        &ctx.accounts.token_program,
+        offer_2_signer,
+    )?;
+
+    // both offers count the match like a normal fill
+    let offer_1 = &mut ctx.accounts.offer_1;
+    offer_1.token_a_remaining = offer_1
+        .token_a_remaining
+        .checked_sub(token_a_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    offer_1.token_b_received = offer_1
+        .token_b_received
+        .checked_add(token_b_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let offer_2 = &mut ctx.accounts.offer_2;
+    offer_2.token_a_remaining = offer_2
+        .token_a_remaining
+        .checked_sub(token_b_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    offer_2.token_b_received = offer_2
+        .token_b_received
+        .checked_add(token_a_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let offer_1_key = ctx.accounts.offer_1.key();
+    let offer_1_remaining = ctx.accounts.offer_1.token_a_remaining;
+    ctx.accounts.offer_book_ab.update_remaining(&offer_1_key, offer_1_remaining);
+
+    let offer_2_key = ctx.accounts.offer_2.key();
+    let offer_2_remaining = ctx.accounts.offer_2.token_a_remaining;
+    ctx.accounts.offer_book_ba.update_remaining(&offer_2_key, offer_2_remaining);
+
+    // fully filled offers are closed, rent goes back to their makers
+    if offer_1_remaining == 0 {
+        harvest_withheld_fees(&ctx.accounts.vault_1.to_account_info(), &ctx.accounts.token_mint_a, &ctx.accounts.token_program)?;
+        close_token_account(
+            &ctx.accounts.vault_1.to_account_info(),
+            &ctx.accounts.maker_1.to_account_info(),
+            &ctx.accounts.offer_1.to_account_info(),
+            &ctx.accounts.token_program,
+            offer_1_signer,
+        )?;
+        ctx.accounts.offer_1.close(ctx.accounts.maker_1.to_account_info())?;
+    }
+
+    if offer_2_remaining == 0 {
+        harvest_withheld_fees(&ctx.accounts.vault_2.to_account_info(), &ctx.accounts.token_mint_b, &ctx.accounts.token_program)?;
+        close_token_account(
+            &ctx.accounts.vault_2.to_account_info(),
+            &ctx.accounts.maker_2.to_account_info(),
+            &ctx.accounts.offer_2.to_account_info(),
+            &ctx.accounts.token_program,
+            offer_2_signer,
+        )?;
+        ctx.accounts.offer_2.close(ctx.accounts.maker_2.to_account_info())?;
+    }
+
+    msg!("Matched {} token A against {} token B", token_a_amount, token_b_amount);
+
+    Ok(())
+}
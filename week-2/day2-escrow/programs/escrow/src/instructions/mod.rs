@@ -1,10 +1,22 @@
+#![allow(ambiguous_glob_reexports)] // every instruction file exports its own handler
+
 pub mod shared;
 pub mod make_offer;
 pub mod take_offer;
 pub mod refund_offer;
+pub mod refund_expired_offer;
+pub mod match_offers;
+pub mod make_bundle_offer;
+pub mod take_bundle_offer;
+pub mod refund_bundle_offer;
 
 pub use shared::*;
 pub use make_offer::*;
 pub use take_offer::*;
 pub use refund_offer::*;
+pub use refund_expired_offer::*;
+pub use match_offers::*;
+pub use make_bundle_offer::*;
+pub use take_bundle_offer::*;
+pub use refund_bundle_offer::*;
 
@@ -0,0 +1,119 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_helpers::{close_token_account, harvest_withheld_fees, init_ata_if_needed, transfer_tokens};
+use super::shared::load_bundle_mint;
+use crate::state::BundleOffer;
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::TokenInterface,
+};
+
+/*
+maker cancels a bundle, every vault is emptied back to them and closed in the same transaction.
+remaining_accounts, 3 per offered leg in the offer's order:
+
+    [mint (writable), vault, maker token account]
+
+the mint is writable so a transfer fee mint can take the fees withheld on the vault before it closes.
+every mint belongs to `token_program`, the one the bundle was made with (see make_bundle_offer).
+*/
+
+pub const REFUND_BUNDLE_ACCOUNTS_PER_LEG: usize = 3;
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct RefundBundleOffer<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        seeds = [b"bundle_offer", maker.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump = bundle_offer.bump
+    )]
+    pub bundle_offer: Account<'info, BundleOffer>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+
+//=======================IMPLEMENT HANDLER METHODS========================//
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RefundBundleOffer<'info>>,
+    id: u64,
+) -> Result<()> {
+    let offered = ctx.accounts.bundle_offer.offered.clone();
+
+    // Validation: one group of accounts per offered leg, nothing extra
+    require!(
+        ctx.remaining_accounts.len() == offered.len() * REFUND_BUNDLE_ACCOUNTS_PER_LEG,
+        ErrorCode::InvalidBundleAccounts
+    );
+
+    let id_bytes = id.to_le_bytes();
+    let maker_key = ctx.accounts.maker.key();
+    let bundle_offer_seeds = &[
+        b"bundle_offer",
+        maker_key.as_ref(),
+        &id_bytes,
+        &[ctx.accounts.bundle_offer.bump]];
+    let signers_seeds = Some(&bundle_offer_seeds[..]);
+
+    let maker = ctx.accounts.maker.to_account_info();
+    let bundle_offer = ctx.accounts.bundle_offer.to_account_info();
+
+    for (leg, accounts) in offered
+        .iter()
+        .zip(ctx.remaining_accounts.chunks(REFUND_BUNDLE_ACCOUNTS_PER_LEG))
+    {
+        let mint = load_bundle_mint(&accounts[0], leg, &ctx.accounts.token_program)?;
+        let vault = init_ata_if_needed(
+            &accounts[1],
+            &mint,
+            &bundle_offer,
+            &maker,
+            &ctx.accounts.token_program,
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.system_program,
+            ErrorCode::InvalidBundleAccounts,
+        )?;
+        let maker_token_account = init_ata_if_needed(
+            &accounts[2],
+            &mint,
+            &maker,
+            &maker,
+            &ctx.accounts.token_program,
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.system_program,
+            ErrorCode::InvalidBundleAccounts,
+        )?;
+
+        transfer_tokens(
+            &vault,
+            &maker_token_account,
+            vault.amount,
+            &mint,
+            &bundle_offer,
+            &ctx.accounts.token_program,
+            signers_seeds,
+        )?;
+
+        harvest_withheld_fees(&vault, &mint, &ctx.accounts.token_program)?;
+        close_token_account(
+            &vault,
+            &maker,
+            &bundle_offer,
+            &ctx.accounts.token_program,
+            signers_seeds,
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_helpers::{close_token_account, harvest_withheld_fees, transfer_tokens};
+use crate::state::{Offer, OfferBook};
+
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+// same as refund_offer, but anyone can send it once the offer is expired.
+// the tokens and all the rent still go back to the maker, the caller only pays the fee
+// (and the maker's ATA rent if the maker closed it in the meantime).
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct RefundExpiredOffer<'info> {
+
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+/// CHECK: Validated through has_one contraint and PDA derivation using maker's key.
+    #[account(mut)]
+    pub maker: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_token_account_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = token_mint_a,
+        seeds = [b"offer", maker.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub offer_details: Account<'info, Offer>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = offer_details,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"offer_book", token_mint_a.key().as_ref(), offer_details.token_mint_b.as_ref()],
+        bump = offer_book.bump
+    )]
+    pub offer_book: Account<'info, OfferBook>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+
+}
+
+
+//=======================IMPLEMENT HANDLER METHODS========================//
+
+pub fn handler(
+    ctx: Context<RefundExpiredOffer>,
+    id: u64,
+)   -> Result <()> {
+    // only expired offers can be cranked, before that it's the maker's call
+    require!(
+        ctx.accounts.offer_details.is_expired(Clock::get()?.unix_timestamp),
+        ErrorCode::OfferNotExpired
+    );
+
+    let id_bytes = id.to_le_bytes();
+    let maker_key = ctx.accounts.maker.key();
+    let offer_account_seeds =
+        &[b"offer",
+        maker_key.as_ref(),
+        &id_bytes,
+        &[ctx.accounts.offer_details.bump]];
+
+    let signers_seeds = Some(&offer_account_seeds[..]);
+
+    // the offer is gone after this, take it out of the book
+    let offer_key = ctx.accounts.offer_details.key();
+    ctx.accounts.offer_book.remove(&offer_key);
+
+    // whatever wasn't filled goes back to the maker
+    transfer_tokens(
+        &ctx.accounts.vault,
+        &ctx.accounts.maker_token_account_a,
+        ctx.accounts.vault.amount,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.offer_details.to_account_info(),
+        &ctx.accounts.token_program,
+        signers_seeds,
+    )?;
+
+    harvest_withheld_fees(&ctx.accounts.vault, &ctx.accounts.token_mint_a, &ctx.accounts.token_program)?;
+    close_token_account(
+        &ctx.accounts.vault,
+        &ctx.accounts.maker.to_account_info(),
+        &ctx.accounts.offer_details.to_account_info(),
+        &ctx.accounts.token_program,
+        signers_seeds,
+    )?;
+
+    Ok(())
+}
@@ -1,8 +1,8 @@
 #![allow(unexpected_cfgs)]
 #![allow(unused_imports)]
 
-use super::shared::{transfer_tokens, close_token_account};
-use crate::state::Offer;
+use anchor_helpers::{close_token_account, harvest_withheld_fees, transfer_tokens};
+use crate::state::{Offer, OfferBook};
 
 use crate::error::ErrorCode;
 use anchor_lang::prelude::*;
@@ -47,6 +47,13 @@ pub struct RefundOffer<'info> {
         associated_token::token_program = token_program,
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"offer_book", token_mint_a.key().as_ref(), offer_details.token_mint_b.as_ref()],
+        bump = offer_book.bump
+    )]
+    pub offer_book: Account<'info, OfferBook>,
     
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
@@ -70,14 +77,18 @@ pub fn handler(
         &id_bytes,
         &[ctx.accounts.offer_details.bump]];
 
-    let signers_seeds = Some(&offer_account_seeds[..]);    
+    let signers_seeds = Some(&offer_account_seeds[..]);
+
+    // the offer is gone after this, take it out of the book
+    let offer_key = ctx.accounts.offer_details.key();
+    ctx.accounts.offer_book.remove(&offer_key);    
 
 
     // first, transfer tokens from the vault to the maker
     transfer_tokens(
         &ctx.accounts.vault,
         &ctx.accounts.maker_token_account_a,
-        &ctx.accounts.vault.amount,
+        ctx.accounts.vault.amount,
         &ctx.accounts.token_mint_a,
         &ctx.accounts.offer_details.to_account_info(),
         &ctx.accounts.token_program,
@@ -85,7 +96,8 @@ pub fn handler(
     )?;
 
     // then, close the vault account since the escrow is done
-     close_token_account(
+    harvest_withheld_fees(&ctx.accounts.vault, &ctx.accounts.token_mint_a, &ctx.accounts.token_program)?;
+    close_token_account(
         &ctx.accounts.vault,
         &ctx.accounts.maker.to_account_info(),
         &ctx.accounts.offer_details.to_account_info(),
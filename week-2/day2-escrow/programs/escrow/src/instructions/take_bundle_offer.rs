@@ -0,0 +1,175 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_helpers::{amount_with_transfer_fee, close_token_account, harvest_withheld_fees, init_ata_if_needed, transfer_tokens};
+use super::shared::load_bundle_mint;
+use crate::state::BundleOffer;
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::TokenInterface,
+};
+
+/*
+takes a whole bundle in one go, either every leg moves or the transaction fails and nothing does.
+remaining_accounts, 3 per leg, offered legs first then wanted legs, each in the offer's order:
+
+    offered: [mint (writable), vault, taker token account]        vault ----> taker, then the vault is closed
+    wanted:  [mint, taker token account, maker token account]   taker ----> maker
+
+receiving ATAs that don't exist yet are created, the taker pays the rent. offered mints are writable so a
+transfer fee mint can take the fees withheld on its vault before the vault closes.
+every mint belongs to `token_program`, the one the bundle was made with (see make_bundle_offer).
+*/
+
+pub const TAKE_BUNDLE_ACCOUNTS_PER_LEG: usize = 3;
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct TakeBundleOffer<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+/// CHECK: Validated through has_one contraint and PDA derivation using maker's key.
+    #[account(mut)]
+    pub maker: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        seeds = [b"bundle_offer", maker.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump = bundle_offer.bump
+    )]
+    pub bundle_offer: Account<'info, BundleOffer>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+
+//=======================IMPLEMENT HANDLER METHODS========================//
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, TakeBundleOffer<'info>>,
+    id: u64,
+) -> Result<()> {
+    let offered = ctx.accounts.bundle_offer.offered.clone();
+    let wanted = ctx.accounts.bundle_offer.wanted.clone();
+
+    // Validation: one group of accounts per leg, nothing extra
+    require!(
+        ctx.remaining_accounts.len() == (offered.len() + wanted.len()) * TAKE_BUNDLE_ACCOUNTS_PER_LEG,
+        ErrorCode::InvalidBundleAccounts
+    );
+    let (offered_accounts, wanted_accounts) = ctx
+        .remaining_accounts
+        .split_at(offered.len() * TAKE_BUNDLE_ACCOUNTS_PER_LEG);
+
+    let id_bytes = id.to_le_bytes();
+    let maker_key = ctx.accounts.maker.key();
+    let bundle_offer_seeds = &[
+        b"bundle_offer",
+        maker_key.as_ref(),
+        &id_bytes,
+        &[ctx.accounts.bundle_offer.bump]];
+    let signers_seeds = Some(&bundle_offer_seeds[..]);
+
+    let taker = ctx.accounts.taker.to_account_info();
+    let maker = ctx.accounts.maker.to_account_info();
+    let bundle_offer = ctx.accounts.bundle_offer.to_account_info();
+
+    // taker ----> maker, the taker pays any transfer fee so the maker gets exactly what the bundle asks for
+    for (leg, accounts) in wanted
+        .iter()
+        .zip(wanted_accounts.chunks(TAKE_BUNDLE_ACCOUNTS_PER_LEG))
+    {
+        let mint = load_bundle_mint(&accounts[0], leg, &ctx.accounts.token_program)?;
+        let taker_token_account = init_ata_if_needed(
+            &accounts[1],
+            &mint,
+            &taker,
+            &taker,
+            &ctx.accounts.token_program,
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.system_program,
+            ErrorCode::InvalidBundleAccounts,
+        )?;
+        let maker_token_account = init_ata_if_needed(
+            &accounts[2],
+            &mint,
+            &maker,
+            &taker,
+            &ctx.accounts.token_program,
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.system_program,
+            ErrorCode::InvalidBundleAccounts,
+        )?;
+
+        let payment = amount_with_transfer_fee(&mint, leg.amount)?;
+        require!(
+            taker_token_account.amount >= payment,
+            ErrorCode::InsufficientTakerBalance
+        );
+
+        transfer_tokens(
+            &taker_token_account,
+            &maker_token_account,
+            payment,
+            &mint,
+            &taker,
+            &ctx.accounts.token_program,
+            None,
+        )?;
+    }
+
+    // vaults ----> taker, then the vault rent goes back to the maker
+    for (leg, accounts) in offered
+        .iter()
+        .zip(offered_accounts.chunks(TAKE_BUNDLE_ACCOUNTS_PER_LEG))
+    {
+        let mint = load_bundle_mint(&accounts[0], leg, &ctx.accounts.token_program)?;
+        let vault = init_ata_if_needed(
+            &accounts[1],
+            &mint,
+            &bundle_offer,
+            &taker,
+            &ctx.accounts.token_program,
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.system_program,
+            ErrorCode::InvalidBundleAccounts,
+        )?;
+        let taker_token_account = init_ata_if_needed(
+            &accounts[2],
+            &mint,
+            &taker,
+            &taker,
+            &ctx.accounts.token_program,
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.system_program,
+            ErrorCode::InvalidBundleAccounts,
+        )?;
+
+        transfer_tokens(
+            &vault,
+            &taker_token_account,
+            vault.amount,
+            &mint,
+            &bundle_offer,
+            &ctx.accounts.token_program,
+            signers_seeds,
+        )?;
+
+        harvest_withheld_fees(&vault, &mint, &ctx.accounts.token_program)?;
+        close_token_account(
+            &vault,
+            &maker,
+            &bundle_offer,
+            &ctx.accounts.token_program,
+            signers_seeds,
+        )?;
+    }
+
+    Ok(())
+}
@@ -1,8 +1,8 @@
 #![allow(unexpected_cfgs)]
 #![allow(unused_imports)]
 
-use super::shared::{transfer_tokens, close_token_account};
-use crate::state::Offer;
+use anchor_helpers::{amount_with_transfer_fee, close_token_account, harvest_withheld_fees, transfer_tokens};
+use crate::state::{Offer, OfferBook};
 
 use crate::error::ErrorCode;
 use anchor_lang::prelude::*;
@@ -58,11 +58,12 @@ pub struct TakeOffer<'info> {
     )]
     pub maker_token_account_b: InterfaceAccount<'info, TokenAccount>,
 
+    // no close = maker here, a partial fill keeps the offer open. the handler closes it on the last fill
     #[account(
         mut,
-        close = maker,
         has_one = maker, // makes sure that Alice's pubkey is the same one she provided in her offer details. the has_one constraint is verifying the .key() method call 
-        has_one = token_mint_b, // makes sure that token_mint_b is the same one that Alice provided in her offer.
+        has_one = token_mint_a @ ErrorCode::InvalidTokenMint, // makes sure that token_mint_a is the one sitting in the vault.
+        has_one = token_mint_b @ ErrorCode::InvalidTokenMint, // makes sure that token_mint_b is the same one that Alice provided in her offer.
         seeds = [b"offer", maker.key().as_ref(), id.to_le_bytes().as_ref()], // to_le_bytes() turns numbers into little-endian format bytes.
         bump
     )]
@@ -76,6 +77,13 @@ pub struct TakeOffer<'info> {
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>, // vault holds Alice's tokens here, hence mint_a, controlled by program PDA offer_details
 
+    #[account(
+        mut,
+        seeds = [b"offer_book", token_mint_a.key().as_ref(), token_mint_b.key().as_ref()],
+        bump = offer_book.bump
+    )]
+    pub offer_book: Account<'info, OfferBook>,
+
 
     
 
@@ -95,7 +103,42 @@ pub struct TakeOffer<'info> {
 pub fn handler(
     ctx: Context<TakeOffer>, // IMPORTANT: all accounts are bundled into Context<TakeOffer>, and then referred to by handlers e.g ctx.accounts.maker_token_account_a, etc.
     id: u64,
+    token_a_amount: u64,
 ) -> Result<()> {
+    let offer = &ctx.accounts.offer_details;
+
+    // Validation: expired offers can only be refunded
+    require!(
+        !offer.is_expired(Clock::get()?.unix_timestamp),
+        ErrorCode::OfferExpired
+    );
+
+    // Validation: private offers can only be taken by the allowlisted takers
+    require!(
+        offer.can_be_taken_by(&ctx.accounts.taker.key()),
+        ErrorCode::TakerNotAllowed
+    );
+
+    // Validation: fill something, but not more than what's left
+    require!(token_a_amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        token_a_amount <= offer.token_a_remaining,
+        ErrorCode::FillExceedsRemaining
+    );
+
+    // price is fixed by the maker: token_b_wanted_amount for token_a_offered_amount.
+    // can be 0 when an earlier fill's rounding already paid for this one, the maker never gets less than the ratio
+    let token_b_amount = offer
+        .token_b_owed(token_a_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // Validation: the taker pays the transfer fee on top (if token B has one), Alice receives exactly token_b_amount
+    let token_b_payment = amount_with_transfer_fee(&ctx.accounts.token_mint_b, token_b_amount)?;
+    require!(
+        ctx.accounts.taker_token_account_b.amount >= token_b_payment,
+        ErrorCode::InsufficientTakerBalance
+    );
+
     let id_bytes = id.to_le_bytes();
     let maker_key = ctx.accounts.maker.key();
     let offer_account_seeds = 
@@ -112,7 +155,7 @@ pub fn handler(
     transfer_tokens(
         &ctx.accounts.taker_token_account_b,
         &ctx.accounts.maker_token_account_b,
-        &ctx.accounts.offer_details.token_b_wanted_amount,
+        token_b_payment,
         &ctx.accounts.token_mint_b,
         &ctx.accounts.taker.to_account_info(),
         &ctx.accounts.token_program,
@@ -124,13 +167,34 @@ pub fn handler(
     transfer_tokens(
         &ctx.accounts.vault,
         &ctx.accounts.taker_token_account_a,
-        &ctx.accounts.vault.amount,
+        token_a_amount,
         &ctx.accounts.token_mint_a,
         &ctx.accounts.offer_details.to_account_info(),
         &ctx.accounts.token_program,
         signers_seeds,
     )?;
 
+    let offer = &mut ctx.accounts.offer_details;
+    offer.token_a_remaining = offer
+        .token_a_remaining
+        .checked_sub(token_a_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    offer.token_b_received = offer
+        .token_b_received
+        .checked_add(token_b_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    ctx.accounts
+        .offer_book
+        .update_remaining(&offer.key(), offer.token_a_remaining);
+
+    // partial fill, the offer stays open for the rest
+    if offer.token_a_remaining > 0 {
+        return Ok(());
+    }
+
+    // fully filled: close the vault and the offer, all rent goes back to the maker
+    harvest_withheld_fees(&ctx.accounts.vault, &ctx.accounts.token_mint_a, &ctx.accounts.token_program)?;
     close_token_account(
         &ctx.accounts.vault,
         &ctx.accounts.maker.to_account_info(),
@@ -139,5 +203,7 @@ pub fn handler(
          signers_seeds,
         
     )?;
+
+    ctx.accounts.offer_details.close(ctx.accounts.maker.to_account_info())?;
     Ok(())
 }
\ No newline at end of file
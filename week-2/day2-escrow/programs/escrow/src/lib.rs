@@ -1,4 +1,5 @@
 #![allow(unexpected_cfgs)]
+#![allow(deprecated)] // anchor 0.31 #[program] still calls AccountInfo::realloc
 
 pub mod constants;
 pub mod error;
@@ -25,24 +26,29 @@ pub mod escrow {
         ctx: Context<MakeOffer>, 
         id: u64, 
         token_a_offered_amount: u64, 
-        token_b_wanted_amount: u64
+        token_b_wanted_amount: u64,
+        expires_at: Option<i64>,
+        allowed_takers: Vec<Pubkey>,
     ) -> Result<()> {
         make_offer::handler(
             ctx,
             id, 
             token_a_offered_amount,
             token_b_wanted_amount,
+            expires_at,
+            allowed_takers,
         )
     }
 
     pub fn take_offer(
         ctx: Context<TakeOffer>, 
         id: u64,
-        
+        token_a_amount: u64, // how much of the offer to fill, the full remaining amount closes it
     ) -> Result<()> {
         take_offer::handler(
             ctx,
             id,
+            token_a_amount,
         )
     }
 
@@ -56,5 +62,61 @@ pub mod escrow {
         )
     }
 
+    pub fn refund_expired_offer(
+        ctx: Context<RefundExpiredOffer>,
+        id: u64,
+    ) -> Result<()> {
+        refund_expired_offer::handler(
+            ctx,
+            id,
+        )
+    }
+
+    pub fn match_offers(
+        ctx: Context<MatchOffers>,
+        id_1: u64,
+        id_2: u64,
+    ) -> Result<()> {
+        match_offers::handler(
+            ctx,
+            id_1,
+            id_2,
+        )
+    }
+
+    pub fn make_bundle_offer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MakeBundleOffer<'info>>,
+        id: u64,
+        offered: Vec<BundleLeg>, // up to MAX_BUNDLE_LEGS (mint, amount) pairs put up by the maker
+        wanted: Vec<BundleLeg>,  // up to MAX_BUNDLE_LEGS (mint, amount) pairs asked in return
+    ) -> Result<()> {
+        make_bundle_offer::handler(
+            ctx,
+            id,
+            offered,
+            wanted,
+        )
+    }
+
+    pub fn take_bundle_offer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TakeBundleOffer<'info>>,
+        id: u64,
+    ) -> Result<()> {
+        take_bundle_offer::handler(
+            ctx,
+            id,
+        )
+    }
+
+    pub fn refund_bundle_offer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefundBundleOffer<'info>>,
+        id: u64,
+    ) -> Result<()> {
+        refund_bundle_offer::handler(
+            ctx,
+            id,
+        )
+    }
+
 }
 
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_BUNDLE_LEGS;
+
+// one (mint, amount) pair of a bundle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct BundleLeg {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+// like Offer, but several mints on each side. all or nothing: no partial fills
+#[account]
+#[derive(InitSpace)]
+pub struct BundleOffer {
+    pub id: u64,
+    pub maker: Pubkey,
+    #[max_len(MAX_BUNDLE_LEGS)]
+    pub offered: Vec<BundleLeg>, // sits in one vault per mint, owned by this account
+    #[max_len(MAX_BUNDLE_LEGS)]
+    pub wanted: Vec<BundleLeg>,  // paid by the taker straight to the maker
+    pub bump: u8,
+}
+
+impl BundleOffer {
+    // a side has to have at least one leg, fit in the account and not repeat a mint,
+    // and no mint can be on both sides (same rule as token_mint_a != token_mint_b)
+    pub fn legs_are_valid(offered: &[BundleLeg], wanted: &[BundleLeg]) -> bool {
+        let side_is_valid = |legs: &[BundleLeg]| {
+            !legs.is_empty()
+                && legs.len() <= MAX_BUNDLE_LEGS as usize
+                && legs.iter().all(|leg| leg.amount > 0)
+                && legs
+                    .iter()
+                    .enumerate()
+                    .all(|(i, leg)| legs[..i].iter().all(|other| other.mint != leg.mint))
+        };
+
+        side_is_valid(offered)
+            && side_is_valid(wanted)
+            && offered
+                .iter()
+                .all(|leg| wanted.iter().all(|other| other.mint != leg.mint))
+    }
+}
@@ -1,2 +1,6 @@
 pub mod offer;
+pub mod offer_book;
+pub mod bundle_offer;
 pub use offer::*;
+pub use offer_book::*;
+pub use bundle_offer::*;
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::MAX_ALLOWED_TAKERS;
+
 #[account]
 #[derive(InitSpace)]
 pub struct Offer {
@@ -7,6 +9,38 @@ pub struct Offer {
     pub maker: Pubkey,
     pub token_mint_a: Pubkey,
     pub token_mint_b: Pubkey,
+    pub token_a_offered_amount: u64, // total token A put up, fixes the price together with token_b_wanted_amount
     pub token_b_wanted_amount: u64,
+    pub token_a_remaining: u64,      // token A still in the vault, goes down with every partial fill
+    pub token_b_received: u64,       // token B the maker got so far, the last fill pays exactly the rest
+    pub expires_at: Option<i64>,     // after this anyone can crank a refund back to the maker
+    #[max_len(MAX_ALLOWED_TAKERS)]
+    pub allowed_takers: Vec<Pubkey>, // empty = anyone can take, otherwise only these takers (OTC deals)
     pub bump: u8,
-}
\ No newline at end of file
+}
+
+impl Offer {
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    pub fn can_be_taken_by(&self, taker: &Pubkey) -> bool {
+        self.allowed_takers.is_empty() || self.allowed_takers.contains(taker)
+    }
+
+    // token B owed for taking `token_a_amount`, always at the maker's fixed ratio.
+    // works on the running total: the maker is owed ceil(filled * wanted / offered) after this fill, minus what
+    // earlier fills paid. rounding each fill up on its own overpaid the maker, and the last fill then underflowed
+    pub fn token_b_owed(&self, token_a_amount: u64) -> Option<u64> {
+        let remaining_after = self.token_a_remaining.checked_sub(token_a_amount)?;
+        let filled_after = self.token_a_offered_amount.checked_sub(remaining_after)?;
+
+        let numerator = (filled_after as u128).checked_mul(self.token_b_wanted_amount as u128)?;
+        let denominator = self.token_a_offered_amount as u128;
+        let owed_so_far = numerator
+            .checked_add(denominator.checked_sub(1)?)?
+            .checked_div(denominator)?;
+
+        u64::try_from(owed_so_far).ok()?.checked_sub(self.token_b_received)
+    }
+}
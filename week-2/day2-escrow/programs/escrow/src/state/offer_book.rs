@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_BOOK_ENTRIES;
+use crate::state::Offer;
+
+// one book per direction: [b"offer_book", token_mint_a, token_mint_b] lists the open offers
+// that sell token A for token B. the crossing side lives in the [token_mint_b, token_mint_a] book.
+// the book is only an index and it is best-effort: once it is full, expired and then the worst priced
+// entries make room, so a pile of dust offers can't keep new offers from being made.
+#[account]
+#[derive(InitSpace)]
+pub struct OfferBook {
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    #[max_len(MAX_BOOK_ENTRIES)]
+    pub entries: Vec<OfferBookEntry>, // sorted by price (token B per token A), cheapest first
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct OfferBookEntry {
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub id: u64,
+    pub token_a_offered_amount: u64, // price = token_b_wanted_amount / token_a_offered_amount
+    pub token_b_wanted_amount: u64,
+    pub token_a_remaining: u64,
+    pub expires_at: Option<i64>,
+}
+
+impl OfferBookEntry {
+    pub fn from_offer(offer_key: Pubkey, offer: &Offer) -> Self {
+        Self {
+            offer: offer_key,
+            maker: offer.maker,
+            id: offer.id,
+            token_a_offered_amount: offer.token_a_offered_amount,
+            token_b_wanted_amount: offer.token_b_wanted_amount,
+            token_a_remaining: offer.token_a_remaining,
+            expires_at: offer.expires_at,
+        }
+    }
+
+    // compares prices without dividing: b1/a1 vs b2/a2 -> b1*a2 vs b2*a1
+    fn price_cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let left = self.token_b_wanted_amount as u128 * other.token_a_offered_amount as u128;
+        let right = other.token_b_wanted_amount as u128 * self.token_a_offered_amount as u128;
+        left.cmp(&right)
+    }
+}
+
+impl OfferBook {
+    // goes after every offer with the same price, so older offers keep priority.
+    // a full book drops an expired entry, or else its worst priced one if the new entry beats it.
+    // returns false when the new entry is the one left out, the offer itself is still open
+    pub fn insert(&mut self, entry: OfferBookEntry, now: i64) -> bool {
+        if self.entries.len() >= MAX_BOOK_ENTRIES as usize {
+            if let Some(expired) = self.entries.iter().position(|e| e.expires_at.is_some_and(|expires_at| now >= expires_at)) {
+                self.entries.remove(expired);
+            } else if self.entries.last().is_some_and(|worst| entry.price_cmp(worst).is_lt()) {
+                self.entries.pop();
+            } else {
+                return false;
+            }
+        }
+
+        let position = self
+            .entries
+            .iter()
+            .position(|existing| entry.price_cmp(existing).is_lt())
+            .unwrap_or(self.entries.len());
+        self.entries.insert(position, entry);
+
+        true
+    }
+
+    // partial fills just update the remaining amount, a fully filled offer leaves the book
+    pub fn update_remaining(&mut self, offer: &Pubkey, token_a_remaining: u64) {
+        if token_a_remaining == 0 {
+            self.remove(offer);
+        } else if let Some(entry) = self.entries.iter_mut().find(|e| e.offer == *offer) {
+            entry.token_a_remaining = token_a_remaining;
+        }
+    }
+
+    pub fn remove(&mut self, offer: &Pubkey) {
+        self.entries.retain(|e| e.offer != *offer);
+    }
+}
@@ -0,0 +1,465 @@
+use std::fs;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::{
+    token::spl_token,
+    associated_token::spl_associated_token_account::{self, get_associated_token_address_with_program_id, instruction::create_associated_token_account},
+    token_2022::spl_token_2022::{
+        self,
+        extension::{transfer_fee::{instruction::initialize_transfer_fee_config, TransferFeeAmount}, BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+        instruction::{initialize_mint2, mint_to},
+        state::{Account as TokenAccountState, Mint as MintState},
+    },
+};
+use escrow::{error::ErrorCode, BundleLeg};
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction::create_account,
+    transaction::{Transaction, TransactionError},
+};
+
+const DECIMALS: u8 = 6;
+const TRANSFER_FEE_BPS: u16 = 100; // 1%
+const AMOUNT: u64 = 1_000_000;
+
+// A maker who has put AMOUNT of a 1% transfer fee mint up for AMOUNT of a plain Token-2022 mint
+struct Offered {
+    svm: LiteSVM,
+    maker: Keypair,
+    mint_authority: Keypair,
+    fee_mint: Pubkey,
+    wanted_mint: Pubkey,
+    offer: Pubkey,
+    vault: Pubkey,
+    id: u64,
+}
+
+fn setup() -> LiteSVM {
+    let mut svm = LiteSVM::new();
+
+    // Load the compiled program - it's in this workspace's target/deploy directory
+    let program_path = "../../target/deploy/escrow.so";
+    let program_bytes = fs::read(program_path)
+        .unwrap_or_else(|e| panic!("Failed to read program bytes at {}: {}", program_path, e));
+    svm.add_program(escrow::ID, &program_bytes);
+
+    svm
+}
+
+fn send(svm: &mut LiteSVM, instructions: &[Instruction], signers: &[&Keypair]) -> Result<(), TransactionError> {
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&signers[0].pubkey()),
+        signers,
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(transaction).map(|_| ()).map_err(|failed| failed.err)
+}
+
+fn funded_keypair(svm: &mut LiteSVM) -> Keypair {
+    let keypair = Keypair::new();
+    svm.airdrop(&keypair.pubkey(), 10_000_000_000).unwrap();
+    keypair
+}
+
+// A Token-2022 mint, with the transfer fee extension when `transfer_fee_bps` is set
+fn create_mint(svm: &mut LiteSVM, authority: &Keypair, transfer_fee_bps: Option<u16>) -> Pubkey {
+    let mint = Keypair::new();
+    let extensions: &[ExtensionType] = match transfer_fee_bps {
+        Some(_) => &[ExtensionType::TransferFeeConfig],
+        None => &[],
+    };
+    let space = ExtensionType::try_calculate_account_len::<MintState>(extensions).unwrap();
+
+    let mut instructions = vec![create_account(
+        &authority.pubkey(),
+        &mint.pubkey(),
+        svm.minimum_balance_for_rent_exemption(space),
+        space as u64,
+        &spl_token_2022::ID,
+    )];
+    if let Some(bps) = transfer_fee_bps {
+        instructions.push(
+            initialize_transfer_fee_config(&spl_token_2022::ID, &mint.pubkey(), Some(&authority.pubkey()), Some(&authority.pubkey()), bps, u64::MAX)
+                .unwrap(),
+        );
+    }
+    instructions.push(initialize_mint2(&spl_token_2022::ID, &mint.pubkey(), &authority.pubkey(), None, DECIMALS).unwrap());
+
+    send(svm, &instructions, &[authority, &mint]).unwrap();
+    mint.pubkey()
+}
+
+// A mint owned by the original SPL Token program instead of Token-2022
+fn create_spl_token_mint(svm: &mut LiteSVM, authority: &Keypair) -> Pubkey {
+    let mint = Keypair::new();
+    let instructions = [
+        create_account(
+            &authority.pubkey(),
+            &mint.pubkey(),
+            svm.minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), &authority.pubkey(), None, DECIMALS).unwrap(),
+    ];
+    send(svm, &instructions, &[authority, &mint]).unwrap();
+    mint.pubkey()
+}
+
+fn ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, &spl_token_2022::ID)
+}
+
+fn mint_tokens_to(svm: &mut LiteSVM, mint_authority: &Keypair, mint: &Pubkey, owner: &Pubkey, amount: u64) {
+    let instructions = [
+        create_associated_token_account(&mint_authority.pubkey(), owner, mint, &spl_token_2022::ID),
+        mint_to(&spl_token_2022::ID, mint, &ata(owner, mint), &mint_authority.pubkey(), &[], amount).unwrap(),
+    ];
+    send(svm, &instructions, &[mint_authority]).unwrap();
+}
+
+fn token_account(svm: &LiteSVM, address: &Pubkey) -> Option<(u64, u64)> {
+    let account = svm.get_account(address).filter(|account| !account.data.is_empty())?;
+    let state = StateWithExtensions::<TokenAccountState>::unpack(&account.data).unwrap();
+    let withheld = state
+        .get_extension::<TransferFeeAmount>()
+        .map(|fee| u64::from(fee.withheld_amount))
+        .unwrap_or(0);
+    Some((state.base.amount, withheld))
+}
+
+fn offer_address(maker: &Pubkey, id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"offer", maker.as_ref(), &id.to_le_bytes()], &escrow::ID).0
+}
+
+fn bundle_offer_address(maker: &Pubkey, id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"bundle_offer", maker.as_ref(), &id.to_le_bytes()], &escrow::ID).0
+}
+
+fn offer_book_address(token_mint_a: &Pubkey, token_mint_b: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"offer_book", token_mint_a.as_ref(), token_mint_b.as_ref()], &escrow::ID).0
+}
+
+fn make_offer_instruction(maker: &Pubkey, token_mint_a: &Pubkey, token_mint_b: &Pubkey, id: u64) -> Instruction {
+    make_offer_for_instruction(maker, token_mint_a, token_mint_b, id, AMOUNT, AMOUNT)
+}
+
+fn make_offer_for_instruction(
+    maker: &Pubkey,
+    token_mint_a: &Pubkey,
+    token_mint_b: &Pubkey,
+    id: u64,
+    token_a_offered_amount: u64,
+    token_b_wanted_amount: u64,
+) -> Instruction {
+    let offer = offer_address(maker, id);
+    Instruction {
+        program_id: escrow::ID,
+        accounts: escrow::accounts::MakeOffer {
+            maker: *maker,
+            token_mint_a: *token_mint_a,
+            token_mint_b: *token_mint_b,
+            maker_token_account_a: ata(maker, token_mint_a),
+            offer_details: offer,
+            offer_book: offer_book_address(token_mint_a, token_mint_b),
+            vault: ata(&offer, token_mint_a),
+            token_program: spl_token_2022::ID,
+            system_program: system_program::ID,
+            associated_token_program: spl_associated_token_account::ID,
+        }
+        .to_account_metas(None),
+        data: escrow::instruction::MakeOffer {
+            id,
+            token_a_offered_amount,
+            token_b_wanted_amount,
+            expires_at: None,
+            allowed_takers: vec![],
+        }
+        .data(),
+    }
+}
+
+fn take_offer_instruction(taker: &Pubkey, offered: &Offered, token_a_amount: u64) -> Instruction {
+    take_instruction(taker, &offered.maker.pubkey(), &offered.fee_mint, &offered.wanted_mint, offered.id, token_a_amount)
+}
+
+fn take_instruction(
+    taker: &Pubkey,
+    maker: &Pubkey,
+    token_mint_a: &Pubkey,
+    token_mint_b: &Pubkey,
+    id: u64,
+    token_a_amount: u64,
+) -> Instruction {
+    let offer = offer_address(maker, id);
+    Instruction {
+        program_id: escrow::ID,
+        accounts: escrow::accounts::TakeOffer {
+            taker: *taker,
+            maker: *maker,
+            token_mint_a: *token_mint_a,
+            token_mint_b: *token_mint_b,
+            taker_token_account_a: ata(taker, token_mint_a),
+            taker_token_account_b: ata(taker, token_mint_b),
+            maker_token_account_b: ata(maker, token_mint_b),
+            offer_details: offer,
+            vault: ata(&offer, token_mint_a),
+            offer_book: offer_book_address(token_mint_a, token_mint_b),
+            associated_token_program: spl_associated_token_account::ID,
+            token_program: spl_token_2022::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: escrow::instruction::TakeOffer { id, token_a_amount }.data(),
+    }
+}
+
+fn refund_offer_instruction(offered: &Offered) -> Instruction {
+    let maker = offered.maker.pubkey();
+    Instruction {
+        program_id: escrow::ID,
+        accounts: escrow::accounts::RefundOffer {
+            maker,
+            maker_token_account_a: ata(&maker, &offered.fee_mint),
+            token_mint_a: offered.fee_mint,
+            offer_details: offered.offer,
+            vault: offered.vault,
+            offer_book: offer_book_address(&offered.fee_mint, &offered.wanted_mint),
+            associated_token_program: spl_associated_token_account::ID,
+            token_program: spl_token_2022::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: escrow::instruction::RefundOffer { id: offered.id }.data(),
+    }
+}
+
+// remaining accounts per offered leg: [mint, maker token account, vault]
+fn make_bundle_offer_instruction(maker: &Pubkey, id: u64, offered: &[Pubkey], wanted: &Pubkey) -> Instruction {
+    let bundle_offer = bundle_offer_address(maker, id);
+    let mut accounts = escrow::accounts::MakeBundleOffer {
+        maker: *maker,
+        bundle_offer,
+        token_program: spl_token_2022::ID,
+        system_program: system_program::ID,
+        associated_token_program: spl_associated_token_account::ID,
+    }
+    .to_account_metas(None);
+    for mint in offered {
+        accounts.extend([
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(ata(maker, mint), false),
+            AccountMeta::new(ata(&bundle_offer, mint), false),
+        ]);
+    }
+    Instruction {
+        program_id: escrow::ID,
+        accounts,
+        data: escrow::instruction::MakeBundleOffer {
+            id,
+            offered: offered.iter().map(|mint| BundleLeg { mint: *mint, amount: AMOUNT / 2 }).collect(),
+            wanted: vec![BundleLeg { mint: *wanted, amount: AMOUNT }],
+        }
+        .data(),
+    }
+}
+
+// remaining accounts per offered leg: [mint (writable), vault, maker token account]
+fn refund_bundle_offer_instruction(maker: &Pubkey, id: u64, offered: &[Pubkey]) -> Instruction {
+    let bundle_offer = bundle_offer_address(maker, id);
+    let mut accounts = escrow::accounts::RefundBundleOffer {
+        maker: *maker,
+        bundle_offer,
+        token_program: spl_token_2022::ID,
+        system_program: system_program::ID,
+        associated_token_program: spl_associated_token_account::ID,
+    }
+    .to_account_metas(None);
+    for mint in offered {
+        accounts.extend([
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(ata(&bundle_offer, mint), false),
+            AccountMeta::new(ata(maker, mint), false),
+        ]);
+    }
+    Instruction {
+        program_id: escrow::ID,
+        accounts,
+        data: escrow::instruction::RefundBundleOffer { id }.data(),
+    }
+}
+
+fn assert_escrow_error(result: Result<(), TransactionError>, expected: ErrorCode) {
+    assert_eq!(result, Err(TransactionError::InstructionError(0, InstructionError::Custom(expected.into()))));
+}
+
+fn offered() -> Offered {
+    let mut svm = setup();
+    let mint_authority = funded_keypair(&mut svm);
+    let maker = funded_keypair(&mut svm);
+
+    let fee_mint = create_mint(&mut svm, &mint_authority, Some(TRANSFER_FEE_BPS));
+    let wanted_mint = create_mint(&mut svm, &mint_authority, None);
+    mint_tokens_to(&mut svm, &mint_authority, &fee_mint, &maker.pubkey(), 2 * AMOUNT);
+
+    let id = 7;
+    send(&mut svm, &[make_offer_instruction(&maker.pubkey(), &fee_mint, &wanted_mint, id)], &[&maker]).unwrap();
+
+    let offer = offer_address(&maker.pubkey(), id);
+    let vault = ata(&offer, &fee_mint);
+    Offered { svm, maker, mint_authority, fee_mint, wanted_mint, offer, vault, id }
+}
+
+#[tokio::test]
+async fn test_refund_closes_a_vault_holding_withheld_fees() {
+    let mut offered = offered();
+
+    // the deposit into the vault withheld a fee on it, which used to make closing the vault fail
+    let (amount, withheld) = token_account(&offered.svm, &offered.vault).unwrap();
+    assert_eq!(amount, AMOUNT);
+    assert!(withheld > 0);
+
+    let refund = refund_offer_instruction(&offered);
+    send(&mut offered.svm, &[refund], &[&offered.maker]).unwrap();
+
+    assert!(token_account(&offered.svm, &offered.vault).is_none());
+    assert!(offered.svm.get_account(&offered.offer).is_none());
+}
+
+#[tokio::test]
+async fn test_the_last_fill_closes_a_vault_holding_withheld_fees() {
+    let mut offered = offered();
+
+    let taker = funded_keypair(&mut offered.svm);
+    mint_tokens_to(&mut offered.svm, &offered.mint_authority, &offered.wanted_mint, &taker.pubkey(), AMOUNT);
+
+    // a partial fill leaves the vault open, then the rest of the offer closes it
+    let half_fill = take_offer_instruction(&taker.pubkey(), &offered, AMOUNT / 2);
+    send(&mut offered.svm, std::slice::from_ref(&half_fill), &[&taker]).unwrap();
+    assert!(token_account(&offered.svm, &offered.vault).is_some());
+    offered.svm.expire_blockhash(); // the second fill is the same transaction again
+    send(&mut offered.svm, &[half_fill], &[&taker]).unwrap();
+
+    assert!(token_account(&offered.svm, &offered.vault).is_none());
+    let (taker_amount, _) = token_account(&offered.svm, &ata(&taker.pubkey(), &offered.fee_mint)).unwrap();
+    assert!(taker_amount > 0 && taker_amount < AMOUNT, "the taker gets the offer minus the transfer fee");
+}
+
+#[tokio::test]
+async fn test_bundle_refund_closes_a_vault_holding_withheld_fees() {
+    let mut offered = offered();
+    let maker = offered.maker.pubkey();
+    let legs = [offered.fee_mint];
+
+    let make = make_bundle_offer_instruction(&maker, 8, &legs, &offered.wanted_mint);
+    send(&mut offered.svm, &[make], &[&offered.maker]).unwrap();
+    let vault = ata(&bundle_offer_address(&maker, 8), &offered.fee_mint);
+    assert!(token_account(&offered.svm, &vault).is_some_and(|(_, withheld)| withheld > 0));
+
+    let refund = refund_bundle_offer_instruction(&maker, 8, &legs);
+    send(&mut offered.svm, &[refund], &[&offered.maker]).unwrap();
+
+    assert!(token_account(&offered.svm, &vault).is_none());
+    assert!(offered.svm.get_account(&bundle_offer_address(&maker, 8)).is_none());
+}
+
+#[tokio::test]
+async fn test_bundle_legs_all_use_the_same_token_program() {
+    let mut offered = offered();
+    let maker = offered.maker.pubkey();
+
+    // the bundle is made with Token-2022, a leg whose mint is owned by SPL Token is rejected
+    let spl_token_mint = create_spl_token_mint(&mut offered.svm, &offered.mint_authority);
+    let make = make_bundle_offer_instruction(&maker, 9, &[offered.fee_mint, spl_token_mint], &offered.wanted_mint);
+    let result = send(&mut offered.svm, &[make], &[&offered.maker]);
+
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(0, InstructionError::Custom(ErrorCode::InvalidBundleAccounts.into()))),
+    );
+}
+
+#[tokio::test]
+async fn test_make_offer_rejects_zero_amounts() {
+    let mut offered = offered();
+    let maker = offered.maker.pubkey();
+
+    let nothing_offered = make_offer_for_instruction(&maker, &offered.fee_mint, &offered.wanted_mint, 8, 0, AMOUNT);
+    let result = send(&mut offered.svm, &[nothing_offered], &[&offered.maker]);
+    assert_escrow_error(result, ErrorCode::InvalidAmount);
+
+    let nothing_wanted = make_offer_for_instruction(&maker, &offered.fee_mint, &offered.wanted_mint, 8, AMOUNT / 2, 0);
+    let result = send(&mut offered.svm, &[nothing_wanted], &[&offered.maker]);
+    assert_escrow_error(result, ErrorCode::InvalidAmount);
+}
+
+#[tokio::test]
+async fn test_make_offer_rejects_the_same_mint_on_both_sides() {
+    let mut offered = offered();
+
+    let make = make_offer_for_instruction(&offered.maker.pubkey(), &offered.fee_mint, &offered.fee_mint, 8, AMOUNT / 2, AMOUNT / 2);
+    let result = send(&mut offered.svm, &[make], &[&offered.maker]);
+
+    assert_escrow_error(result, ErrorCode::InvalidTokenMint);
+}
+
+#[tokio::test]
+async fn test_make_offer_needs_the_deposit_and_its_transfer_fee() {
+    let mut offered = offered();
+    let maker = funded_keypair(&mut offered.svm);
+    mint_tokens_to(&mut offered.svm, &offered.mint_authority, &offered.fee_mint, &maker.pubkey(), AMOUNT);
+
+    // the maker holds exactly AMOUNT, but the vault has to end up with AMOUNT so the 1% fee comes on top
+    let make = make_offer_instruction(&maker.pubkey(), &offered.fee_mint, &offered.wanted_mint, 8);
+    let result = send(&mut offered.svm, &[make], &[&maker]);
+    assert_escrow_error(result, ErrorCode::InsufficientMakerBalance);
+
+    let make = make_offer_for_instruction(&maker.pubkey(), &offered.fee_mint, &offered.wanted_mint, 8, AMOUNT / 2, AMOUNT);
+    send(&mut offered.svm, &[make], &[&maker]).unwrap();
+}
+
+#[tokio::test]
+async fn test_take_offer_rejects_a_taker_who_cannot_pay() {
+    let mut offered = offered();
+    let taker = funded_keypair(&mut offered.svm);
+    mint_tokens_to(&mut offered.svm, &offered.mint_authority, &offered.wanted_mint, &taker.pubkey(), AMOUNT / 2);
+
+    let take_nothing = take_offer_instruction(&taker.pubkey(), &offered, 0);
+    let result = send(&mut offered.svm, &[take_nothing], &[&taker]);
+    assert_escrow_error(result, ErrorCode::InvalidAmount);
+
+    // the whole offer costs AMOUNT of the wanted mint, the taker only has half
+    let take_everything = take_offer_instruction(&taker.pubkey(), &offered, AMOUNT);
+    let result = send(&mut offered.svm, &[take_everything], &[&taker]);
+    assert_escrow_error(result, ErrorCode::InsufficientTakerBalance);
+    assert_eq!(token_account(&offered.svm, &offered.vault).map(|(amount, _)| amount), Some(AMOUNT));
+}
+
+#[tokio::test]
+async fn test_the_maker_receives_exactly_what_they_wanted_of_a_fee_bearing_token_b() {
+    let mut offered = offered();
+    let maker = offered.maker.pubkey();
+
+    // the other way round: the plain mint is offered for the 1% transfer fee mint
+    mint_tokens_to(&mut offered.svm, &offered.mint_authority, &offered.wanted_mint, &maker, AMOUNT);
+    let make = make_offer_for_instruction(&maker, &offered.wanted_mint, &offered.fee_mint, 8, AMOUNT, AMOUNT);
+    send(&mut offered.svm, &[make], &[&offered.maker]).unwrap();
+
+    // the maker still holds what the first offer's deposit left of the fee mint
+    let (maker_before, _) = token_account(&offered.svm, &ata(&maker, &offered.fee_mint)).unwrap();
+
+    let taker = funded_keypair(&mut offered.svm);
+    mint_tokens_to(&mut offered.svm, &offered.mint_authority, &offered.fee_mint, &taker.pubkey(), 2 * AMOUNT);
+    let take = take_instruction(&taker.pubkey(), &maker, &offered.wanted_mint, &offered.fee_mint, 8, AMOUNT);
+    send(&mut offered.svm, &[take], &[&taker]).unwrap();
+
+    let (maker_amount, withheld) = token_account(&offered.svm, &ata(&maker, &offered.fee_mint)).unwrap();
+    assert_eq!(maker_amount, maker_before + AMOUNT);
+    assert!(withheld > 0, "the taker paid the fee on top");
+    let (taker_amount, _) = token_account(&offered.svm, &ata(&taker.pubkey(), &offered.wanted_mint)).unwrap();
+    assert_eq!(taker_amount, AMOUNT);
+}
@@ -19,6 +19,16 @@ export const getRandomBigInt = () => {
   return BigInt(Math.floor(Math.random() * 1_000_000_000_000_000_000));
 };
 
+// Helper function to get the offer book PDA for offers selling tokenMintA for tokenMintB
+export async function getOfferBookAddress(connection: Connection, tokenMintA: Address, tokenMintB: Address) {
+  const offerBookPDAAndBump = await connection.getPDAAndBump(programClient.ESCROW_PROGRAM_ADDRESS, [
+    "offer_book",
+    tokenMintA,
+    tokenMintB,
+  ]);
+  return offerBookPDAAndBump.pda;
+}
+
 // Helper function to create a test offer
 export async function createTestOffer(params: {
   connection: Connection;
@@ -29,6 +39,8 @@ export async function createTestOffer(params: {
   tokenAOfferedAmount: bigint;
   tokenBWantedAmount: bigint;
   offerId?: bigint;
+  expiresAt?: bigint | null;
+  allowedTakers?: Array<Address>;
 }) {
   const {
     connection,
@@ -39,11 +51,14 @@ export async function createTestOffer(params: {
     tokenAOfferedAmount,
     tokenBWantedAmount,
     offerId = getRandomBigInt(),
+    expiresAt = null,
+    allowedTakers = [],
   } = params;
 
   const offerPDAAndBump = await connection.getPDAAndBump(programClient.ESCROW_PROGRAM_ADDRESS, ["offer", maker.address, offerId]);
   const offer = offerPDAAndBump.pda;
   const vault = await connection.getTokenAccountAddress(offer, tokenMintA, true);
+  const offerBook = await getOfferBookAddress(connection, tokenMintA, tokenMintB);
 
   const makeOfferInstruction = await programClient.getMakeOfferInstructionAsync({
     maker,
@@ -52,9 +67,12 @@ export async function createTestOffer(params: {
     makerTokenAccountA,
     offerDetails: offer,
     vault,
+    offerBook,
     id: offerId,
     tokenAOfferedAmount,
     tokenBWantedAmount,
+    expiresAt,
+    allowedTakers,
     tokenProgram: TOKEN_EXTENSIONS_PROGRAM,
   });
 
@@ -3,15 +3,19 @@ import assert from "node:assert";
 import * as programClient from "../dist/js-client";
 import { getOfferDecoder, OFFER_DISCRIMINATOR } from "../dist/js-client";
 import { connect, Connection, TOKEN_EXTENSIONS_PROGRAM, ErrorWithTransaction } from "solana-kite";
-import { type KeyPairSigner, type Address } from "@solana/kit";
-import { createTestOffer, getRandomBigInt, ONE_SOL } from "./escrow.test-helpers";
+import { AccountRole, type KeyPairSigner, type Address } from "@solana/kit";
+import { createTestOffer, getOfferBookAddress, getRandomBigInt, ONE_SOL } from "./escrow.test-helpers";
 
-const INSUFFICIENT_FUNDS_ERROR = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb.TransferChecked: insufficient funds";
+const INSUFFICIENT_TAKER_BALANCE_ERROR = "custom program error: #6000";
+const INSUFFICIENT_MAKER_BALANCE_ERROR = "custom program error: #6001";
 const REFUND_OFFER_ERROR =
   "FJaGSGgyggHR6Rr1hdEzgWQ9Ysv8KyySSw3Jwq33Ydic.RefundOffer: The program expected this account to be already initialized";
 const ACCOUNT_IN_USE_ERROR = "11111111111111111111111111111111.Allocate: account already in use";
 const INVALID_TOKEN_MINT_ERROR = "custom program error: #6002";
 const INVALID_AMOUNT_ERROR = "custom program error: #6003";
+const OFFER_NOT_EXPIRED_ERROR = "custom program error: #6005";
+const TAKER_NOT_ALLOWED_ERROR = "custom program error: #6008";
+const FILL_EXCEEDS_REMAINING_ERROR = "custom program error: #6009";
 
 describe("Escrow", () => {
   let connection: Connection;
@@ -23,6 +27,7 @@ describe("Escrow", () => {
   let aliceTokenAccountA: Address;
   let bobTokenAccountA: Address;
   let aliceTokenAccountB: Address;
+  let offerBookAB: Address;
 
   const tokenDecimals = 9;
 
@@ -80,6 +85,9 @@ describe("Escrow", () => {
     aliceTokenAccountA = await connection.getTokenAccountAddress(alice.address, tokenMintA, true);
     bobTokenAccountA = await connection.getTokenAccountAddress(bob.address, tokenMintA, true);
     aliceTokenAccountB = await connection.getTokenAccountAddress(alice.address, tokenMintB, true);
+
+    // Every offer selling token A for token B is listed in this book
+    offerBookAB = await getOfferBookAddress(connection, tokenMintA, tokenMintB);
   });
 
   describe("makeOffer", () => {
@@ -160,8 +168,8 @@ describe("Escrow", () => {
       } catch (thrownObject) {
         const error = thrownObject as ErrorWithTransaction;
         assert(
-          error.message === INSUFFICIENT_FUNDS_ERROR,
-          `Expected "${INSUFFICIENT_FUNDS_ERROR}" but got: ${error.message}`,
+          error.message.includes(INSUFFICIENT_MAKER_BALANCE_ERROR),
+          `Expected InsufficientMakerBalance error but got: ${error.message}`,
         );
       }
     });
@@ -289,8 +297,10 @@ describe("Escrow", () => {
         makerTokenAccountB: aliceTokenAccountB,
         offerDetails: testOffer,
         vault: testVault,
+        offerBook: offerBookAB,
         tokenProgram: TOKEN_EXTENSIONS_PROGRAM,
         id: testOfferId,
+        tokenAAmount: tokenAOfferedAmount,
       });
 
       await connection.sendTransactionFromInstructions({
@@ -340,8 +350,10 @@ describe("Escrow", () => {
         makerTokenAccountB: aliceTokenAccountB,
         offerDetails: offer,
         vault,
+        offerBook: offerBookAB,
         tokenProgram: TOKEN_EXTENSIONS_PROGRAM,
         id: offerId,
+        tokenAAmount: tokenAOfferedAmount,
       });
 
       try {
@@ -353,11 +365,232 @@ describe("Escrow", () => {
       } catch (thrownObject) {
         const error = thrownObject as ErrorWithTransaction;
         assert(
-          error.message === INSUFFICIENT_FUNDS_ERROR,
-          `Expected "${INSUFFICIENT_FUNDS_ERROR}" but got: ${error.message}`,
+          error.message.includes(INSUFFICIENT_TAKER_BALANCE_ERROR),
+          `Expected InsufficientTakerBalance error but got: ${error.message}`,
         );
       }
     });
+
+    test("partially fills an offer at the maker's ratio and keeps it open", async () => {
+      // Alice offers 2 token A for 2 token B, Bob only takes half
+      const { offer, vault, offerId } = await createTestOffer({
+        connection,
+        maker: alice,
+        tokenMintA,
+        tokenMintB,
+        makerTokenAccountA: aliceTokenAccountA,
+        tokenAOfferedAmount: 2n * TOKEN,
+        tokenBWantedAmount: 2n * TOKEN,
+      });
+
+      // Bob spent his token B in the first take, give him some more
+      await connection.mintTokens(tokenMintB, user, 1n * TOKEN, bob.address);
+
+      const takeOfferInstruction = await programClient.getTakeOfferInstructionAsync({
+        taker: bob,
+        maker: alice.address,
+        tokenMintA,
+        tokenMintB,
+        takerTokenAccountA: bobTokenAccountA,
+        makerTokenAccountB: aliceTokenAccountB,
+        offerDetails: offer,
+        vault,
+        offerBook: offerBookAB,
+        tokenProgram: TOKEN_EXTENSIONS_PROGRAM,
+        id: offerId,
+        tokenAAmount: 1n * TOKEN,
+      });
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: bob,
+        instructions: [takeOfferInstruction],
+      });
+
+      const vaultBalance = await connection.getTokenAccountBalance({
+        tokenAccount: vault,
+        mint: tokenMintA,
+        useTokenExtensions: true,
+      });
+      assert.equal(vaultBalance.amount, 1n * TOKEN, "Half of the offer should still be in the vault");
+
+      const offerAccount = await programClient.fetchOffer(connection.rpc, offer);
+      assert.equal(offerAccount.data.tokenARemaining, 1n * TOKEN, "Offer should track the remaining token A");
+      assert.equal(offerAccount.data.tokenBReceived, 1n * TOKEN, "Offer should track the token B paid so far");
+    });
+
+    test("rounds partial fills on the running total so the last fill still goes through", async () => {
+      // 3 minor units of token A for 1 of token B: the first fill rounds up to 1, the next two are already paid for
+      const { offer, vault, offerId } = await createTestOffer({
+        connection,
+        maker: alice,
+        tokenMintA,
+        tokenMintB,
+        makerTokenAccountA: aliceTokenAccountA,
+        tokenAOfferedAmount: 3n,
+        tokenBWantedAmount: 1n,
+      });
+
+      await connection.mintTokens(tokenMintB, user, 1n, bob.address);
+
+      const aliceTokenBBefore = await connection.getTokenAccountBalance({
+        tokenAccount: aliceTokenAccountB,
+        mint: tokenMintB,
+        useTokenExtensions: true,
+      });
+
+      for (let fill = 0; fill < 3; fill++) {
+        const takeOfferInstruction = await programClient.getTakeOfferInstructionAsync({
+          taker: bob,
+          maker: alice.address,
+          tokenMintA,
+          tokenMintB,
+          takerTokenAccountA: bobTokenAccountA,
+          makerTokenAccountB: aliceTokenAccountB,
+          offerDetails: offer,
+          vault,
+          offerBook: offerBookAB,
+          tokenProgram: TOKEN_EXTENSIONS_PROGRAM,
+          id: offerId,
+          tokenAAmount: 1n,
+        });
+
+        await connection.sendTransactionFromInstructions({
+          feePayer: bob,
+          instructions: [takeOfferInstruction],
+        });
+      }
+
+      const aliceTokenBAfter = await connection.getTokenAccountBalance({
+        tokenAccount: aliceTokenAccountB,
+        mint: tokenMintB,
+        useTokenExtensions: true,
+      });
+      assert.equal(aliceTokenBAfter.amount - aliceTokenBBefore.amount, 1n, "Alice should get exactly the 1 token B she asked for");
+
+      const isClosed = await connection.checkTokenAccountIsClosed({
+        tokenAccount: vault,
+        useTokenExtensions: true,
+      });
+      assert.ok(isClosed, "The filled offer's vault should be closed");
+    });
+
+    test("fails when the fill amount is zero", async () => {
+      const { offer, vault, offerId } = await createTestOffer({
+        connection,
+        maker: alice,
+        tokenMintA,
+        tokenMintB,
+        makerTokenAccountA: aliceTokenAccountA,
+        tokenAOfferedAmount,
+        tokenBWantedAmount,
+      });
+
+      const takeOfferInstruction = await programClient.getTakeOfferInstructionAsync({
+        taker: bob,
+        maker: alice.address,
+        tokenMintA,
+        tokenMintB,
+        takerTokenAccountA: bobTokenAccountA,
+        makerTokenAccountB: aliceTokenAccountB,
+        offerDetails: offer,
+        vault,
+        offerBook: offerBookAB,
+        tokenProgram: TOKEN_EXTENSIONS_PROGRAM,
+        id: offerId,
+        tokenAAmount: 0n,
+      });
+
+      try {
+        await connection.sendTransactionFromInstructions({
+          feePayer: bob,
+          instructions: [takeOfferInstruction],
+        });
+        assert.fail("Expected the take offer to fail but it succeeded");
+      } catch (thrownObject) {
+        const error = thrownObject as ErrorWithTransaction;
+        assert(error.message.includes(INVALID_AMOUNT_ERROR), `Expected InvalidAmount error but got: ${error.message}`);
+      }
+    });
+
+    test("fails when the fill is larger than what is left in the offer", async () => {
+      const { offer, vault, offerId } = await createTestOffer({
+        connection,
+        maker: alice,
+        tokenMintA,
+        tokenMintB,
+        makerTokenAccountA: aliceTokenAccountA,
+        tokenAOfferedAmount,
+        tokenBWantedAmount,
+      });
+
+      const takeOfferInstruction = await programClient.getTakeOfferInstructionAsync({
+        taker: bob,
+        maker: alice.address,
+        tokenMintA,
+        tokenMintB,
+        takerTokenAccountA: bobTokenAccountA,
+        makerTokenAccountB: aliceTokenAccountB,
+        offerDetails: offer,
+        vault,
+        offerBook: offerBookAB,
+        tokenProgram: TOKEN_EXTENSIONS_PROGRAM,
+        id: offerId,
+        tokenAAmount: tokenAOfferedAmount + 1n,
+      });
+
+      try {
+        await connection.sendTransactionFromInstructions({
+          feePayer: bob,
+          instructions: [takeOfferInstruction],
+        });
+        assert.fail("Expected the take offer to fail but it succeeded");
+      } catch (thrownObject) {
+        const error = thrownObject as ErrorWithTransaction;
+        assert(
+          error.message.includes(FILL_EXCEEDS_REMAINING_ERROR),
+          `Expected FillExceedsRemaining error but got: ${error.message}`,
+        );
+      }
+    });
+
+    test("fails when a taker is not on the offer's allowlist", async () => {
+      const { offer, vault, offerId } = await createTestOffer({
+        connection,
+        maker: alice,
+        tokenMintA,
+        tokenMintB,
+        makerTokenAccountA: aliceTokenAccountA,
+        tokenAOfferedAmount,
+        tokenBWantedAmount,
+        allowedTakers: [user.address], // private deal with 'user', not Bob
+      });
+
+      const takeOfferInstruction = await programClient.getTakeOfferInstructionAsync({
+        taker: bob,
+        maker: alice.address,
+        tokenMintA,
+        tokenMintB,
+        takerTokenAccountA: bobTokenAccountA,
+        makerTokenAccountB: aliceTokenAccountB,
+        offerDetails: offer,
+        vault,
+        offerBook: offerBookAB,
+        tokenProgram: TOKEN_EXTENSIONS_PROGRAM,
+        id: offerId,
+        tokenAAmount: tokenAOfferedAmount,
+      });
+
+      try {
+        await connection.sendTransactionFromInstructions({
+          feePayer: bob,
+          instructions: [takeOfferInstruction],
+        });
+        assert.fail("Expected the take offer to fail but it succeeded");
+      } catch (thrownObject) {
+        const error = thrownObject as ErrorWithTransaction;
+        assert(error.message.includes(TAKER_NOT_ALLOWED_ERROR), `Expected TakerNotAllowed error but got: ${error.message}`);
+      }
+    });
   });
 
   describe("refundOffer", () => {
@@ -393,6 +626,7 @@ describe("Escrow", () => {
         makerTokenAccountA: aliceTokenAccountA,
         offer: testOffer,
         vault: testVault,
+        offerBook: offerBookAB,
         tokenProgram: TOKEN_EXTENSIONS_PROGRAM,
         id: testOfferId,
       });
@@ -438,6 +672,7 @@ describe("Escrow", () => {
         makerTokenAccountA: bobTokenAccountA,  // Bob's token account
         offer,  // But this is Alice's offer PDA
         vault,  // And this is Alice's vault
+        offerBook: offerBookAB,
         tokenProgram: TOKEN_EXTENSIONS_PROGRAM,
         id: offerId,
       });
@@ -453,5 +688,289 @@ describe("Escrow", () => {
         assert.equal(error.message, REFUND_OFFER_ERROR, "Expected refund offer error");
       }
     });
+
+    test("anyone can refund an expired offer back to the maker", async () => {
+      const expiresAt = BigInt(Math.floor(Date.now() / 1000) + 2);
+      const { offer, vault, offerId } = await createTestOffer({
+        connection,
+        maker: alice,
+        tokenMintA,
+        tokenMintB,
+        makerTokenAccountA: aliceTokenAccountA,
+        tokenAOfferedAmount,
+        tokenBWantedAmount,
+        expiresAt,
+      });
+
+      const getRefundExpiredInstruction = () =>
+        programClient.getRefundExpiredOfferInstructionAsync({
+          caller: bob,
+          maker: alice.address,
+          tokenMintA,
+          offerDetails: offer,
+          vault,
+          offerBook: offerBookAB,
+          tokenProgram: TOKEN_EXTENSIONS_PROGRAM,
+          id: offerId,
+        });
+
+      // Too early, the offer is still live
+      try {
+        await connection.sendTransactionFromInstructions({
+          feePayer: bob,
+          instructions: [await getRefundExpiredInstruction()],
+        });
+        assert.fail("Expected the refund to fail but it succeeded");
+      } catch (thrownObject) {
+        const error = thrownObject as ErrorWithTransaction;
+        assert(error.message.includes(OFFER_NOT_EXPIRED_ERROR), `Expected OfferNotExpired error but got: ${error.message}`);
+      }
+
+      // Wait until the offer has expired
+      await new Promise((resolve) => setTimeout(resolve, 4000));
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: bob,
+        instructions: [await getRefundExpiredInstruction()],
+      });
+
+      const isClosed = await connection.checkTokenAccountIsClosed({
+        tokenAccount: vault,
+        useTokenExtensions: true,
+      });
+      assert.ok(isClosed, "Vault should be closed");
+    });
+  });
+
+  describe("matchOffers", () => {
+    test("settles two crossing offers without a third party holding funds", async () => {
+      // Alice sells 1 token A for 1 token B, Bob sells 1 token B for 1 token A
+      await connection.mintTokens(tokenMintB, user, 1n * TOKEN, bob.address);
+      const bobTokenAccountB = await connection.getTokenAccountAddress(bob.address, tokenMintB, true);
+
+      const aliceOffer = await createTestOffer({
+        connection,
+        maker: alice,
+        tokenMintA,
+        tokenMintB,
+        makerTokenAccountA: aliceTokenAccountA,
+        tokenAOfferedAmount,
+        tokenBWantedAmount,
+      });
+      const bobOffer = await createTestOffer({
+        connection,
+        maker: bob,
+        tokenMintA: tokenMintB,
+        tokenMintB: tokenMintA,
+        makerTokenAccountA: bobTokenAccountB,
+        tokenAOfferedAmount: tokenBWantedAmount,
+        tokenBWantedAmount: tokenAOfferedAmount,
+      });
+
+      const aliceTokenBBefore = await connection.getTokenAccountBalance({
+        tokenAccount: aliceTokenAccountB,
+        mint: tokenMintB,
+        useTokenExtensions: true,
+      });
+
+      const matchOffersInstruction = await programClient.getMatchOffersInstructionAsync({
+        matcher: user,
+        maker1: alice.address,
+        maker2: bob.address,
+        tokenMintA,
+        tokenMintB,
+        offer1: aliceOffer.offer,
+        offer2: bobOffer.offer,
+        vault1: aliceOffer.vault,
+        vault2: bobOffer.vault,
+        offerBookAb: offerBookAB,
+        offerBookBa: await getOfferBookAddress(connection, tokenMintB, tokenMintA),
+        tokenProgram: TOKEN_EXTENSIONS_PROGRAM,
+        id1: aliceOffer.offerId,
+        id2: bobOffer.offerId,
+      });
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: user,
+        instructions: [matchOffersInstruction],
+      });
+
+      const aliceTokenBAfter = await connection.getTokenAccountBalance({
+        tokenAccount: aliceTokenAccountB,
+        mint: tokenMintB,
+        useTokenExtensions: true,
+      });
+      assert.equal(
+        aliceTokenBAfter.amount - aliceTokenBBefore.amount,
+        tokenBWantedAmount,
+        "Alice should receive the token B she wanted",
+      );
+
+      // Both offers were filled completely, so both vaults are closed
+      for (const vault of [aliceOffer.vault, bobOffer.vault]) {
+        const isClosed = await connection.checkTokenAccountIsClosed({
+          tokenAccount: vault,
+          useTokenExtensions: true,
+        });
+        assert.ok(isClosed, "Vault should be closed");
+      }
+    });
+  });
+
+  describe("offer book", () => {
+    test("makes room for better priced offers once the book is full", async () => {
+      // a fresh pair so the book starts empty
+      const tokenMintD = await connection.createTokenMint({
+        mintAuthority: user,
+        decimals: tokenDecimals,
+        name: "Token D",
+        symbol: "TOKEN_D",
+        uri: "https://example.com/token-d",
+      });
+      const offerBookAD = await getOfferBookAddress(connection, tokenMintA, tokenMintD);
+      const makeOffer = (tokenBWantedAmount: bigint) =>
+        createTestOffer({
+          connection,
+          maker: alice,
+          tokenMintA,
+          tokenMintB: tokenMintD,
+          makerTokenAccountA: aliceTokenAccountA,
+          tokenAOfferedAmount: 1n,
+          tokenBWantedAmount,
+        });
+
+      // fill all 32 entries with dust offers at 2 token D each
+      const dustOffers: Array<Address> = [];
+      for (let index = 0; index < 32; index++) {
+        dustOffers.push((await makeOffer(2n)).offer);
+      }
+
+      // a worse priced offer is still made, it just isn't listed
+      const worseOffer = await makeOffer(3n);
+      const worseOfferAccount = await programClient.fetchOffer(connection.rpc, worseOffer.offer);
+      assert.equal(worseOfferAccount.data.tokenARemaining, 1n, "The offer should be open even though the book is full");
+
+      let offerBook = await programClient.fetchOfferBook(connection.rpc, offerBookAD);
+      assert.equal(offerBook.data.entries.length, 32);
+      assert.ok(!offerBook.data.entries.some((entry) => entry.offer === worseOffer.offer), "The worse offer should not be listed");
+
+      // a better priced offer pushes out the newest of the worst priced entries
+      const betterOffer = await makeOffer(1n);
+      offerBook = await programClient.fetchOfferBook(connection.rpc, offerBookAD);
+      assert.equal(offerBook.data.entries.length, 32);
+      assert.equal(offerBook.data.entries[0].offer, betterOffer.offer, "The better offer should be first in the book");
+      assert.ok(
+        !offerBook.data.entries.some((entry) => entry.offer === dustOffers[31]),
+        "The newest dust offer should have been dropped",
+      );
+    });
+  });
+
+  describe("bundle offers", () => {
+    test("swaps a bundle of two mints for one mint and closes every vault", async () => {
+      // Alice puts up 1 token A + 1 token C, and wants 1 token B for the lot
+      const tokenMintC = await connection.createTokenMint({
+        mintAuthority: user,
+        decimals: tokenDecimals,
+        name: "Token C",
+        symbol: "TOKEN_C",
+        uri: "https://example.com/token-c",
+      });
+      await connection.mintTokens(tokenMintC, user, 1n * TOKEN, alice.address);
+      await connection.mintTokens(tokenMintB, user, 1n * TOKEN, bob.address);
+
+      const aliceTokenAccountC = await connection.getTokenAccountAddress(alice.address, tokenMintC, true);
+      const bobTokenAccountB = await connection.getTokenAccountAddress(bob.address, tokenMintB, true);
+      const bobTokenAccountC = await connection.getTokenAccountAddress(bob.address, tokenMintC, true);
+
+      const bundleId = getRandomBigInt();
+      const bundleOffer = (
+        await connection.getPDAAndBump(programClient.ESCROW_PROGRAM_ADDRESS, ["bundle_offer", alice.address, bundleId])
+      ).pda;
+      const vaultA = await connection.getTokenAccountAddress(bundleOffer, tokenMintA, true);
+      const vaultC = await connection.getTokenAccountAddress(bundleOffer, tokenMintC, true);
+
+      const writable = (address: Address) => ({ address, role: AccountRole.WRITABLE });
+      const readonly = (address: Address) => ({ address, role: AccountRole.READONLY });
+
+      const makeBundleInstruction = await programClient.getMakeBundleOfferInstructionAsync({
+        maker: alice,
+        bundleOffer,
+        tokenProgram: TOKEN_EXTENSIONS_PROGRAM,
+        id: bundleId,
+        offered: [
+          { mint: tokenMintA, amount: 1n * TOKEN },
+          { mint: tokenMintC, amount: 1n * TOKEN },
+        ],
+        wanted: [{ mint: tokenMintB, amount: 1n * TOKEN }],
+      });
+
+      // [mint, maker token account, vault] per offered leg
+      await connection.sendTransactionFromInstructions({
+        feePayer: alice,
+        instructions: [
+          {
+            ...makeBundleInstruction,
+            accounts: [
+              ...makeBundleInstruction.accounts,
+              readonly(tokenMintA), writable(aliceTokenAccountA), writable(vaultA),
+              readonly(tokenMintC), writable(aliceTokenAccountC), writable(vaultC),
+            ],
+          },
+        ],
+      });
+
+      const aliceTokenBBefore = await connection.getTokenAccountBalance({
+        tokenAccount: aliceTokenAccountB,
+        mint: tokenMintB,
+        useTokenExtensions: true,
+      });
+
+      const takeBundleInstruction = await programClient.getTakeBundleOfferInstructionAsync({
+        taker: bob,
+        maker: alice.address,
+        bundleOffer,
+        tokenProgram: TOKEN_EXTENSIONS_PROGRAM,
+        id: bundleId,
+      });
+
+      // offered legs [mint (writable), vault, taker token account], then wanted legs [mint, taker token account, maker token account]
+      await connection.sendTransactionFromInstructions({
+        feePayer: bob,
+        instructions: [
+          {
+            ...takeBundleInstruction,
+            accounts: [
+              ...takeBundleInstruction.accounts,
+              writable(tokenMintA), writable(vaultA), writable(bobTokenAccountA),
+              writable(tokenMintC), writable(vaultC), writable(bobTokenAccountC),
+              readonly(tokenMintB), writable(bobTokenAccountB), writable(aliceTokenAccountB),
+            ],
+          },
+        ],
+      });
+
+      const aliceTokenBAfter = await connection.getTokenAccountBalance({
+        tokenAccount: aliceTokenAccountB,
+        mint: tokenMintB,
+        useTokenExtensions: true,
+      });
+      assert.equal(aliceTokenBAfter.amount - aliceTokenBBefore.amount, 1n * TOKEN, "Alice should receive the token B she wanted");
+
+      const bobTokenCBalance = await connection.getTokenAccountBalance({
+        tokenAccount: bobTokenAccountC,
+        mint: tokenMintC,
+        useTokenExtensions: true,
+      });
+      assert.equal(bobTokenCBalance.amount, 1n * TOKEN, "Bob should receive the token C from the bundle");
+
+      for (const vault of [vaultA, vaultC]) {
+        const isClosed = await connection.checkTokenAccountIsClosed({
+          tokenAccount: vault,
+          useTokenExtensions: true,
+        });
+        assert.ok(isClosed, "Bundle vault should be closed");
+      }
+    });
   });
-});
\ No newline at end of file
+});
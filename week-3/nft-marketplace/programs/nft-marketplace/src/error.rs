@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum MarketplaceError {
+    #[msg("Only the marketplace admin can do this")]
+    Unauthorized,
+
+    #[msg("Fee is above the marketplace maximum")]
+    FeeTooHigh,
+
+    #[msg("Creator accounts don't match the verified creators in the metadata")]
+    InvalidCreatorAccounts,
+
+    #[msg("Withdrawal would leave the treasury below rent exemption")]
+    InsufficientTreasuryBalance,
+
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+
+    #[msg("Listings can't be priced in this mint")]
+    PaymentMintNotAllowed,
+
+    #[msg("Too many payment mints")]
+    TooManyPaymentMints,
+
+    #[msg("Payment mint doesn't match the listing")]
+    InvalidPaymentMint,
+
+    #[msg("SPL listings need the payment mint and token accounts")]
+    MissingPaymentAccounts,
+
+    #[msg("NFT doesn't match what the bid is for")]
+    BidTargetMismatch,
+
+    #[msg("Reserve, increment, duration or extension is invalid")]
+    InvalidAuction,
+
+    #[msg("Auction has ended")]
+    AuctionEnded,
+
+    #[msg("Auction hasn't ended yet")]
+    AuctionNotEnded,
+
+    #[msg("Bid is below the reserve or the minimum increment")]
+    BidTooLow,
+
+    #[msg("The highest bid can't be refunded")]
+    BidStillWinning,
+
+    #[msg("Winner accounts don't match the auction")]
+    InvalidAuctionAccounts,
+
+    #[msg("NFT isn't in a verified collection matching the collection mint")]
+    InvalidCollection,
+
+    #[msg("Maker doesn't match the listing")]
+    InvalidMaker,
+
+    #[msg("Batch accounts are missing or in the wrong order")]
+    InvalidBatchAccounts,
+
+    #[msg("Listing is already taken or no longer there")]
+    ListingUnavailable,
+
+    #[msg("Price is above what the buyer agreed to pay")]
+    PriceLimitExceeded,
+
+    #[msg("Rewards from a sale would be worth more fee credit than the sale's fee")]
+    RewardsTooHigh,
+
+    #[msg("Math overflow")]
+    MathOverflow,
+}
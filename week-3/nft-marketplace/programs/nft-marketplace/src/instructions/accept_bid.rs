@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{Metadata, MetadataAccount},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use anchor_helpers::transfer_tokens;
+
+use crate::{
+    error::MarketplaceError,
+    instructions::shared::pay_from_escrow,
+    state::{bid::{Bid, BidTarget}, marketplace::Marketplace},
+};
+
+#[derive(Accounts)]
+pub struct AcceptBid<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(mut)]
+    pub bidder: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    pub maker_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = maker_mint,
+        associated_token::authority = seller,
+    )]
+    pub seller_ata: InterfaceAccount<'info, TokenAccount>, // the NFT has to be in the seller's wallet, a listed NFT gets delisted first
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = maker_mint,
+        associated_token::authority = bidder,
+    )]
+    pub bidder_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = bidder, // after paying out the bid only the rent is left, it goes back to the bidder
+        seeds = [b"bid", marketplace.key().as_ref(), bid.target.as_ref(), bidder.key().as_ref()],
+        bump = bid.bump,
+        has_one = bidder,
+    )]
+    pub bid: Account<'info, Bid>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            maker_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub metadata: Account<'info, MetadataAccount>, // proves the NFT is in the bid's collection, and has the royalties
+
+    pub metadata_program: Program<'info, Metadata>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AcceptBid<'info> {
+    pub fn check_target(&self) -> Result<()> {
+        let matches = match self.bid.kind {
+            BidTarget::Nft => self.maker_mint.key() == self.bid.target,
+            BidTarget::Collection => self.metadata.collection
+                .as_ref()
+                .is_some_and(|collection| collection.verified && collection.key == self.bid.target),
+        };
+        require!(matches, MarketplaceError::BidTargetMismatch);
+        Ok(())
+    }
+
+    pub fn pay_seller(&self, creator_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        pay_from_escrow(
+            &self.bid.to_account_info(),
+            self.bid.amount,
+            &self.marketplace,
+            &self.metadata,
+            &self.treasury.to_account_info(),
+            &self.seller.to_account_info(),
+            creator_accounts,
+        )
+    }
+
+    pub fn send_nft(&mut self) -> Result<()> {
+        transfer_tokens(&self.seller_ata, &self.bidder_ata, 1, &self.maker_mint, &self.seller, &self.token_program, None)
+    }
+}
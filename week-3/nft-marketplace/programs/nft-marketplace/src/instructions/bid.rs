@@ -0,0 +1,71 @@
+use anchor_lang::{prelude::*, system_program::{Transfer, transfer}};
+
+use crate::{error::MarketplaceError, state::{bid::{Bid, BidTarget}, marketplace::Marketplace}};
+
+#[derive(Accounts)]
+#[instruction(target: Pubkey)]
+pub struct MakeBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        init,
+        payer = bidder,
+        seeds = [b"bid", marketplace.key().as_ref(), target.as_ref(), bidder.key().as_ref()], // one bid per bidder per NFT or collection
+        bump,
+        space = 8 + Bid::INIT_SPACE,
+    )]
+    pub bid: Account<'info, Bid>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeBid<'info> {
+    pub fn make_bid(&mut self, target: Pubkey, kind: BidTarget, amount: u64, bumps: &MakeBidBumps) -> Result<()> {
+        require!(amount > 0, MarketplaceError::InvalidAmount);
+
+        self.bid.set_inner(Bid {
+            bidder: self.bidder.key(),
+            target,
+            kind,
+            amount,
+            bump: bumps.bid,
+        });
+
+        let accounts = Transfer {
+            from: self.bidder.to_account_info(),
+            to: self.bid.to_account_info(), // the offered SOL sits in the bid account itself, next to its rent
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+
+        transfer(cpi_ctx, amount)
+    }
+}
+
+#[derive(Accounts)]
+pub struct CancelBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        close = bidder, // hands back the escrowed SOL together with the rent
+        seeds = [b"bid", marketplace.key().as_ref(), bid.target.as_ref(), bidder.key().as_ref()],
+        bump = bid.bump,
+        has_one = bidder,
+    )]
+    pub bid: Account<'info, Bid>,
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{MasterEditionAccount, Metadata, MetadataAccount},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use anchor_helpers::transfer_tokens;
+
+use crate::{error::MarketplaceError, state::{auction::Auction, marketplace::Marketplace}};
+
+#[derive(Accounts)]
+pub struct CreateAuction<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    pub maker_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = maker_mint,
+        associated_token::authority = maker,
+    )]
+    pub maker_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"auction", marketplace.key().as_ref(), maker_mint.key().as_ref()],
+        bump,
+        space = 8 + Auction::INIT_SPACE,
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        init,
+        payer = maker,
+        associated_token::mint = maker_mint,
+        associated_token::authority = auction,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>, // same as a listing, but the auction pda controls the vault
+
+    pub collection_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(                                                              // Same checks as listing: the NFT is in a verified collection...
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            maker_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+        constraint = metadata.collection.as_ref().is_some_and(|collection| collection.key == collection_mint.key()) @ MarketplaceError::InvalidCollection,
+        constraint = metadata.collection.as_ref().is_some_and(|collection| collection.verified) @ MarketplaceError::InvalidCollection,
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+
+    #[account(                                                              // ...and has a master edition
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            maker_mint.key().as_ref(),
+            b"edition",
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub master_edition: Account<'info, MasterEditionAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+impl<'info> CreateAuction<'info> {
+    pub fn create_auction(&mut self, reserve_price: u64, min_increment: u64, duration: i64, extension: i64, bumps: &CreateAuctionBumps) -> Result<()> {
+        require!(reserve_price > 0 && min_increment > 0, MarketplaceError::InvalidAuction);
+        require!(duration > 0 && extension >= 0 && extension <= duration, MarketplaceError::InvalidAuction);
+
+        let end_time = Clock::get()?
+            .unix_timestamp
+            .checked_add(duration)
+            .ok_or(MarketplaceError::MathOverflow)?;
+
+        self.auction.set_inner(Auction {
+            maker: self.maker.key(),
+            maker_mint: self.maker_mint.key(),
+            reserve_price,
+            min_increment,
+            end_time,
+            extension,
+            highest_bidder: None,
+            highest_bid: 0,
+            bump: bumps.auction,
+        });
+        Ok(())
+    }
+
+    pub fn deposit_nft(&mut self) -> Result<()> {
+        transfer_tokens(&self.maker_ata, &self.vault, 1, &self.maker_mint, &self.maker, &self.token_program, None)
+    }
+}
@@ -1,10 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_interface::{transfer_checked, TransferChecked, Mint, TokenAccount, TokenInterface},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
+use anchor_helpers::transfer_tokens;
 
-use crate::state::{listing::Listing, marketplace::Marketplace};
+use crate::{error::MarketplaceError, state::{listing::Listing, marketplace::Marketplace}};
 
 #[derive(Accounts)]
 pub struct Delist<'info> {
@@ -31,6 +32,7 @@ pub struct Delist<'info> {
         close = maker,
         seeds = [marketplace.key().as_ref(), maker_mint.key().as_ref()],
         bump = listing.bump,
+        has_one = maker @ MarketplaceError::Unauthorized, // only whoever listed the NFT can take it back
     )]
     pub listing: Account<'info, Listing>,
 
@@ -53,19 +55,15 @@ impl<'info> Delist<'info> {
             &self.maker_mint.key().to_bytes()[..],
             &[self.listing.bump],
         ];
-        let signer_seeds = &[&seeds[..]];
 
-        let cpi_program = self.token_program.to_account_info();
-
-        let cpi_accounts = TransferChecked {
-            from: self.vault.to_account_info(),
-            mint: self.maker_mint.to_account_info(),
-            to: self.maker_ata.to_account_info(),
-            authority: self.listing.to_account_info(),
-        };
-
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-
-        transfer_checked(cpi_ctx, 1, self.maker_mint.decimals)
+        transfer_tokens(
+            &self.vault,
+            &self.maker_ata,
+            1,
+            &self.maker_mint,
+            &self.listing,
+            &self.token_program,
+            Some(&seeds[..]),
+        )
     }
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenInterface};
 
-use crate::state::marketplace::Marketplace;
+use crate::{error::MarketplaceError, state::marketplace::{Marketplace, REWARDS_DECIMALS}};
 
 #[derive(Accounts)]
 #[instruction(name: String)] // what is this
@@ -15,7 +15,7 @@ pub struct Initialize<'info> {
         payer = admin,
         seeds = [b"marketplace", name.as_str().as_bytes()], // derived from name
         bump,
-        space = Marketplace::INIT_SPACE, 
+        space = 8 + Marketplace::INIT_SPACE, // 8 bytes for the anchor discriminator
     )]
     pub marketplace: Account<'info, Marketplace>,
 
@@ -34,7 +34,7 @@ pub struct Initialize<'info> {
         payer = admin,
         seeds = [b"rewards", marketplace.key().as_ref()],
         bump,
-        mint::decimals = 6,
+        mint::decimals = REWARDS_DECIMALS,
         mint::authority = marketplace,
     )]
     pub rewards_mint: InterfaceAccount<'info, Mint>,
@@ -46,7 +46,7 @@ pub struct Initialize<'info> {
     
 
 impl<'info> Initialize<'info> {
-    pub fn init(&mut self, name: String, fee: u16, bumps: &InitializeBumps ) -> Result <()> {
+    pub fn init(&mut self, name: String, fee: u16, rewards_per_sol: u64, discount_per_token: u64, bumps: &InitializeBumps ) -> Result <()> {
         
         self.marketplace.set_inner(Marketplace {
             admin: self.admin.key(),
@@ -54,8 +54,12 @@ impl<'info> Initialize<'info> {
             bump: bumps.marketplace,
             treasury_bump: bumps.treasury,
             rewards_bump: bumps.rewards_mint,
-            name
+            rewards_per_sol,
+            discount_per_token,
+            name,
+            payment_mints: Vec::new(),
         });
+        require!(self.marketplace.rewards_below_fee(), MarketplaceError::RewardsTooHigh);
         
         Ok(())
     }
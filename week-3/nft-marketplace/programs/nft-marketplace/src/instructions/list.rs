@@ -2,20 +2,19 @@ use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     metadata::{MasterEditionAccount, Metadata, MetadataAccount},
-    token_interface::{transfer_checked, TransferChecked, Mint, TokenAccount, TokenInterface},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
+use anchor_helpers::transfer_tokens;
 
-use crate::state::{listing::Listing, marketplace::Marketplace};
+use crate::{error::MarketplaceError, state::{listing::Listing, marketplace::Marketplace}};
 
 #[derive(Accounts)]
-#[instruction(name: String)]
-
 pub struct List<'info> {
     #[account(mut)]
     pub maker: Signer<'info>,
 
     #[account(
-        seeds = [b"marketplace", name.as_str().as_bytes()],
+        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
         bump = marketplace.bump,
     )]
     pub marketplace: Account<'info, Marketplace>,
@@ -56,8 +55,8 @@ pub struct List<'info> {
         ],                                                                  // End of the recipe.
         seeds::program = metadata_program.key(),                            // We promise: The official Metadata program is the one who made this info card.
         bump,                                                               // 
-        constraint = metadata.collection.as_ref().unwrap().key.as_ref() == collection_mint.key().as_ref(), // Rule #1: The NFT must be in the right collection.
-        constraint = metadata.collection.as_ref().unwrap().verified == true,                               // Rule #2: That collection must have an official "verified" sticker.
+        constraint = metadata.collection.as_ref().is_some_and(|collection| collection.key == collection_mint.key()) @ MarketplaceError::InvalidCollection, // Rule #1: The NFT must be in the right collection.
+        constraint = metadata.collection.as_ref().is_some_and(|collection| collection.verified) @ MarketplaceError::InvalidCollection,                      // Rule #2: That collection must have an official "verified" sticker.
     )]                                                                   // End of our checks for this account.
     pub metadata: Account<'info, MetadataAccount>,                     // This is the NFT's metadata we've been checking.
 
@@ -80,12 +79,17 @@ pub struct List<'info> {
 }
 
 impl<'info> List<'info> {
-    pub fn create_listing(&mut self, price: u64, bumps: &ListBumps) -> Result<()> {
+    pub fn create_listing(&mut self, price: u64, payment_mint: Option<Pubkey>, bumps: &ListBumps) -> Result<()> {
+        require!(price > 0, MarketplaceError::InvalidAmount);
+        if let Some(payment_mint) = payment_mint {
+            require!(self.marketplace.payment_mints.contains(&payment_mint), MarketplaceError::PaymentMintNotAllowed);
+        }
 
         self.listing.set_inner(Listing { 
             maker: self.maker.key(), 
             maker_mint: self.maker_mint.key(), 
             price,
+            payment_mint,
             bump: bumps.listing,
         });
         Ok(())
@@ -93,18 +97,15 @@ impl<'info> List<'info> {
 
     pub fn deposit_nft(&mut self) -> Result<()> {
 
-        let cpi_program = self.token_program.to_account_info();
-
-        let cpi_accounts = TransferChecked { 
-            from: self.maker_ata.to_account_info(), 
-            mint: self.maker_mint.to_account_info(), 
-            to: self.vault.to_account_info(),
-            authority: self.maker.to_account_info(), 
-        };
-
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-
-        transfer_checked(cpi_ctx, self.maker_ata.amount, self.maker_mint.decimals)?; // This securely moves the NFT (amount=1) from the maker's wallet to the vault, checking its decimal count (0 for NFTs).
+        transfer_tokens(
+            &self.maker_ata,
+            &self.vault,
+            self.maker_ata.amount,
+            &self.maker_mint,
+            &self.maker,
+            &self.token_program,
+            None,
+        )?; // This securely moves the NFT (amount=1) from the maker's wallet to the vault, checking its decimal count (0 for NFTs).
 
         Ok(())
     }
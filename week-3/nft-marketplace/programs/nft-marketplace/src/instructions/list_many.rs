@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    metadata::{MasterEditionAccount, Metadata, MetadataAccount},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use anchor_helpers::{create_ata_if_needed, load_account, transfer_tokens};
+
+use crate::{error::MarketplaceError, instructions::shared::create_pda_account, state::{listing::Listing, marketplace::Marketplace}};
+
+// accounts per NFT in remaining_accounts: maker_mint, maker_ata, listing, vault, metadata, master_edition
+pub const LIST_MANY_ACCOUNTS: usize = 6;
+
+#[derive(Accounts)]
+pub struct ListMany<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    pub collection_mint: InterfaceAccount<'info, Mint>, // every NFT in the batch has to be in this verified collection
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+impl<'info> ListMany<'info> {
+    // lists every NFT the same way `listing` does. Without all_or_nothing, NFTs that fail the checks
+    // (not in the collection, not held by the maker, already listed) are skipped instead of failing the batch
+    pub fn list_many(&self, prices: &[u64], payment_mint: Option<Pubkey>, all_or_nothing: bool, nft_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            !prices.is_empty() && nft_accounts.len() == prices.len() * LIST_MANY_ACCOUNTS,
+            MarketplaceError::InvalidBatchAccounts
+        );
+        if let Some(payment_mint) = payment_mint {
+            require!(self.marketplace.payment_mints.contains(&payment_mint), MarketplaceError::PaymentMintNotAllowed);
+        }
+
+        for (&price, accounts) in prices.iter().zip(nft_accounts.chunks(LIST_MANY_ACCOUNTS)) {
+            let (maker_mint, maker_ata, listing, vault) = (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+
+            // every check happens before any CPI, so a skipped NFT leaves nothing behind
+            let checked = self.check_nft(price, accounts);
+            let (mint, listing_bump) = match checked {
+                Ok(checked) => checked,
+                Err(_) if !all_or_nothing => continue,
+                Err(error) => return Err(error),
+            };
+
+            let marketplace_key = self.marketplace.key();
+            let seeds = &[marketplace_key.as_ref(), maker_mint.key.as_ref(), &[listing_bump]];
+            let signer_seeds = &[&seeds[..]];
+
+            // a listing PDA someone sent lamports to, or a vault ATA someone created, must not block the batch
+            create_pda_account(&self.maker, listing, 8 + Listing::INIT_SPACE, signer_seeds, &self.system_program)?;
+            Listing {
+                maker: self.maker.key(),
+                maker_mint: maker_mint.key(),
+                price,
+                payment_mint,
+                bump: listing_bump,
+            }
+            .try_serialize(&mut &mut listing.try_borrow_mut_data()?[..])?;
+
+            create_ata_if_needed(
+                &self.maker,
+                vault,
+                listing,
+                maker_mint,
+                &self.token_program,
+                &self.associated_token_program,
+                &self.system_program,
+            )?;
+
+            transfer_tokens(maker_ata, vault, 1, &mint, &self.maker, &self.token_program, None)?;
+        }
+
+        Ok(())
+    }
+
+    // the same account checks `List` does with constraints, returns the mint and the listing bump
+    fn check_nft(&self, price: u64, accounts: &'info [AccountInfo<'info>]) -> Result<(InterfaceAccount<'info, Mint>, u8)> {
+        let (maker_mint, maker_ata, listing, vault, metadata, master_edition) =
+            (&accounts[0], &accounts[1], &accounts[2], &accounts[3], &accounts[4], &accounts[5]);
+        require!(price > 0, MarketplaceError::InvalidAmount);
+
+        let marketplace_key = self.marketplace.key();
+        let (listing_key, listing_bump) = Pubkey::find_program_address(&[marketplace_key.as_ref(), maker_mint.key.as_ref()], &crate::ID);
+        require_keys_eq!(listing_key, listing.key(), MarketplaceError::InvalidBatchAccounts);
+        require!(listing.data_is_empty(), MarketplaceError::ListingUnavailable); // already listed (lamports alone don't count, see create_pda_account)
+
+        let mint = InterfaceAccount::<Mint>::try_from(maker_mint)?;
+
+        let maker_token_account = InterfaceAccount::<TokenAccount>::try_from(maker_ata)?;
+        require!(
+            maker_token_account.owner == self.maker.key() && maker_token_account.mint == maker_mint.key() && maker_token_account.amount == 1,
+            MarketplaceError::InvalidBatchAccounts
+        );
+
+        let vault_key = get_associated_token_address_with_program_id(&listing_key, maker_mint.key, self.token_program.key);
+        require_keys_eq!(vault_key, vault.key(), MarketplaceError::InvalidBatchAccounts);
+
+        let metadata_program = self.metadata_program.key();
+        let (metadata_key, _) = Pubkey::find_program_address(&[b"metadata", metadata_program.as_ref(), maker_mint.key.as_ref()], &metadata_program);
+        let metadata = load_account::<MetadataAccount>(metadata, &metadata_key, MarketplaceError::InvalidBatchAccounts)?;
+        require!(
+            metadata.collection.as_ref().is_some_and(|collection| collection.verified && collection.key == self.collection_mint.key()),
+            MarketplaceError::InvalidCollection
+        );
+
+        let (master_edition_key, _) = Pubkey::find_program_address(&[b"metadata", metadata_program.as_ref(), maker_mint.key.as_ref(), b"edition"], &metadata_program);
+        load_account::<MasterEditionAccount>(master_edition, &master_edition_key, MarketplaceError::InvalidBatchAccounts)?;
+
+        Ok((mint, listing_bump))
+    }
+}
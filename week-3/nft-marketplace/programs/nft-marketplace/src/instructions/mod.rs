@@ -1,9 +1,34 @@
+pub mod shared;
 pub mod initialize;
 pub mod list;
 pub mod delist;
 pub mod purchase;
+pub mod withdraw_treasury;
+pub mod update_fee;
+pub mod redeem_rewards;
+pub mod update_price;
+pub mod bid;
+pub mod accept_bid;
+pub mod create_auction;
+pub mod place_bid;
+pub mod settle_auction;
+pub mod refund_auction_bid;
+pub mod list_many;
+pub mod sweep;
 
 pub use initialize::*;
 pub use list::*;
 pub use delist::*;
-pub use purchase::*;
\ No newline at end of file
+pub use purchase::*;
+pub use withdraw_treasury::*;
+pub use update_fee::*;
+pub use redeem_rewards::*;
+pub use update_price::*;
+pub use bid::*;
+pub use accept_bid::*;
+pub use create_auction::*;
+pub use place_bid::*;
+pub use settle_auction::*;
+pub use refund_auction_bid::*;
+pub use list_many::*;
+pub use sweep::*;
\ No newline at end of file
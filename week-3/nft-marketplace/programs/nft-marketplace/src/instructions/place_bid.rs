@@ -0,0 +1,76 @@
+use anchor_lang::{prelude::*, system_program::{Transfer, transfer}};
+
+use crate::{error::MarketplaceError, state::{auction::{Auction, AuctionBid}, marketplace::Marketplace}};
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"auction", marketplace.key().as_ref(), auction.maker_mint.as_ref()],
+        bump = auction.bump,
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"auction_bid", auction.key().as_ref(), bidder.key().as_ref()],
+        bump,
+        space = 8 + AuctionBid::INIT_SPACE,
+    )]
+    pub auction_bid: Account<'info, AuctionBid>, // a bidder raising their own bid tops up the same escrow
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PlaceBid<'info> {
+    // amount is the bidder's whole bid, not the increase
+    pub fn place_bid(&mut self, amount: u64, bumps: &PlaceBidBumps) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < self.auction.end_time, MarketplaceError::AuctionEnded);
+
+        let minimum = match self.auction.highest_bidder {
+            None => self.auction.reserve_price,
+            Some(_) => self.auction.highest_bid
+                .checked_add(self.auction.min_increment)
+                .ok_or(MarketplaceError::MathOverflow)?,
+        };
+        require!(amount >= minimum, MarketplaceError::BidTooLow);
+
+        // an outbid bidder's escrow stays put until the refund crank or their next bid, so only the difference moves here
+        let top_up = amount.saturating_sub(self.auction_bid.amount);
+        if top_up > 0 {
+            let accounts = Transfer {
+                from: self.bidder.to_account_info(),
+                to: self.auction_bid.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+            transfer(cpi_ctx, top_up)?;
+        }
+
+        self.auction_bid.set_inner(AuctionBid {
+            auction: self.auction.key(),
+            bidder: self.bidder.key(),
+            amount: self.auction_bid.amount.max(amount),
+            bump: bumps.auction_bid,
+        });
+
+        self.auction.highest_bidder = Some(self.bidder.key());
+        self.auction.highest_bid = amount;
+
+        // anti-sniping: a late bid gives everyone else `extension` seconds to answer
+        let extended_end = now.checked_add(self.auction.extension).ok_or(MarketplaceError::MathOverflow)?;
+        self.auction.end_time = self.auction.end_time.max(extended_end);
+
+        Ok(())
+    }
+}
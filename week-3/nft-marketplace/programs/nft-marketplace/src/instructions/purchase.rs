@@ -1,7 +1,9 @@
 use anchor_lang::{prelude::*, system_program::{Transfer, transfer}};
-use anchor_spl::{token_interface::{Mint, TokenAccount, TokenInterface, CloseAccount, close_account, transfer_checked, TransferChecked}, associated_token::AssociatedToken};
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_spl::{token_interface::{Mint, TokenAccount, TokenInterface}, associated_token::{AssociatedToken, get_associated_token_address_with_program_id}, metadata::{Metadata, MetadataAccount}};
+use anchor_helpers::{close_token_account, create_ata_if_needed, mint_tokens, transfer_tokens};
 
-use crate::state::{listing::Listing, marketplace::Marketplace};
+use crate::{error::MarketplaceError, instructions::shared::royalty_shares, state::{listing::Listing, marketplace::{Marketplace, REWARDS_DECIMALS}, rewards_discount::RewardsDiscount}};
 
 #[derive(Accounts)]
 pub struct Purchase<'info> {
@@ -42,109 +44,272 @@ pub struct Purchase<'info> {
         mut,
         seeds = [b"rewards", marketplace.key().as_ref()],
         bump = marketplace.rewards_bump,
-        mint::decimals = 6,
+        mint::decimals = REWARDS_DECIMALS,
         mint::authority = marketplace,
     )]
     rewards: InterfaceAccount<'info, Mint>, // interfaceaccount accesses the solana program instructions in Mint
 
 
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = rewards,
+        associated_token::authority = taker,
+    )]
+    taker_rewards_ata: InterfaceAccount<'info, TokenAccount>, // buyer's reward tokens
+
+
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = rewards,
+        associated_token::authority = maker,
+    )]
+    maker_rewards_ata: InterfaceAccount<'info, TokenAccount>, // seller's reward tokens, the buyer pays its rent like the NFT ata
+
+
+    #[account(
+        mut,
+        seeds = [b"discount", marketplace.key().as_ref(), taker.key().as_ref()],
+        bump = discount.bump,
+    )]
+    discount: Option<Account<'info, RewardsDiscount>>, // only passed by buyers who burned reward tokens for a fee credit
+
+
+    #[account(
+        constraint = listing.payment_mint == Some(payment_mint.key()) @ MarketplaceError::InvalidPaymentMint,
+        mint::token_program = payment_token_program,
+    )]
+    payment_mint: Option<InterfaceAccount<'info, Mint>>, // the accounts below are only needed when the listing is priced in an SPL mint
+
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = taker,
+        associated_token::token_program = payment_token_program,
+    )]
+    taker_payment_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = payment_mint,
+        associated_token::authority = maker,
+        associated_token::token_program = payment_token_program,
+    )]
+    maker_payment_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = payment_token_program,
+    )]
+    treasury_payment_ata: Option<InterfaceAccount<'info, TokenAccount>>, // SPL fees collect in the treasury PDA's ata
+
+
     #[account(
         mut,
         close = maker,                     // we close the maker's Listing Account after the purchase is complete, and the rent gets sent back to the maker. Anchor handles this automatically after all our functions are executed.
         seeds = [marketplace.key().as_ref(), maker_mint.key().as_ref()],
         bump = listing.bump,
+        has_one = maker @ MarketplaceError::InvalidMaker, // the sale proceeds and the rent can only go to whoever listed the NFT
     )]
     listing: Account<'info, Listing>,
 
 
     #[account(
+        mut,
         seeds = [b"treasury", marketplace.key().as_ref()],
         bump = marketplace.treasury_bump,
     )]
-    treasury: SystemAccount<'info>, // systemaccount because it only transfers lamports
+    treasury: SystemAccount<'info>, // systemaccount because it only holds lamports, SPL fees go to its ata
 
 
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            maker_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    metadata: Account<'info, MetadataAccount>, // same metadata list checked, here for the royalty rate and the creators
+
+
+    metadata_program: Program<'info, Metadata>,
     associated_token_program: Program<'info, AssociatedToken>,
     system_program: Program<'info, System>,
     token_program: Interface<'info, TokenInterface>,
+    payment_token_program: Interface<'info, TokenInterface>, // owner of the payment mint, which can be Token-2022 while the NFT and rewards are classic. SOL listings pass either
 }
 
 impl<'info> Purchase<'info> {                                                   // The Purchase implementation contains three critical functions that execute the complete NFT purchase flow
-    pub fn send_sol(&self) -> Result<()> {                                      // 💰 STEP 1: Handle all SOL payments - split between seller and marketplace
-        let accounts = Transfer {                                               // First payment setup: Taker pays most of the price to the Maker (seller)
-            from: self.taker.to_account_info(),                                 // Money flows FROM: The buyer (taker) who's purchasing the NFT
-            to: self.maker.to_account_info(),                                   // Money flows TO: The original seller (maker) who listed the NFT
-        };                                                                      // End of the first payment routing
-
-        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts); // Create the payment context using Solana's System Program for SOL transfers
-
-        let amount = self.listing.price                                 // Calculate the marketplace fee that needs to be deducted:
-            .checked_mul(self.marketplace.fee as u64).unwrap()               // Take the listing price × marketplace fee percentage (e.g., 250 = 2.5%)
-            .checked_div(10000).unwrap();                                       // Divide by 10000 to convert basis points to actual fee amount
-
-        transfer(cpi_ctx, self.listing.price - amount)?;               // Execute the main payment: Send (listing price - marketplace fee) to the seller
-
-        let accounts = Transfer {                                 // Second payment setup: Taker pays marketplace fee to Treasury
-            from: self.taker.to_account_info(),                                 // Money flows FROM: Same buyer (taker) - they pay both amounts
-            to: self.treasury.to_account_info(),                                // Money flows TO: The marketplace treasury (collects fees)
-        };                                                                      // End of the second payment routing
-
-        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts); // Create second payment context for the fee transfer
-
-        transfer(cpi_ctx, amount)                                               // Execute the fee payment: Send the calculated marketplace fee to treasury
+    pub fn send_payment(&mut self, max_price: u64, creator_accounts: &'info [AccountInfo<'info>]) -> Result<()> {   // 💰 STEP 1: Handle all payments - split between seller, creators and marketplace, in SOL or the listing's SPL mint
+        let price = self.listing.price;
+        require!(price <= max_price, MarketplaceError::PriceLimitExceeded);     // the seller can't raise the price with update_price after the buyer signed
+        let pays_in_token = self.listing.payment_mint.is_some();
+        require!(                                                               // SPL listings need the payment mint and all three token accounts
+            !pays_in_token || (self.payment_mint.is_some() && self.taker_payment_ata.is_some() && self.maker_payment_ata.is_some() && self.treasury_payment_ata.is_some()),
+            MarketplaceError::MissingPaymentAccounts
+        );
+
+        let mut fee = self.marketplace                                          // Calculate the marketplace fee that needs to be deducted:
+            .fee_for(price)                                                     // listing price × marketplace fee in basis points (e.g., 250 = 2.5%) / 10000
+            .ok_or(MarketplaceError::MathOverflow)?;
+        let full_fee = fee;
+
+        if let Some(discount) = self.discount.as_mut().filter(|_| !pays_in_token) { // Burned reward tokens pay for (part of) the fee. The credit is in lamports so only SOL sales use it
+            let waived = fee.min(discount.fee_credit);
+            discount.fee_credit -= waived;
+            fee -= waived;
+        }
+
+        // Creator royalties use the same basis point math, with the rate the NFT itself sets, split between the verified creators.
+        // SOL sales pass one account per creator (their wallet), SPL sales pass two (wallet, then their ata for the payment mint).
+        // The cap is the full fee, before any discount, so a fee credit never lets royalties take more of the price
+        let shares = royalty_shares(&self.metadata, price, full_fee)?;
+        let accounts_per_creator = if pays_in_token { 2 } else { 1 };
+        require!(creator_accounts.len() == shares.len() * accounts_per_creator, MarketplaceError::InvalidCreatorAccounts);
+
+        let mut royalties_paid = 0u64;
+        for ((creator, share), accounts) in shares.into_iter().zip(creator_accounts.chunks(accounts_per_creator)) {
+            require_keys_eq!(creator, accounts[0].key(), MarketplaceError::InvalidCreatorAccounts);
+
+            if share == 0 {
+                continue;
+            }
+
+            let destination = if pays_in_token {
+                self.creator_token_account(&accounts[0], &accounts[1])?
+            } else {
+                accounts[0].clone()
+            };
+            self.pay(destination, share)?;
+
+            royalties_paid = royalties_paid.checked_add(share).ok_or(MarketplaceError::MathOverflow)?;
+        }
+
+        let seller_amount = price                                               // The seller gets whatever is left after the marketplace and the creators
+            .checked_sub(fee)
+            .and_then(|amount| amount.checked_sub(royalties_paid))
+            .ok_or(MarketplaceError::MathOverflow)?;
+
+        let (seller, treasury) = match (&self.maker_payment_ata, &self.treasury_payment_ata) {
+            (Some(maker_payment_ata), Some(treasury_payment_ata)) if pays_in_token => {
+                (maker_payment_ata.to_account_info(), treasury_payment_ata.to_account_info())
+            }
+            _ => (self.maker.to_account_info(), self.treasury.to_account_info()),
+        };
+
+        self.pay(seller, seller_amount)?;                                       // Execute the main payment: Send (listing price - marketplace fee - royalties) to the seller
+
+        self.pay(treasury, fee)                                                 // Execute the fee payment: Send the calculated marketplace fee to treasury
 
                                                              // ==========================================================================================================
-    }                                                        // =============== End of SOL distribution - seller gets most money, marketplace gets its cut ===============
+    }                                                        // =============== End of payment distribution - seller gets most money, creators and marketplace get their cut
                                                             // ==========================================================================================================
 
 
-    pub fn send_nft(&mut self) -> Result<()> {                                  // 🖼️ STEP 2: Transfer the NFT from escrow to buyer - this is the main event!
+    fn pay(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {          // One payment from the buyer, SOL straight to a wallet or SPL tokens to a token account
+        match (&self.payment_mint, &self.taker_payment_ata) {
+            (Some(payment_mint), Some(taker_payment_ata)) if self.listing.payment_mint.is_some() => {
+                transfer_tokens(taker_payment_ata, &to, amount, payment_mint, &self.taker, &self.payment_token_program, None)
+            }
+            _ => {
+                let accounts = Transfer {
+                    from: self.taker.to_account_info(),
+                    to,
+                };
+                let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+                transfer(cpi_ctx, amount)
+            }
+        }
+    }
+
+    fn creator_token_account(&self, creator: &AccountInfo<'info>, creator_ata: &AccountInfo<'info>) -> Result<AccountInfo<'info>> {
+        let payment_mint = self.payment_mint.as_ref().ok_or(MarketplaceError::MissingPaymentAccounts)?;
+        let expected = get_associated_token_address_with_program_id(creator.key, &payment_mint.key(), self.payment_token_program.key);
+        require_keys_eq!(expected, creator_ata.key(), MarketplaceError::InvalidCreatorAccounts);
+
+        if creator_ata.data_is_empty() {                                        // the buyer pays the rent for creators who never held the payment mint
+            create_ata_if_needed(
+                &self.taker,
+                creator_ata,
+                creator,
+                payment_mint,
+                &self.payment_token_program,
+                &self.associated_token_program,
+                &self.system_program,
+            )?;
+        }
+
+        Ok(creator_ata.clone())
+    }
+
+
+    pub fn mint_rewards(&mut self) -> Result<()> {                             // 🎁 STEP 2: Both sides of the sale earn reward tokens, scaled by the price
+        let amount = (self.listing.price as u128)
+            .checked_mul(self.marketplace.rewards_per_sol as u128)
+            .and_then(|amount| amount.checked_div(LAMPORTS_PER_SOL as u128))
+            .and_then(|amount| u64::try_from(amount).ok())
+            .ok_or(MarketplaceError::MathOverflow)?;
+        if amount == 0 || self.listing.payment_mint.is_some() {                 // rewards turned off, a sale too small to earn anything, or not priced in SOL
+            return Ok(());
+        }
+
+        let seeds = &[
+            b"marketplace",
+            self.marketplace.name.as_str().as_bytes(),
+            &[self.marketplace.bump],
+        ];
+
+        for to in [&self.taker_rewards_ata, &self.maker_rewards_ata] {
+            mint_tokens(&self.rewards, to, amount, &self.marketplace, &self.token_program, Some(&seeds[..]))?; // the marketplace PDA is the rewards mint authority
+        }
+
+        Ok(())
+    }
+
+    pub fn send_nft(&mut self) -> Result<()> {                                  // 🖼️ STEP 3: Transfer the NFT from escrow to buyer - this is the main event!
         let seeds = &[                                                          
             &self.marketplace.key().to_bytes()[..],                             // Ingredient #1: The marketplace's unique address (as bytes)
             &self.maker_mint.key().to_bytes()[..],                              // Ingredient #2: The NFT's unique mint address (as bytes)
             &[self.listing.bump],                                               // Ingredient #3: The bump seed that makes this PDA address valid
         ];                                                                      // End of the PDA signature recipe
-        let signer_seeds = &[&seeds[..]];                       // Package the seeds for Anchor to use as signing authority
-
-        let accounts = TransferChecked {                   // Setup the NFT transfer parameters - this is a secure token transfer:
-            from: self.vault.to_account_info(),                                 // NFT source: The escrow vault (controlled by listing PDA)
-            to: self.taker_ata.to_account_info(),                               // NFT destination: The buyer's token account (receives the NFT)
-            authority: self.listing.to_account_info(),                          // Transfer authority: The listing PDA (signs using the seeds above)
-            mint: self.maker_mint.to_account_info(),                            // NFT mint: Used to verify this is the correct token being transferred
-        };                                                                      // End of transfer setup
 
-        let cpi_ctx = CpiContext::new_with_signer(        // Create transfer context WITH the PDA signature capability:
-            self.token_program.to_account_info(),                               // Use the Token Program to handle the NFT transfer
-            accounts,                                                           // Pass the transfer parameters we just set up
-            signer_seeds,                                                       // Include the PDA seeds so listing can sign on behalf of the vault
-        );                                                                      // End of CPI context creation
-
-        transfer_checked(cpi_ctx, 1, self.maker_mint.decimals)           // Execute the transfer: Move exactly 1 NFT (amount=1) with decimal verification
+        transfer_tokens(                                                        // Execute the transfer: Move exactly 1 NFT (amount=1) with decimal verification
+            &self.vault,                                                        // NFT source: The escrow vault (controlled by listing PDA)
+            &self.taker_ata,                                                    // NFT destination: The buyer's token account (receives the NFT)
+            1,
+            &self.maker_mint,                                                   // NFT mint: Used to verify this is the correct token being transferred
+            &self.listing,                                                      // Transfer authority: The listing PDA (signs using the seeds above)
+            &self.token_program,                                                // Use the Token Program to handle the NFT transfer
+            Some(&seeds[..]),                                                   // Include the PDA seeds so listing can sign on behalf of the vault
+        )
 
     }                                                         // ==========================================================================================================
                                                               // ============== End of NFT transfer - buyer now owns the NFT! =============================================
                                                               // ==========================================================================================================
 
-    pub fn close_mint_vault(&mut self) -> Result<()> {                          // 🧹 STEP 3: Clean up by closing the vault and refunding rent to seller
+    pub fn close_mint_vault(&mut self) -> Result<()> {                          // 🧹 STEP 4: Clean up by closing the vault and refunding rent to seller
         let seeds = &[                                             // Recreate the same PDA signature recipe (listing needs to sign for closure):
             &self.marketplace.key().to_bytes()[..],                             // Ingredient #1: Marketplace address (same as NFT transfer)
             &self.maker_mint.key().to_bytes()[..],                              // Ingredient #2: NFT mint address (same as NFT transfer)
             &[self.listing.bump],                                               // Ingredient #3: Listing bump (same as NFT transfer)
         ];                                                                      // End of PDA signature recipe
-        let signer_seeds = &[&seeds[..]];                       // Package seeds for signing authority
-
-        let accounts = CloseAccount {                         // Setup account closure parameters:
-            account: self.vault.to_account_info(),                              // Account to close: The vault that held the NFT (now empty)
-            destination: self.maker.to_account_info(),                          // Rent refund goes to: The original seller (maker)
-            authority: self.listing.to_account_info(),                          // Closure authority: The listing PDA (same authority that transferred the NFT)
-        };                                                                      // End of closure setup
-
-        let cpi_ctx = CpiContext::new_with_signer(// Create closure context WITH PDA signing capability:
-            self.token_program.to_account_info(),                       // Use Token Program to close the token account
-            accounts,                                                           // Pass the closure parameters
-            signer_seeds                                                        // Include PDA seeds for signing authority
-        );                                                                      // End of CPI context
 
-        close_account(cpi_ctx)                                                  // Execute closure: Close vault & send remaining rent lamports to seller
+        close_token_account(                                                    // Execute closure: Close vault & send remaining rent lamports to seller
+            &self.vault,                                                        // Account to close: The vault that held the NFT (now empty)
+            &self.maker,                                                        // Rent refund goes to: The original seller (maker)
+            &self.listing,                                                      // Closure authority: The listing PDA (same authority that transferred the NFT)
+            &self.token_program,                                                // Use Token Program to close the token account
+            Some(&seeds[..]),                                                   // Include PDA seeds for signing authority
+        )
     }                                                                           // End of cleanup - all temporary accounts closed, rent refunded, including the rent in the Listings account, returned to maker.
 }                                                                               // End of Purchase implementation - complete transaction flow executed!
\ No newline at end of file
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
+
+use crate::{error::MarketplaceError, state::{marketplace::Marketplace, rewards_discount::RewardsDiscount}};
+
+#[derive(Accounts)]
+pub struct RedeemRewards<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"rewards", marketplace.key().as_ref()],
+        bump = marketplace.rewards_bump,
+    )]
+    pub rewards: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = rewards,
+        associated_token::authority = user,
+    )]
+    pub user_rewards_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"discount", marketplace.key().as_ref(), user.key().as_ref()],
+        bump,
+        space = 8 + RewardsDiscount::INIT_SPACE,
+    )]
+    pub discount: Account<'info, RewardsDiscount>, // keeps the fee credit until the user's next purchases use it up
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RedeemRewards<'info> {
+    pub fn redeem_rewards(&mut self, amount: u64, bumps: &RedeemRewardsBumps) -> Result<()> {
+        require!(amount > 0, MarketplaceError::InvalidAmount);
+
+        // credit is priced per whole token, so convert from base units with the mint's decimals
+        let credit = (amount as u128)
+            .checked_mul(self.marketplace.discount_per_token as u128)
+            .and_then(|credit| credit.checked_div(10u128.pow(self.rewards.decimals as u32)))
+            .and_then(|credit| u64::try_from(credit).ok())
+            .ok_or(MarketplaceError::MathOverflow)?;
+
+        let accounts = Burn {
+            mint: self.rewards.to_account_info(),
+            from: self.user_rewards_ata.to_account_info(),
+            authority: self.user.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), accounts);
+
+        burn(cpi_ctx, amount)?;
+
+        self.discount.fee_credit = self.discount.fee_credit
+            .checked_add(credit)
+            .ok_or(MarketplaceError::MathOverflow)?;
+        self.discount.bump = bumps.discount;
+
+        Ok(())
+    }
+}
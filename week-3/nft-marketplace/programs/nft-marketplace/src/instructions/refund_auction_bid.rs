@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::MarketplaceError, state::auction::{Auction, AuctionBid}};
+
+#[derive(Accounts)]
+pub struct RefundAuctionBid<'info> {
+    #[account(mut)]
+    pub bidder: SystemAccount<'info>,
+
+    /// CHECK: the auction this bid was placed on. It is gone once the auction is settled, which makes every bid left refundable
+    #[account(address = auction_bid.auction)]
+    pub auction: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = bidder, // the escrowed bid and the rent go back to the bidder
+        seeds = [b"auction_bid", auction.key().as_ref(), bidder.key().as_ref()],
+        bump = auction_bid.bump,
+        has_one = bidder,
+    )]
+    pub auction_bid: Account<'info, AuctionBid>,
+}
+
+impl<'info> RefundAuctionBid<'info> {
+    // permissionless crank, anyone can refund a bid that can no longer win
+    pub fn refund_auction_bid(&self) -> Result<()> {
+        if self.auction.data_is_empty() {
+            return Ok(());
+        }
+
+        let auction = Auction::try_deserialize(&mut &self.auction.try_borrow_data()?[..])?;
+        require!(auction.highest_bidder != Some(self.bidder.key()), MarketplaceError::BidStillWinning);
+        Ok(())
+    }
+}
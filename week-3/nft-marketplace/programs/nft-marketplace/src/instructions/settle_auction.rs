@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{Metadata, MetadataAccount},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use anchor_helpers::{close_token_account, transfer_tokens};
+
+use crate::{
+    error::MarketplaceError,
+    instructions::shared::pay_from_escrow,
+    state::{auction::{Auction, AuctionBid}, marketplace::Marketplace},
+};
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>, // anyone can settle an ended auction, they pay the winner's ata rent if it doesn't exist yet
+
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = winner.key() == auction.highest_bidder.unwrap_or(auction.maker) @ MarketplaceError::InvalidAuctionAccounts,
+    )]
+    pub winner: SystemAccount<'info>, // the highest bidder, or the maker when nobody bid and the NFT goes back
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    pub maker_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        close = maker,
+        seeds = [b"auction", marketplace.key().as_ref(), maker_mint.key().as_ref()],
+        bump = auction.bump,
+        has_one = maker,
+        has_one = maker_mint,
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        associated_token::mint = maker_mint,
+        associated_token::authority = auction,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = maker_mint,
+        associated_token::authority = winner,
+    )]
+    pub winner_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = winner, // whatever is left after paying the winning bid (rent, plus any escrow above it) goes back to the winner
+        seeds = [b"auction_bid", auction.key().as_ref(), winner.key().as_ref()],
+        bump = winner_bid.bump,
+    )]
+    pub winner_bid: Option<Account<'info, AuctionBid>>, // left out when nobody bid
+
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            maker_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+
+    pub metadata_program: Program<'info, Metadata>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SettleAuction<'info> {
+    pub fn pay_maker(&self, creator_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        require!(Clock::get()?.unix_timestamp >= self.auction.end_time, MarketplaceError::AuctionNotEnded);
+
+        if self.auction.highest_bidder.is_none() {
+            return Ok(()); // no sale, the NFT just goes back to the maker
+        }
+        let winner_bid = self.winner_bid.as_ref().ok_or(MarketplaceError::InvalidAuctionAccounts)?;
+
+        pay_from_escrow(
+            &winner_bid.to_account_info(),
+            self.auction.highest_bid,
+            &self.marketplace,
+            &self.metadata,
+            &self.treasury.to_account_info(),
+            &self.maker.to_account_info(),
+            creator_accounts,
+        )
+    }
+
+    pub fn send_nft_and_close_vault(&mut self) -> Result<()> {
+        let marketplace_key = self.marketplace.key();
+        let maker_mint_key = self.maker_mint.key();
+        let seeds = &[
+            b"auction",
+            marketplace_key.as_ref(),
+            maker_mint_key.as_ref(),
+            &[self.auction.bump],
+        ];
+
+        transfer_tokens(
+            &self.vault,
+            &self.winner_ata,
+            1,
+            &self.maker_mint,
+            &self.auction,
+            &self.token_program,
+            Some(&seeds[..]),
+        )?;
+
+        close_token_account(&self.vault, &self.maker, &self.auction, &self.token_program, Some(&seeds[..]))
+    }
+}
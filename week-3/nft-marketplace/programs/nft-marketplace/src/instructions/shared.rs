@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::metadata::MetadataAccount;
+use anchor_helpers::bps_of;
+
+use crate::{error::MarketplaceError, state::marketplace::Marketplace};
+
+// royalty owed to each verified creator, in the order the metadata lists them.
+// unverified creators get nothing (anyone can add themselves unverified), their part stays with the seller.
+// royalties are capped at what's left after the marketplace fee, otherwise a high royalty NFT could never be sold
+pub fn royalty_shares(metadata: &MetadataAccount, price: u64, fee: u64) -> Result<Vec<(Pubkey, u64)>> {
+    let after_fee = price.checked_sub(fee).ok_or(MarketplaceError::MathOverflow)?;
+    let royalties = bps_of(price, metadata.seller_fee_basis_points)
+        .ok_or(MarketplaceError::MathOverflow)?
+        .min(after_fee);
+
+    metadata.creators
+        .iter()
+        .flatten()
+        .filter(|creator| creator.verified)
+        .map(|creator| {
+            let share = royalties
+                .checked_mul(creator.share as u64) // share is a percentage (all shares add up to 100)
+                .and_then(|amount| amount.checked_div(100))
+                .ok_or(MarketplaceError::MathOverflow)?;
+            Ok((creator.address, share))
+        })
+        .collect()
+}
+
+// pays out a SOL sale from a program owned escrow (a Bid or an AuctionBid): the marketplace fee to the treasury,
+// royalties to the creator wallets passed as remaining accounts, and the rest to the seller
+pub fn pay_from_escrow<'info>(
+    escrow: &AccountInfo<'info>,
+    price: u64,
+    marketplace: &Marketplace,
+    metadata: &MetadataAccount,
+    treasury: &AccountInfo<'info>,
+    seller: &AccountInfo<'info>,
+    creator_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let fee = marketplace.fee_for(price).ok_or(MarketplaceError::MathOverflow)?;
+
+    let shares = royalty_shares(metadata, price, fee)?;
+    require!(creator_accounts.len() == shares.len(), MarketplaceError::InvalidCreatorAccounts);
+
+    let mut royalties_paid = 0u64;
+    for ((creator, share), creator_account) in shares.into_iter().zip(creator_accounts) {
+        require_keys_eq!(creator, creator_account.key(), MarketplaceError::InvalidCreatorAccounts);
+        move_lamports(escrow, creator_account, share)?;
+        royalties_paid = royalties_paid.checked_add(share).ok_or(MarketplaceError::MathOverflow)?;
+    }
+
+    let seller_amount = price
+        .checked_sub(fee)
+        .and_then(|amount| amount.checked_sub(royalties_paid))
+        .ok_or(MarketplaceError::MathOverflow)?;
+
+    move_lamports(escrow, treasury, fee)?;
+    move_lamports(escrow, seller, seller_amount)
+}
+
+// the escrow is owned by this program, so it can hand out lamports directly instead of going through the system program
+pub fn move_lamports<'info>(from: &AccountInfo<'info>, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+    from.sub_lamports(amount)?;
+    to.add_lamports(amount)?;
+    Ok(())
+}
+
+// creates a program owned PDA the way anchor's `init` does: create_account fails on an address that already holds lamports,
+// so anyone could block it by sending a few lamports there first. Those get topped up to rent exemption, allocated and assigned instead
+pub fn create_pda_account<'info>(
+    payer: &Signer<'info>,
+    account: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[&[u8]]],
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let system_program = system_program.to_account_info();
+
+    if account.lamports() == 0 {
+        let accounts = CreateAccount { from: payer.to_account_info(), to: account.clone() };
+        return create_account(
+            CpiContext::new_with_signer(system_program, accounts, signer_seeds),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let shortfall = rent.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        let accounts = Transfer { from: payer.to_account_info(), to: account.clone() };
+        transfer(CpiContext::new(system_program.clone(), accounts), shortfall)?;
+    }
+    allocate(
+        CpiContext::new_with_signer(system_program.clone(), Allocate { account_to_allocate: account.clone() }, signer_seeds),
+        space as u64,
+    )?;
+    assign(
+        CpiContext::new_with_signer(system_program, Assign { account_to_assign: account.clone() }, signer_seeds),
+        &crate::ID,
+    )
+}
@@ -0,0 +1,166 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    metadata::{Metadata, MetadataAccount},
+    token_interface::{Mint, TokenInterface},
+};
+use anchor_helpers::{close_token_account, create_ata_if_needed, load_account, transfer_tokens};
+
+use crate::{
+    error::MarketplaceError,
+    instructions::shared::royalty_shares,
+    state::{listing::Listing, marketplace::Marketplace},
+};
+
+// accounts per NFT in remaining_accounts: maker, maker_mint, listing, vault, taker_ata, metadata,
+// then the NFT's verified creators' wallets in metadata order
+pub const SWEEP_ACCOUNTS: usize = 6;
+
+#[derive(Accounts)]
+pub struct Sweep<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+// a listing that passed the checks, ready to be bought
+struct SweepItem<'info> {
+    listing: Account<'info, Listing>,
+    mint: InterfaceAccount<'info, Mint>,
+    metadata: Account<'info, MetadataAccount>,
+    creators: &'info [AccountInfo<'info>],
+}
+
+impl<'info> Sweep<'info> {
+    // buys every listing like `purchase` does, for SOL listings only and without reward minting or fee credit.
+    // The total price can't go above max_total_price. Without all_or_nothing, listings that are gone (sold or delisted
+    // since the transaction was built) or would go over the limit are skipped instead of failing the sweep
+    pub fn sweep(&self, max_total_price: u64, all_or_nothing: bool, mut nft_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(!nft_accounts.is_empty(), MarketplaceError::InvalidBatchAccounts);
+
+        let mut total_price = 0u64;
+        while !nft_accounts.is_empty() {
+            require!(nft_accounts.len() >= SWEEP_ACCOUNTS, MarketplaceError::InvalidBatchAccounts);
+            let (accounts, rest) = nft_accounts.split_at(SWEEP_ACCOUNTS);
+
+            // the number of creator accounts comes from the metadata, so it's read before the checks
+            let creator_count = Account::<MetadataAccount>::try_from(&accounts[5])
+                .map(|metadata| metadata.creators.iter().flatten().filter(|creator| creator.verified).count())?;
+            require!(rest.len() >= creator_count, MarketplaceError::InvalidBatchAccounts);
+            let (creators, rest) = rest.split_at(creator_count);
+            nft_accounts = rest;
+
+            // every check happens before any transfer, so a skipped listing leaves nothing behind
+            let checked = self.check_listing(accounts, creators).and_then(|item| {
+                let price = item.listing.price;
+                let new_total = total_price.checked_add(price).ok_or(MarketplaceError::MathOverflow)?;
+                require!(new_total <= max_total_price, MarketplaceError::PriceLimitExceeded);
+                Ok((item, new_total))
+            });
+            let (item, new_total) = match checked {
+                Ok(checked) => checked,
+                Err(_) if !all_or_nothing => continue,
+                Err(error) => return Err(error),
+            };
+            total_price = new_total;
+
+            self.buy(accounts, item)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_listing(&self, accounts: &'info [AccountInfo<'info>], creators: &'info [AccountInfo<'info>]) -> Result<SweepItem<'info>> {
+        let (maker, maker_mint, listing, vault, taker_ata, metadata) =
+            (&accounts[0], &accounts[1], &accounts[2], &accounts[3], &accounts[4], &accounts[5]);
+
+        let marketplace_key = self.marketplace.key();
+        let (listing_key, _) = Pubkey::find_program_address(&[marketplace_key.as_ref(), maker_mint.key.as_ref()], &crate::ID);
+        require_keys_eq!(listing_key, listing.key(), MarketplaceError::InvalidBatchAccounts);
+        require!(!listing.data_is_empty(), MarketplaceError::ListingUnavailable); // sold or delisted
+
+        let listing = Account::<Listing>::try_from(listing)?;
+        require_keys_eq!(listing.maker, maker.key(), MarketplaceError::ListingUnavailable); // sold and listed again by someone else
+        require!(listing.payment_mint.is_none(), MarketplaceError::InvalidPaymentMint);
+
+        let mint = InterfaceAccount::<Mint>::try_from(maker_mint)?;
+
+        let vault_key = get_associated_token_address_with_program_id(&listing_key, maker_mint.key, self.token_program.key);
+        require_keys_eq!(vault_key, vault.key(), MarketplaceError::InvalidBatchAccounts);
+        let taker_ata_key = get_associated_token_address_with_program_id(self.taker.key, maker_mint.key, self.token_program.key);
+        require_keys_eq!(taker_ata_key, taker_ata.key(), MarketplaceError::InvalidBatchAccounts);
+
+        let metadata_program = self.metadata_program.key();
+        let (metadata_key, _) = Pubkey::find_program_address(&[b"metadata", metadata_program.as_ref(), maker_mint.key.as_ref()], &metadata_program);
+        let metadata = load_account::<MetadataAccount>(metadata, &metadata_key, MarketplaceError::InvalidBatchAccounts)?;
+
+        Ok(SweepItem { listing, mint, metadata, creators })
+    }
+
+    fn buy(&self, accounts: &'info [AccountInfo<'info>], item: SweepItem<'info>) -> Result<()> {
+        let (maker, maker_mint, vault, taker_ata) = (&accounts[0], &accounts[1], &accounts[3], &accounts[4]);
+        let price = item.listing.price;
+
+        let fee = self.marketplace.fee_for(price).ok_or(MarketplaceError::MathOverflow)?;
+
+        let mut royalties_paid = 0u64;
+        for ((creator, share), creator_account) in royalty_shares(&item.metadata, price, fee)?.into_iter().zip(item.creators) {
+            require_keys_eq!(creator, creator_account.key(), MarketplaceError::InvalidCreatorAccounts);
+            if share == 0 {
+                continue;
+            }
+            self.pay(creator_account, share)?;
+            royalties_paid = royalties_paid.checked_add(share).ok_or(MarketplaceError::MathOverflow)?;
+        }
+
+        let seller_amount = price
+            .checked_sub(fee)
+            .and_then(|amount| amount.checked_sub(royalties_paid))
+            .ok_or(MarketplaceError::MathOverflow)?;
+        self.pay(maker, seller_amount)?;
+        self.pay(&self.treasury.to_account_info(), fee)?;
+
+        create_ata_if_needed(
+            &self.taker,
+            taker_ata,
+            &self.taker,
+            maker_mint,
+            &self.token_program,
+            &self.associated_token_program,
+            &self.system_program,
+        )?;
+
+        let marketplace_key = self.marketplace.key();
+        let seeds = &[marketplace_key.as_ref(), maker_mint.key.as_ref(), &[item.listing.bump]];
+
+        transfer_tokens(vault, taker_ata, 1, &item.mint, &item.listing, &self.token_program, Some(&seeds[..]))?;
+        close_token_account(vault, maker, &item.listing, &self.token_program, Some(&seeds[..]))?;
+
+        item.listing.close(maker.clone()) // listing rent goes back to the maker, same as `purchase`
+    }
+
+    fn pay(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let accounts = Transfer {
+            from: self.taker.to_account_info(),
+            to: to.clone(),
+        };
+        transfer(CpiContext::new(self.system_program.to_account_info(), accounts), amount)
+    }
+}
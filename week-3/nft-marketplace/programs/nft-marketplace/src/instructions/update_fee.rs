@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::MarketplaceError, state::marketplace::{Marketplace, MAX_FEE_BPS, MAX_PAYMENT_MINTS}};
+
+#[derive(Accounts)]
+pub struct UpdateFee<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
+        bump = marketplace.bump,
+        has_one = admin @ MarketplaceError::Unauthorized,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+}
+
+impl<'info> UpdateFee<'info> {
+    pub fn update_fee(&mut self, fee: u16) -> Result<()> {
+        require!(fee <= MAX_FEE_BPS, MarketplaceError::FeeTooHigh); // capped so the admin can't take the whole sale
+
+        self.marketplace.fee = fee;
+        require!(self.marketplace.rewards_below_fee(), MarketplaceError::RewardsTooHigh); // a lower fee can make the current rewards farmable
+        Ok(())
+    }
+
+    pub fn update_rewards(&mut self, rewards_per_sol: u64, discount_per_token: u64) -> Result<()> {
+        self.marketplace.rewards_per_sol = rewards_per_sol;
+        self.marketplace.discount_per_token = discount_per_token;
+        require!(self.marketplace.rewards_below_fee(), MarketplaceError::RewardsTooHigh);
+        Ok(())
+    }
+
+    pub fn set_payment_mint(&mut self, mint: Pubkey, allowed: bool) -> Result<()> {
+        let payment_mints = &mut self.marketplace.payment_mints;
+        payment_mints.retain(|payment_mint| *payment_mint != mint);
+        if allowed {
+            require!(payment_mints.len() < MAX_PAYMENT_MINTS, MarketplaceError::TooManyPaymentMints);
+            payment_mints.push(mint);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::MarketplaceError, state::{listing::Listing, marketplace::Marketplace}};
+
+#[derive(Accounts)]
+pub struct UpdatePrice<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [marketplace.key().as_ref(), listing.maker_mint.as_ref()],
+        bump = listing.bump,
+        has_one = maker @ MarketplaceError::Unauthorized, // only whoever listed the NFT can reprice it
+    )]
+    pub listing: Account<'info, Listing>,
+}
+
+impl<'info> UpdatePrice<'info> {
+    pub fn update_price(&mut self, price: u64) -> Result<()> {
+        require!(price > 0, MarketplaceError::InvalidAmount);
+
+        self.listing.price = price; // stays in the same mint, the NFT never leaves the vault
+        Ok(())
+    }
+}
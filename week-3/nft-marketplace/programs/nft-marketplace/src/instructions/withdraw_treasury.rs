@@ -0,0 +1,116 @@
+use anchor_lang::{prelude::*, system_program::{Transfer, transfer}};
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+use anchor_helpers::transfer_tokens;
+
+use crate::{error::MarketplaceError, state::marketplace::Marketplace};
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
+        bump = marketplace.bump,
+        has_one = admin @ MarketplaceError::Unauthorized, // only the admin who set up the marketplace can take the fees out
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>, // holds the collected fees as plain lamports
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawTreasury<'info> {
+    pub fn withdraw_treasury(&mut self, amount: u64) -> Result<()> {
+        // the treasury has to stay rent exempt, otherwise the next fee payment under the minimum would fail
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        let available = self.treasury.lamports().saturating_sub(rent_exempt_minimum);
+        require!(amount <= available, MarketplaceError::InsufficientTreasuryBalance);
+
+        let marketplace_key = self.marketplace.key();
+        let seeds = &[
+            b"treasury",
+            marketplace_key.as_ref(),
+            &[self.marketplace.treasury_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let accounts = Transfer {
+            from: self.treasury.to_account_info(),                              // the treasury PDA signs with its seeds
+            to: self.admin.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(self.system_program.to_account_info(), accounts, signer_seeds);
+
+        transfer(cpi_ctx, amount)
+    }
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasuryTokens<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
+        bump = marketplace.bump,
+        has_one = admin @ MarketplaceError::Unauthorized,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_payment_ata: InterfaceAccount<'info, TokenAccount>, // fees from SPL listings
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = payment_mint,
+        associated_token::authority = admin,
+        associated_token::token_program = token_program,
+    )]
+    pub admin_payment_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawTreasuryTokens<'info> {
+    pub fn withdraw_treasury_tokens(&mut self, amount: u64) -> Result<()> {
+        let marketplace_key = self.marketplace.key();
+        let seeds = &[
+            b"treasury",
+            marketplace_key.as_ref(),
+            &[self.marketplace.treasury_bump],
+        ];
+
+        transfer_tokens(
+            &self.treasury_payment_ata,
+            &self.admin_payment_ata,
+            amount,
+            &self.payment_mint,
+            &self.treasury,
+            &self.token_program,
+            Some(&seeds[..]),
+        )
+    }
+}
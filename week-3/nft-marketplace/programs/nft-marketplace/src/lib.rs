@@ -6,19 +6,22 @@ pub mod instructions;
 use instructions::*;
 pub mod state;
 use state::*;
+pub mod error;
 
 #[program]
 pub mod nft_marketplace {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>, name: String, fee: u16) -> Result<()> {
-        ctx.accounts.init(name, fee, &ctx.bumps)?;
+    pub fn initialize(ctx: Context<Initialize>, name: String, fee: u16, rewards_per_sol: u64, discount_per_token: u64) -> Result<()> {
+        require!(fee <= MAX_FEE_BPS, error::MarketplaceError::FeeTooHigh);
+        ctx.accounts.init(name, fee, rewards_per_sol, discount_per_token, &ctx.bumps)?;
         Ok(())
     }
 
 
-    pub fn listing(ctx: Context<List>, name: String, price: u64) -> Result<()> {
-        ctx.accounts.create_listing(price, &ctx.bumps)?;
+    // payment_mint: None lists in SOL, otherwise one of the marketplace's allowed SPL mints
+    pub fn listing(ctx: Context<List>, price: u64, payment_mint: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.create_listing(price, payment_mint, &ctx.bumps)?;
         ctx.accounts.deposit_nft()?;
         Ok(())
     }
@@ -28,11 +31,90 @@ pub mod nft_marketplace {
         Ok(())
     }
 
-    pub fn purchase(ctx: Context<Purchase>) -> Result<()> {
-        ctx.accounts.send_sol()?;
+    // one price per NFT. remaining_accounts: for each NFT, maker_mint, maker_ata, listing, vault, metadata, master_edition
+    pub fn list_many<'info>(ctx: Context<'_, '_, 'info, 'info, ListMany<'info>>, prices: Vec<u64>, payment_mint: Option<Pubkey>, all_or_nothing: bool) -> Result<()> {
+        ctx.accounts.list_many(&prices, payment_mint, all_or_nothing, ctx.remaining_accounts)
+    }
+
+    pub fn update_price(ctx: Context<UpdatePrice>, price: u64) -> Result<()> {
+        ctx.accounts.update_price(price)
+    }
+
+    // remaining_accounts: the verified creators from the NFT's metadata, in metadata order, they get the royalties.
+    // SOL listings pass each creator's wallet (writable), SPL listings pass the wallet and then its ata for the payment mint (writable).
+    // max_price is the most the buyer pays, so a price raised after they signed fails the purchase instead
+    pub fn purchase<'info>(ctx: Context<'_, '_, 'info, 'info, Purchase<'info>>, max_price: u64) -> Result<()> {
+        ctx.accounts.send_payment(max_price, ctx.remaining_accounts)?;
+        ctx.accounts.mint_rewards()?;
         ctx.accounts.send_nft()?;
         ctx.accounts.close_mint_vault()
     }
+
+    // remaining_accounts: for each NFT, maker, maker_mint, listing, vault, taker_ata, metadata (all writable but the mint and metadata),
+    // then its verified creators' wallets in metadata order (writable)
+    pub fn sweep<'info>(ctx: Context<'_, '_, 'info, 'info, Sweep<'info>>, max_total_price: u64, all_or_nothing: bool) -> Result<()> {
+        ctx.accounts.sweep(max_total_price, all_or_nothing, ctx.remaining_accounts)
+    }
+
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_treasury(amount)
+    }
+
+    pub fn withdraw_treasury_tokens(ctx: Context<WithdrawTreasuryTokens>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_treasury_tokens(amount)
+    }
+
+    pub fn update_fee(ctx: Context<UpdateFee>, fee: u16) -> Result<()> {
+        ctx.accounts.update_fee(fee)
+    }
+
+    pub fn update_rewards(ctx: Context<UpdateFee>, rewards_per_sol: u64, discount_per_token: u64) -> Result<()> {
+        ctx.accounts.update_rewards(rewards_per_sol, discount_per_token)
+    }
+
+    pub fn set_payment_mint(ctx: Context<UpdateFee>, mint: Pubkey, allowed: bool) -> Result<()> {
+        ctx.accounts.set_payment_mint(mint, allowed)
+    }
+
+    pub fn redeem_rewards(ctx: Context<RedeemRewards>, amount: u64) -> Result<()> {
+        ctx.accounts.redeem_rewards(amount, &ctx.bumps)
+    }
+
+    // kind says whether target is one NFT's mint or a collection mint. Bids are in SOL
+    pub fn make_bid(ctx: Context<MakeBid>, target: Pubkey, kind: BidTarget, amount: u64) -> Result<()> {
+        ctx.accounts.make_bid(target, kind, amount, &ctx.bumps)
+    }
+
+    pub fn cancel_bid(_ctx: Context<CancelBid>) -> Result<()> {
+        Ok(())
+    }
+
+    // remaining_accounts: the verified creators' wallets from the NFT's metadata, in metadata order (writable)
+    pub fn accept_bid<'info>(ctx: Context<'_, '_, 'info, 'info, AcceptBid<'info>>) -> Result<()> {
+        ctx.accounts.check_target()?;
+        ctx.accounts.pay_seller(ctx.remaining_accounts)?;
+        ctx.accounts.send_nft()
+    }
+
+    // duration and extension are in seconds
+    pub fn create_auction(ctx: Context<CreateAuction>, reserve_price: u64, min_increment: u64, duration: i64, extension: i64) -> Result<()> {
+        ctx.accounts.create_auction(reserve_price, min_increment, duration, extension, &ctx.bumps)?;
+        ctx.accounts.deposit_nft()
+    }
+
+    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+        ctx.accounts.place_bid(amount, &ctx.bumps)
+    }
+
+    // remaining_accounts: the verified creators' wallets from the NFT's metadata, in metadata order (writable)
+    pub fn settle_auction<'info>(ctx: Context<'_, '_, 'info, 'info, SettleAuction<'info>>) -> Result<()> {
+        ctx.accounts.pay_maker(ctx.remaining_accounts)?;
+        ctx.accounts.send_nft_and_close_vault()
+    }
+
+    pub fn refund_auction_bid(ctx: Context<RefundAuctionBid>) -> Result<()> {
+        ctx.accounts.refund_auction_bid()
+    }
     
 
 
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Auction { // like Listing, this PDA owns the vault holding the NFT until the auction is settled
+    pub maker: Pubkey,
+    pub maker_mint: Pubkey,
+    pub reserve_price: u64, // the first bid has to be at least this
+    pub min_increment: u64, // every later bid has to beat the highest by at least this
+    pub end_time: i64,
+    pub extension: i64, // seconds, a bid this close to the end pushes end_time out to now + extension
+    pub highest_bidder: Option<Pubkey>,
+    pub highest_bid: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AuctionBid { // one per bidder per auction, escrows their bid in lamports on top of rent
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum BidTarget {
+    Nft,        // target is one NFT's mint
+    Collection, // target is a collection mint, any verified NFT in it can fill the bid
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Bid { // escrows the offered SOL on top of its own rent until a seller accepts or the bidder cancels
+    pub bidder: Pubkey,
+    pub target: Pubkey,
+    pub kind: BidTarget,
+    pub amount: u64, // lamports the seller gets (minus fee and royalties)
+    pub bump: u8,
+}
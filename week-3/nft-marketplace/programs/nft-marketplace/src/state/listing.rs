@@ -5,6 +5,7 @@ use anchor_lang::prelude::*;
 pub struct Listing { // this is the PDA holding NFT up for listing
     pub maker: Pubkey, // who did the listing
     pub maker_mint: Pubkey, // the unique mint of the NFT
-    pub price: u64,
+    pub price: u64, // in lamports, or base units of payment_mint
+    pub payment_mint: Option<Pubkey>, // None for SOL listings
     pub bump: u8,
 }
\ No newline at end of file
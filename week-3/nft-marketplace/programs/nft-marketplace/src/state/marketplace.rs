@@ -1,4 +1,15 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_helpers::bps_of;
+
+// 10% in basis points, the most the admin can charge on a sale
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+// decimals of the rewards mint
+pub const REWARDS_DECIMALS: u8 = 6;
+
+// how many SPL mints (USDC, SCRAP, ...) listings can be priced in, besides SOL
+pub const MAX_PAYMENT_MINTS: usize = 4;
 
 #[account]
 #[derive(InitSpace)]
@@ -9,7 +20,29 @@ pub struct Marketplace {
     pub bump: u8,
     pub treasury_bump: u8,
     pub rewards_bump: u8,
+    pub rewards_per_sol: u64, // reward base units minted to the buyer and to the seller for every SOL of a sale. Only sales mint, listing and delisting would be free to farm
+    pub discount_per_token: u64, // lamports of marketplace fee waived for every whole reward token burned
     #[max_len(32)]
     pub name: String,
+    #[max_len(MAX_PAYMENT_MINTS)]
+    pub payment_mints: Vec<Pubkey>,
+}
+
+impl Marketplace {
+    // the marketplace's cut of a sale, in the same units as the price
+    pub fn fee_for(&self, price: u64) -> Option<u64> {
+        bps_of(price, self.fee)
+    }
+
+    // a SOL of sales mints rewards to both sides, and burning those has to be worth less fee credit than the fee that SOL paid.
+    // otherwise selling an NFT back and forth between two of your own wallets earns more than it costs
+    pub fn rewards_below_fee(&self) -> bool {
+        let credit = (self.rewards_per_sol as u128)
+            .checked_mul(2)
+            .and_then(|rewards| rewards.checked_mul(self.discount_per_token as u128))
+            .map(|credit| credit / 10u128.pow(REWARDS_DECIMALS as u32));
+        let fee = self.fee as u128 * LAMPORTS_PER_SOL as u128 / 10_000;
 
-}
\ No newline at end of file
+        credit.is_some_and(|credit| credit == 0 || credit < fee)
+    }
+}
@@ -1,3 +1,12 @@
 pub mod marketplace;
 pub mod listing;
+pub mod rewards_discount;
+pub mod bid;
+pub mod auction;
+
+pub use marketplace::*;
+pub use listing::*;
+pub use rewards_discount::*;
+pub use bid::*;
+pub use auction::*;
 
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct RewardsDiscount { // one per user per marketplace, filled by burning reward tokens
+    pub fee_credit: u64, // lamports of fee the user doesn't pay on their next purchases
+    pub bump: u8,
+}
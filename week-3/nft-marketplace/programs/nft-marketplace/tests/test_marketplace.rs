@@ -0,0 +1,1045 @@
+use std::fs;
+use anchor_lang::{system_program, AccountDeserialize, InstructionData, Space, ToAccountMetas};
+use anchor_spl::{metadata::mpl_token_metadata, token_2022};
+use litesvm::LiteSVM;
+use nft_marketplace::{error::MarketplaceError, state::{Auction, AuctionBid, BidTarget, Marketplace}};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::{get_associated_token_address, get_associated_token_address_with_program_id};
+
+const MARKETPLACE_NAME: &str = "turbin3";
+const PRICE: u64 = 1_000_000_000;
+const AUCTION_DURATION: i64 = 3_600;
+const AUCTION_EXTENSION: i64 = 300;
+
+// Everything a test needs after a marketplace is set up and `maker` has listed `maker_mint`
+struct Listed {
+    svm: LiteSVM,
+    admin: Keypair,
+    maker: Keypair,
+    marketplace: Pubkey,
+    maker_mint: Pubkey,
+    collection_mint: Pubkey,
+}
+
+fn setup() -> (LiteSVM, Keypair) {
+    let mut svm = LiteSVM::new();
+
+    // Load the compiled program - it's in this program's target/deploy directory
+    let program_name = env!("CARGO_PKG_NAME").replace('-', "_");
+    let program_path = format!("target/deploy/{}.so", program_name);
+    let program_bytes = fs::read(&program_path)
+        .unwrap_or_else(|e| panic!("Failed to read program bytes at {}: {}", program_path, e));
+    svm.add_program(nft_marketplace::ID, &program_bytes);
+
+    // The marketplace never calls into token metadata, it only checks the program account is executable
+    // and reads metadata accounts it owns. Any program bytes work as a stand-in for those checks.
+    svm.add_program(mpl_token_metadata::ID, &program_bytes);
+
+    let admin = Keypair::new();
+    svm.airdrop(&admin.pubkey(), 10_000_000_000).unwrap();
+
+    (svm, admin)
+}
+
+fn send(svm: &mut LiteSVM, instruction: Instruction, signers: &[&Keypair]) -> Result<(), TransactionError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&signers[0].pubkey()),
+        signers,
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(transaction).map(|_| ()).map_err(|failed| failed.err)
+}
+
+fn assert_marketplace_error(result: Result<(), TransactionError>, expected: MarketplaceError) {
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(0, InstructionError::Custom(expected.into()))),
+    );
+}
+
+fn set_mint(svm: &mut LiteSVM, mint: Pubkey, authority: Pubkey, supply: u64) {
+    set_mint_of(svm, mint, authority, supply, spl_token::ID);
+}
+
+// Same base layout for either token program, Token-2022 takes it as a mint without extensions
+fn set_mint_of(svm: &mut LiteSVM, mint: Pubkey, authority: Pubkey, supply: u64, token_program: Pubkey) {
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        mint_authority: COption::Some(authority),
+        supply,
+        decimals: 0,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    set_account(svm, mint, data, token_program);
+}
+
+fn set_token_account(svm: &mut LiteSVM, mint: Pubkey, owner: Pubkey, amount: u64) -> Pubkey {
+    set_token_account_of(svm, mint, owner, amount, spl_token::ID)
+}
+
+fn set_token_account_of(svm: &mut LiteSVM, mint: Pubkey, owner: Pubkey, amount: u64, token_program: Pubkey) -> Pubkey {
+    let address = get_associated_token_address_with_program_id(&owner, &mint, &token_program);
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint,
+        owner,
+        amount,
+        delegate: COption::None,
+        state: spl_token::state::AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    set_account(svm, address, data, token_program);
+    address
+}
+
+fn set_account(svm: &mut LiteSVM, address: Pubkey, data: Vec<u8>, owner: Pubkey) {
+    let lamports = svm.minimum_balance_for_rent_exemption(data.len());
+    svm.set_account(address, Account { lamports, data, owner, executable: false, rent_epoch: 0 }).unwrap();
+}
+
+fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", mpl_token_metadata::ID.as_ref(), mint.as_ref()],
+        &mpl_token_metadata::ID,
+    ).0
+}
+
+fn master_edition_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", mpl_token_metadata::ID.as_ref(), mint.as_ref(), b"edition"],
+        &mpl_token_metadata::ID,
+    ).0
+}
+
+// Borsh layout of a token metadata `Metadata` account, written by hand because the
+// metadata crate's borsh is older than ours. No creators and no royalties.
+fn set_metadata(svm: &mut LiteSVM, mint: Pubkey, collection: Option<(Pubkey, bool)>) {
+    set_metadata_with_royalties(svm, mint, collection, 0, &[]);
+}
+
+// Same layout, with a royalty rate split evenly between verified `creators`
+fn set_metadata_with_royalties(svm: &mut LiteSVM, mint: Pubkey, collection: Option<(Pubkey, bool)>, seller_fee_basis_points: u16, creators: &[Pubkey]) {
+    let mut data = vec![4]; // Key::MetadataV1
+    data.extend_from_slice(Pubkey::new_unique().as_ref()); // update authority
+    data.extend_from_slice(mint.as_ref());
+    for text in ["Turbin3 NFT", "TRB", "https://turbin3.com"] {
+        data.extend_from_slice(&(text.len() as u32).to_le_bytes());
+        data.extend_from_slice(text.as_bytes());
+    }
+    data.extend_from_slice(&seller_fee_basis_points.to_le_bytes());
+    if creators.is_empty() {
+        data.push(0); // creators: None
+    } else {
+        data.push(1);
+        data.extend_from_slice(&(creators.len() as u32).to_le_bytes());
+        for creator in creators {
+            data.extend_from_slice(creator.as_ref());
+            data.push(1); // verified
+            data.push((100 / creators.len()) as u8); // share
+        }
+    }
+    data.push(1); // primary sale happened
+    data.push(1); // is mutable
+    data.push(0); // edition nonce: None
+    data.extend_from_slice(&[1, 0]); // token standard: Some(NonFungible)
+    match collection {
+        Some((key, verified)) => {
+            data.extend_from_slice(&[1, verified as u8]);
+            data.extend_from_slice(key.as_ref());
+        }
+        None => data.push(0),
+    }
+    data.extend_from_slice(&[0, 0, 0]); // uses, collection details, programmable config: None
+    set_account(svm, metadata_address(&mint), data, mpl_token_metadata::ID);
+}
+
+fn set_master_edition(svm: &mut LiteSVM, mint: Pubkey) {
+    let mut data = vec![6]; // Key::MasterEditionV2
+    data.extend_from_slice(&0u64.to_le_bytes()); // supply
+    data.push(1);
+    data.extend_from_slice(&0u64.to_le_bytes()); // max supply: Some(0)
+    set_account(svm, master_edition_address(&mint), data, mpl_token_metadata::ID);
+}
+
+fn marketplace_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"marketplace", MARKETPLACE_NAME.as_bytes()], &nft_marketplace::ID).0
+}
+
+fn treasury_address(marketplace: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"treasury", marketplace.as_ref()], &nft_marketplace::ID).0
+}
+
+fn rewards_address(marketplace: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"rewards", marketplace.as_ref()], &nft_marketplace::ID).0
+}
+
+fn listing_address(marketplace: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[marketplace.as_ref(), mint.as_ref()], &nft_marketplace::ID).0
+}
+
+fn bid_address(marketplace: &Pubkey, target: &Pubkey, bidder: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"bid", marketplace.as_ref(), target.as_ref(), bidder.as_ref()], &nft_marketplace::ID).0
+}
+
+fn auction_address(marketplace: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"auction", marketplace.as_ref(), mint.as_ref()], &nft_marketplace::ID).0
+}
+
+fn auction_bid_address(auction: &Pubkey, bidder: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"auction_bid", auction.as_ref(), bidder.as_ref()], &nft_marketplace::ID).0
+}
+
+fn initialize_instruction(admin: &Pubkey, fee: u16) -> Instruction {
+    let marketplace = marketplace_address();
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts: nft_marketplace::accounts::Initialize {
+            admin: *admin,
+            marketplace,
+            treasury: treasury_address(&marketplace),
+            rewards_mint: rewards_address(&marketplace),
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: nft_marketplace::instruction::Initialize {
+            name: MARKETPLACE_NAME.to_string(),
+            fee,
+            rewards_per_sol: 0,
+            discount_per_token: 0,
+        }
+        .data(),
+    }
+}
+
+fn listing_instruction(maker: &Pubkey, maker_mint: &Pubkey, collection_mint: &Pubkey, price: u64) -> Instruction {
+    listing_in_mint_instruction(maker, maker_mint, collection_mint, price, None)
+}
+
+fn listing_in_mint_instruction(
+    maker: &Pubkey,
+    maker_mint: &Pubkey,
+    collection_mint: &Pubkey,
+    price: u64,
+    payment_mint: Option<Pubkey>,
+) -> Instruction {
+    let marketplace = marketplace_address();
+    let listing = listing_address(&marketplace, maker_mint);
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts: nft_marketplace::accounts::List {
+            maker: *maker,
+            marketplace,
+            maker_mint: *maker_mint,
+            maker_ata: get_associated_token_address(maker, maker_mint),
+            listing,
+            vault: get_associated_token_address(&listing, maker_mint),
+            collection_mint: *collection_mint,
+            metadata: metadata_address(maker_mint),
+            master_edition: master_edition_address(maker_mint),
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: system_program::ID,
+            metadata_program: mpl_token_metadata::ID,
+        }
+        .to_account_metas(None),
+        data: nft_marketplace::instruction::Listing { price, payment_mint }.data(),
+    }
+}
+
+fn purchase_instruction(taker: &Pubkey, maker: &Pubkey, maker_mint: &Pubkey) -> Instruction {
+    purchase_at_most_instruction(taker, maker, maker_mint, PRICE)
+}
+
+fn purchase_at_most_instruction(taker: &Pubkey, maker: &Pubkey, maker_mint: &Pubkey, max_price: u64) -> Instruction {
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts: purchase_accounts(taker, maker, maker_mint).to_account_metas(None),
+        data: nft_marketplace::instruction::Purchase { max_price }.data(),
+    }
+}
+
+// a SOL purchase, payment_token_program is only there to fill the slot
+fn purchase_accounts(taker: &Pubkey, maker: &Pubkey, maker_mint: &Pubkey) -> nft_marketplace::accounts::Purchase {
+    let marketplace = marketplace_address();
+    let listing = listing_address(&marketplace, maker_mint);
+    let rewards = rewards_address(&marketplace);
+    nft_marketplace::accounts::Purchase {
+        taker: *taker,
+        maker: *maker,
+        maker_mint: *maker_mint,
+        marketplace,
+        taker_ata: get_associated_token_address(taker, maker_mint),
+        vault: get_associated_token_address(&listing, maker_mint),
+        rewards,
+        taker_rewards_ata: get_associated_token_address(taker, &rewards),
+        maker_rewards_ata: get_associated_token_address(maker, &rewards),
+        discount: None,
+        payment_mint: None,
+        taker_payment_ata: None,
+        maker_payment_ata: None,
+        treasury_payment_ata: None,
+        listing,
+        treasury: treasury_address(&marketplace),
+        metadata: metadata_address(maker_mint),
+        metadata_program: mpl_token_metadata::ID,
+        associated_token_program: spl_associated_token_account::ID,
+        system_program: system_program::ID,
+        token_program: spl_token::ID,
+        payment_token_program: spl_token::ID,
+    }
+}
+
+fn purchase_with_creators_instruction(taker: &Pubkey, maker: &Pubkey, maker_mint: &Pubkey, creators: &[Pubkey]) -> Instruction {
+    let mut instruction = purchase_instruction(taker, maker, maker_mint);
+    instruction.accounts.extend(creators.iter().map(|creator| AccountMeta::new(*creator, false)));
+    instruction
+}
+
+// A purchase of a listing priced in `payment_mint`, a mint of `payment_token_program`
+fn purchase_in_mint_instruction(
+    taker: &Pubkey,
+    maker: &Pubkey,
+    maker_mint: &Pubkey,
+    payment_mint: &Pubkey,
+    payment_token_program: &Pubkey,
+) -> Instruction {
+    let marketplace = marketplace_address();
+    let payment_ata = |owner: &Pubkey| get_associated_token_address_with_program_id(owner, payment_mint, payment_token_program);
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts: nft_marketplace::accounts::Purchase {
+            payment_mint: Some(*payment_mint),
+            taker_payment_ata: Some(payment_ata(taker)),
+            maker_payment_ata: Some(payment_ata(maker)),
+            treasury_payment_ata: Some(payment_ata(&treasury_address(&marketplace))),
+            payment_token_program: *payment_token_program,
+            ..purchase_accounts(taker, maker, maker_mint)
+        }
+        .to_account_metas(None),
+        data: nft_marketplace::instruction::Purchase { max_price: PRICE }.data(),
+    }
+}
+
+fn update_price_instruction(maker: &Pubkey, maker_mint: &Pubkey, price: u64) -> Instruction {
+    let marketplace = marketplace_address();
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts: nft_marketplace::accounts::UpdatePrice {
+            maker: *maker,
+            marketplace,
+            listing: listing_address(&marketplace, maker_mint),
+        }
+        .to_account_metas(None),
+        data: nft_marketplace::instruction::UpdatePrice { price }.data(),
+    }
+}
+
+fn set_payment_mint_instruction(admin: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts: nft_marketplace::accounts::UpdateFee { admin: *admin, marketplace: marketplace_address() }
+            .to_account_metas(None),
+        data: nft_marketplace::instruction::SetPaymentMint { mint: *mint, allowed: true }.data(),
+    }
+}
+
+fn update_fee_instruction(admin: &Pubkey, fee: u16) -> Instruction {
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts: nft_marketplace::accounts::UpdateFee { admin: *admin, marketplace: marketplace_address() }
+            .to_account_metas(None),
+        data: nft_marketplace::instruction::UpdateFee { fee }.data(),
+    }
+}
+
+fn update_rewards_instruction(admin: &Pubkey, rewards_per_sol: u64, discount_per_token: u64) -> Instruction {
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts: nft_marketplace::accounts::UpdateFee { admin: *admin, marketplace: marketplace_address() }
+            .to_account_metas(None),
+        data: nft_marketplace::instruction::UpdateRewards { rewards_per_sol, discount_per_token }.data(),
+    }
+}
+
+fn delisting_instruction(maker: &Pubkey, maker_mint: &Pubkey) -> Instruction {
+    let marketplace = marketplace_address();
+    let listing = listing_address(&marketplace, maker_mint);
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts: nft_marketplace::accounts::Delist {
+            maker: *maker,
+            marketplace,
+            maker_mint: *maker_mint,
+            maker_ata: get_associated_token_address(maker, maker_mint),
+            listing,
+            vault: get_associated_token_address(&listing, maker_mint),
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: nft_marketplace::instruction::Delisting {}.data(),
+    }
+}
+
+fn list_many_instruction(maker: &Pubkey, maker_mints: &[Pubkey], collection_mint: &Pubkey, all_or_nothing: bool) -> Instruction {
+    let marketplace = marketplace_address();
+    let mut accounts = nft_marketplace::accounts::ListMany {
+        maker: *maker,
+        marketplace,
+        collection_mint: *collection_mint,
+        token_program: spl_token::ID,
+        associated_token_program: spl_associated_token_account::ID,
+        system_program: system_program::ID,
+        metadata_program: mpl_token_metadata::ID,
+    }
+    .to_account_metas(None);
+    for maker_mint in maker_mints {
+        let listing = listing_address(&marketplace, maker_mint);
+        accounts.extend([
+            AccountMeta::new_readonly(*maker_mint, false),
+            AccountMeta::new(get_associated_token_address(maker, maker_mint), false),
+            AccountMeta::new(listing, false),
+            AccountMeta::new(get_associated_token_address(&listing, maker_mint), false),
+            AccountMeta::new_readonly(metadata_address(maker_mint), false),
+            AccountMeta::new_readonly(master_edition_address(maker_mint), false),
+        ]);
+    }
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts,
+        data: nft_marketplace::instruction::ListMany {
+            prices: vec![PRICE; maker_mints.len()],
+            payment_mint: None,
+            all_or_nothing,
+        }
+        .data(),
+    }
+}
+
+// the test NFTs have no creators, so there are no creator accounts after each NFT
+fn sweep_instruction(taker: &Pubkey, maker: &Pubkey, maker_mints: &[Pubkey], max_total_price: u64, all_or_nothing: bool) -> Instruction {
+    let marketplace = marketplace_address();
+    let mut accounts = nft_marketplace::accounts::Sweep {
+        taker: *taker,
+        marketplace,
+        treasury: treasury_address(&marketplace),
+        token_program: spl_token::ID,
+        associated_token_program: spl_associated_token_account::ID,
+        system_program: system_program::ID,
+        metadata_program: mpl_token_metadata::ID,
+    }
+    .to_account_metas(None);
+    for maker_mint in maker_mints {
+        let listing = listing_address(&marketplace, maker_mint);
+        accounts.extend([
+            AccountMeta::new(*maker, false),
+            AccountMeta::new_readonly(*maker_mint, false),
+            AccountMeta::new(listing, false),
+            AccountMeta::new(get_associated_token_address(&listing, maker_mint), false),
+            AccountMeta::new(get_associated_token_address(taker, maker_mint), false),
+            AccountMeta::new_readonly(metadata_address(maker_mint), false),
+        ]);
+    }
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts,
+        data: nft_marketplace::instruction::Sweep { max_total_price, all_or_nothing }.data(),
+    }
+}
+
+fn make_bid_instruction(bidder: &Pubkey, target: &Pubkey, kind: BidTarget, amount: u64) -> Instruction {
+    let marketplace = marketplace_address();
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts: nft_marketplace::accounts::MakeBid {
+            bidder: *bidder,
+            marketplace,
+            bid: bid_address(&marketplace, target, bidder),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: nft_marketplace::instruction::MakeBid { target: *target, kind, amount }.data(),
+    }
+}
+
+// the test NFTs have no creators, so there are no creator accounts
+fn accept_bid_instruction(seller: &Pubkey, bidder: &Pubkey, maker_mint: &Pubkey, target: &Pubkey) -> Instruction {
+    let marketplace = marketplace_address();
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts: nft_marketplace::accounts::AcceptBid {
+            seller: *seller,
+            bidder: *bidder,
+            marketplace,
+            maker_mint: *maker_mint,
+            seller_ata: get_associated_token_address(seller, maker_mint),
+            bidder_ata: get_associated_token_address(bidder, maker_mint),
+            bid: bid_address(&marketplace, target, bidder),
+            treasury: treasury_address(&marketplace),
+            metadata: metadata_address(maker_mint),
+            metadata_program: mpl_token_metadata::ID,
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: nft_marketplace::instruction::AcceptBid {}.data(),
+    }
+}
+
+fn create_auction_instruction(maker: &Pubkey, maker_mint: &Pubkey, collection_mint: &Pubkey, reserve_price: u64) -> Instruction {
+    let marketplace = marketplace_address();
+    let auction = auction_address(&marketplace, maker_mint);
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts: nft_marketplace::accounts::CreateAuction {
+            maker: *maker,
+            marketplace,
+            maker_mint: *maker_mint,
+            maker_ata: get_associated_token_address(maker, maker_mint),
+            auction,
+            vault: get_associated_token_address(&auction, maker_mint),
+            collection_mint: *collection_mint,
+            metadata: metadata_address(maker_mint),
+            master_edition: master_edition_address(maker_mint),
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: system_program::ID,
+            metadata_program: mpl_token_metadata::ID,
+        }
+        .to_account_metas(None),
+        data: nft_marketplace::instruction::CreateAuction {
+            reserve_price,
+            min_increment: reserve_price / 10,
+            duration: AUCTION_DURATION,
+            extension: AUCTION_EXTENSION,
+        }
+        .data(),
+    }
+}
+
+fn place_bid_instruction(bidder: &Pubkey, maker_mint: &Pubkey, amount: u64) -> Instruction {
+    let marketplace = marketplace_address();
+    let auction = auction_address(&marketplace, maker_mint);
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts: nft_marketplace::accounts::PlaceBid {
+            bidder: *bidder,
+            marketplace,
+            auction,
+            auction_bid: auction_bid_address(&auction, bidder),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: nft_marketplace::instruction::PlaceBid { amount }.data(),
+    }
+}
+
+// `winner` is the highest bidder, or the maker when nobody bid
+fn settle_auction_instruction(cranker: &Pubkey, maker: &Pubkey, winner: &Pubkey, maker_mint: &Pubkey) -> Instruction {
+    let marketplace = marketplace_address();
+    let auction = auction_address(&marketplace, maker_mint);
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts: nft_marketplace::accounts::SettleAuction {
+            cranker: *cranker,
+            maker: *maker,
+            winner: *winner,
+            marketplace,
+            maker_mint: *maker_mint,
+            auction,
+            vault: get_associated_token_address(&auction, maker_mint),
+            winner_ata: get_associated_token_address(winner, maker_mint),
+            winner_bid: (winner != maker).then(|| auction_bid_address(&auction, winner)),
+            treasury: treasury_address(&marketplace),
+            metadata: metadata_address(maker_mint),
+            metadata_program: mpl_token_metadata::ID,
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: nft_marketplace::instruction::SettleAuction {}.data(),
+    }
+}
+
+fn refund_auction_bid_instruction(bidder: &Pubkey, maker_mint: &Pubkey) -> Instruction {
+    let auction = auction_address(&marketplace_address(), maker_mint);
+    Instruction {
+        program_id: nft_marketplace::ID,
+        accounts: nft_marketplace::accounts::RefundAuctionBid {
+            bidder: *bidder,
+            auction,
+            auction_bid: auction_bid_address(&auction, bidder),
+        }
+        .to_account_metas(None),
+        data: nft_marketplace::instruction::RefundAuctionBid {}.data(),
+    }
+}
+
+// An NFT in a verified collection, held by `owner`
+fn mint_nft(svm: &mut LiteSVM, owner: &Pubkey, collection: Option<(Pubkey, bool)>) -> Pubkey {
+    let mint = Pubkey::new_unique();
+    set_mint(svm, mint, Pubkey::new_unique(), 1);
+    set_token_account(svm, mint, *owner, 1);
+    set_metadata(svm, mint, collection);
+    set_master_edition(svm, mint);
+    mint
+}
+
+fn funded_keypair(svm: &mut LiteSVM) -> Keypair {
+    let keypair = Keypair::new();
+    svm.airdrop(&keypair.pubkey(), 10_000_000_000).unwrap();
+    keypair
+}
+
+fn token_balance(svm: &LiteSVM, owner: &Pubkey, mint: &Pubkey) -> u64 {
+    let account = svm.get_account(&get_associated_token_address(owner, mint)).unwrap();
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+fn auction_state(svm: &LiteSVM, maker_mint: &Pubkey) -> Auction {
+    let account = svm.get_account(&auction_address(&marketplace_address(), maker_mint)).unwrap();
+    Auction::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+fn warp_to(svm: &mut LiteSVM, unix_timestamp: i64) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = unix_timestamp;
+    svm.set_sysvar(&clock);
+}
+
+fn warp_to_auction_end(svm: &mut LiteSVM, maker_mint: &Pubkey) {
+    let end_time = auction_state(svm, maker_mint).end_time;
+    warp_to(svm, end_time);
+}
+
+fn listed() -> Listed {
+    let (mut svm, admin) = setup();
+    send(&mut svm, initialize_instruction(&admin.pubkey(), 250), &[&admin]).unwrap();
+
+    let maker = Keypair::new();
+    svm.airdrop(&maker.pubkey(), 10_000_000_000).unwrap();
+
+    let collection_mint = Pubkey::new_unique();
+    set_mint(&mut svm, collection_mint, Pubkey::new_unique(), 1);
+    let maker_mint = mint_nft(&mut svm, &maker.pubkey(), Some((collection_mint, true)));
+
+    send(&mut svm, listing_instruction(&maker.pubkey(), &maker_mint, &collection_mint, PRICE), &[&maker]).unwrap();
+
+    Listed { svm, admin, maker, marketplace: marketplace_address(), maker_mint, collection_mint }
+}
+
+#[tokio::test]
+async fn test_initialize_allocates_the_discriminator() {
+    let (mut svm, admin) = setup();
+
+    // Without the 8 discriminator bytes the marketplace didn't fit in its own account and initialize failed
+    send(&mut svm, initialize_instruction(&admin.pubkey(), 250), &[&admin]).unwrap();
+
+    let marketplace = svm.get_account(&marketplace_address()).unwrap();
+    assert_eq!(marketplace.data.len(), 8 + Marketplace::INIT_SPACE);
+}
+
+#[tokio::test]
+async fn test_initialize_rejects_a_fee_above_the_cap() {
+    let (mut svm, admin) = setup();
+
+    let result = send(&mut svm, initialize_instruction(&admin.pubkey(), 10_001), &[&admin]);
+
+    assert_marketplace_error(result, MarketplaceError::FeeTooHigh);
+}
+
+#[tokio::test]
+async fn test_rewards_cannot_be_worth_more_than_the_fee() {
+    let (mut svm, admin) = setup();
+    send(&mut svm, initialize_instruction(&admin.pubkey(), 250), &[&admin]).unwrap();
+
+    // 1 token per SOL to each side, so 2 tokens per SOL sold. At 0.0125 SOL of credit each that's exactly the 2.5% fee,
+    // and selling an NFT between two of your own wallets would mint its fee back for free
+    let result = send(&mut svm, update_rewards_instruction(&admin.pubkey(), 1_000_000, 12_500_000), &[&admin]);
+    assert_marketplace_error(result, MarketplaceError::RewardsTooHigh);
+
+    send(&mut svm, update_rewards_instruction(&admin.pubkey(), 1_000_000, 12_000_000), &[&admin]).unwrap();
+
+    // lowering the fee under what the rewards are already worth is caught too
+    let result = send(&mut svm, update_fee_instruction(&admin.pubkey(), 200), &[&admin]);
+    assert_marketplace_error(result, MarketplaceError::RewardsTooHigh);
+    send(&mut svm, update_fee_instruction(&admin.pubkey(), 245), &[&admin]).unwrap();
+}
+
+#[tokio::test]
+async fn test_purchase_rejects_a_maker_other_than_the_lister() {
+    let Listed { mut svm, maker, maker_mint, .. } = listed();
+
+    let taker = Keypair::new();
+    svm.airdrop(&taker.pubkey(), 10_000_000_000).unwrap();
+
+    // The buyer names their own second wallet as the maker, to get the price and the listing rent back
+    let accomplice = Keypair::new();
+    let result = send(&mut svm, purchase_instruction(&taker.pubkey(), &accomplice.pubkey(), &maker_mint), &[&taker]);
+    assert_marketplace_error(result, MarketplaceError::InvalidMaker);
+
+    // The real maker still gets paid, the price minus the fee plus the listing and vault rent
+    let maker_balance = svm.get_balance(&maker.pubkey()).unwrap();
+    send(&mut svm, purchase_instruction(&taker.pubkey(), &maker.pubkey(), &maker_mint), &[&taker]).unwrap();
+    let fee = PRICE * 250 / 10_000;
+    assert!(svm.get_balance(&maker.pubkey()).unwrap() >= maker_balance + PRICE - fee);
+}
+
+#[tokio::test]
+async fn test_purchase_fails_when_the_price_went_up_after_the_buyer_signed() {
+    let Listed { mut svm, maker, maker_mint, marketplace, .. } = listed();
+    let taker = funded_keypair(&mut svm);
+
+    // the seller reprices the listing right before the buyer's purchase lands
+    send(&mut svm, update_price_instruction(&maker.pubkey(), &maker_mint, 2 * PRICE), &[&maker]).unwrap();
+    let taker_balance = svm.get_balance(&taker.pubkey()).unwrap();
+    let result = send(&mut svm, purchase_at_most_instruction(&taker.pubkey(), &maker.pubkey(), &maker_mint, PRICE), &[&taker]);
+    assert_marketplace_error(result, MarketplaceError::PriceLimitExceeded);
+    assert_eq!(svm.get_balance(&taker.pubkey()).unwrap(), taker_balance);
+
+    // a buyer who agrees to the new price still gets the NFT
+    send(&mut svm, purchase_at_most_instruction(&taker.pubkey(), &maker.pubkey(), &maker_mint, 2 * PRICE), &[&taker]).unwrap();
+    assert_eq!(token_balance(&svm, &taker.pubkey(), &maker_mint), 1);
+    assert!(svm.get_account(&listing_address(&marketplace, &maker_mint)).is_none());
+}
+
+#[tokio::test]
+async fn test_purchase_pays_in_a_token_2022_mint() {
+    let Listed { mut svm, admin, maker, collection_mint, marketplace, .. } = listed();
+
+    // the NFT and the rewards mint are classic SPL, the payment mint is Token-2022
+    let payment_mint = Pubkey::new_unique();
+    set_mint_of(&mut svm, payment_mint, Pubkey::new_unique(), PRICE, token_2022::ID);
+    send(&mut svm, set_payment_mint_instruction(&admin.pubkey(), &payment_mint), &[&admin]).unwrap();
+
+    let maker_mint = mint_nft(&mut svm, &maker.pubkey(), Some((collection_mint, true)));
+    let listing = listing_in_mint_instruction(&maker.pubkey(), &maker_mint, &collection_mint, PRICE, Some(payment_mint));
+    send(&mut svm, listing, &[&maker]).unwrap();
+
+    let taker = funded_keypair(&mut svm);
+    set_token_account_of(&mut svm, payment_mint, taker.pubkey(), PRICE, token_2022::ID);
+    let purchase = purchase_in_mint_instruction(&taker.pubkey(), &maker.pubkey(), &maker_mint, &payment_mint, &token_2022::ID);
+    send(&mut svm, purchase, &[&taker]).unwrap();
+
+    let fee = PRICE * 250 / 10_000;
+    let payment_balance = |owner: &Pubkey| {
+        let ata = get_associated_token_address_with_program_id(owner, &payment_mint, &token_2022::ID);
+        let account = svm.get_account(&ata).unwrap();
+        // the atas the purchase created carry the ImmutableOwner extension after the base layout
+        spl_token::state::Account::unpack_from_slice(&account.data[..spl_token::state::Account::LEN]).unwrap().amount
+    };
+    assert_eq!(payment_balance(&taker.pubkey()), 0);
+    assert_eq!(payment_balance(&maker.pubkey()), PRICE - fee);
+    assert_eq!(payment_balance(&treasury_address(&marketplace)), fee);
+    assert_eq!(token_balance(&svm, &taker.pubkey(), &maker_mint), 1);
+}
+
+#[tokio::test]
+async fn test_delisting_rejects_someone_other_than_the_lister() {
+    let Listed { mut svm, maker_mint, .. } = listed();
+
+    // Delist used to only check that the signer had an ata for the mint, so anyone could take a listed NFT
+    let thief = Keypair::new();
+    svm.airdrop(&thief.pubkey(), 10_000_000_000).unwrap();
+    set_token_account(&mut svm, maker_mint, thief.pubkey(), 0);
+
+    let result = send(&mut svm, delisting_instruction(&thief.pubkey(), &maker_mint), &[&thief]);
+
+    assert_marketplace_error(result, MarketplaceError::Unauthorized);
+}
+
+#[tokio::test]
+async fn test_listing_rejects_a_zero_price() {
+    let Listed { mut svm, admin, collection_mint, .. } = listed();
+
+    let maker_mint = mint_nft(&mut svm, &admin.pubkey(), Some((collection_mint, true)));
+    let result = send(&mut svm, listing_instruction(&admin.pubkey(), &maker_mint, &collection_mint, 0), &[&admin]);
+
+    assert_marketplace_error(result, MarketplaceError::InvalidAmount);
+}
+
+#[tokio::test]
+async fn test_listing_rejects_nfts_outside_a_verified_collection() {
+    let Listed { mut svm, admin, collection_mint, marketplace, .. } = listed();
+
+    // No collection at all used to panic on unwrap instead of failing with an error
+    let no_collection = mint_nft(&mut svm, &admin.pubkey(), None);
+    let result = send(&mut svm, listing_instruction(&admin.pubkey(), &no_collection, &collection_mint, PRICE), &[&admin]);
+    assert_marketplace_error(result, MarketplaceError::InvalidCollection);
+
+    let unverified = mint_nft(&mut svm, &admin.pubkey(), Some((collection_mint, false)));
+    let result = send(&mut svm, listing_instruction(&admin.pubkey(), &unverified, &collection_mint, PRICE), &[&admin]);
+    assert_marketplace_error(result, MarketplaceError::InvalidCollection);
+
+    assert!(svm.get_account(&listing_address(&marketplace, &unverified)).is_none());
+}
+
+#[tokio::test]
+async fn test_list_many_skips_nfts_that_fail_the_checks() {
+    let Listed { mut svm, maker, maker_mint, collection_mint, marketplace, .. } = listed();
+
+    let first = mint_nft(&mut svm, &maker.pubkey(), Some((collection_mint, true)));
+    let second = mint_nft(&mut svm, &maker.pubkey(), Some((collection_mint, true)));
+    let batch = [first, maker_mint, second]; // maker_mint is listed already
+
+    let result = send(&mut svm, list_many_instruction(&maker.pubkey(), &batch, &collection_mint, true), &[&maker]);
+    assert_marketplace_error(result, MarketplaceError::ListingUnavailable);
+
+    send(&mut svm, list_many_instruction(&maker.pubkey(), &batch, &collection_mint, false), &[&maker]).unwrap();
+    assert!(svm.get_account(&listing_address(&marketplace, &first)).is_some());
+    assert!(svm.get_account(&listing_address(&marketplace, &second)).is_some());
+}
+
+#[tokio::test]
+async fn test_list_many_lists_through_a_prefunded_listing_and_an_existing_vault() {
+    let Listed { mut svm, maker, collection_mint, marketplace, .. } = listed();
+
+    // someone sends lamports to the first listing PDA and creates the second one's vault ata ahead of the batch
+    let first = mint_nft(&mut svm, &maker.pubkey(), Some((collection_mint, true)));
+    let second = mint_nft(&mut svm, &maker.pubkey(), Some((collection_mint, true)));
+    let griefer = funded_keypair(&mut svm);
+    let first_listing = listing_address(&marketplace, &first);
+    let transfer = solana_sdk::system_instruction::transfer(&griefer.pubkey(), &first_listing, 1_000_000);
+    send(&mut svm, transfer, &[&griefer]).unwrap();
+    let second_listing = listing_address(&marketplace, &second);
+    set_token_account(&mut svm, second, second_listing, 0);
+
+    send(&mut svm, list_many_instruction(&maker.pubkey(), &[first, second], &collection_mint, true), &[&maker]).unwrap();
+
+    for (maker_mint, listing) in [(first, first_listing), (second, second_listing)] {
+        assert_eq!(svm.get_account(&listing).unwrap().owner, nft_marketplace::ID);
+        assert_eq!(token_balance(&svm, &listing, &maker_mint), 1);
+        assert_eq!(token_balance(&svm, &maker.pubkey(), &maker_mint), 0);
+    }
+    let first_listing_account = svm.get_account(&first_listing).unwrap();
+    assert!(first_listing_account.lamports >= svm.minimum_balance_for_rent_exemption(first_listing_account.data.len()));
+}
+
+#[tokio::test]
+async fn test_sweep_stays_under_the_max_total_price() {
+    let Listed { mut svm, maker, maker_mint, collection_mint, marketplace, .. } = listed();
+
+    let second = mint_nft(&mut svm, &maker.pubkey(), Some((collection_mint, true)));
+    let third = mint_nft(&mut svm, &maker.pubkey(), Some((collection_mint, true)));
+    send(&mut svm, list_many_instruction(&maker.pubkey(), &[second, third], &collection_mint, true), &[&maker]).unwrap();
+
+    let taker = Keypair::new();
+    svm.airdrop(&taker.pubkey(), 10_000_000_000).unwrap();
+    let batch = [maker_mint, second, third];
+
+    // all three listings cost 3 SOL, the buyer only wants to spend 2
+    let result = send(&mut svm, sweep_instruction(&taker.pubkey(), &maker.pubkey(), &batch, 2 * PRICE, true), &[&taker]);
+    assert_marketplace_error(result, MarketplaceError::PriceLimitExceeded);
+
+    send(&mut svm, sweep_instruction(&taker.pubkey(), &maker.pubkey(), &batch, 2 * PRICE, false), &[&taker]).unwrap();
+    assert!(svm.get_account(&listing_address(&marketplace, &maker_mint)).is_none());
+    assert!(svm.get_account(&listing_address(&marketplace, &second)).is_none());
+    assert!(svm.get_account(&listing_address(&marketplace, &third)).is_some());
+}
+
+#[tokio::test]
+async fn test_purchase_caps_royalties_at_the_price_minus_the_fee() {
+    let Listed { mut svm, admin, collection_mint, .. } = listed();
+
+    // 100% royalties plus the 2.5% fee came to more than the price, so the seller amount underflowed and the NFT could never be bought
+    let creator = Pubkey::new_unique();
+    let maker_mint = Pubkey::new_unique();
+    set_mint(&mut svm, maker_mint, Pubkey::new_unique(), 1);
+    set_token_account(&mut svm, maker_mint, admin.pubkey(), 1);
+    set_metadata_with_royalties(&mut svm, maker_mint, Some((collection_mint, true)), 10_000, &[creator]);
+    set_master_edition(&mut svm, maker_mint);
+    send(&mut svm, listing_instruction(&admin.pubkey(), &maker_mint, &collection_mint, PRICE), &[&admin]).unwrap();
+
+    let taker = Keypair::new();
+    svm.airdrop(&taker.pubkey(), 10_000_000_000).unwrap();
+    send(&mut svm, purchase_with_creators_instruction(&taker.pubkey(), &admin.pubkey(), &maker_mint, &[creator]), &[&taker]).unwrap();
+
+    let fee = PRICE * 250 / 10_000;
+    assert_eq!(svm.get_balance(&creator).unwrap(), PRICE - fee);
+}
+
+#[tokio::test]
+async fn test_accept_bid_sells_the_bid_nft() {
+    let Listed { mut svm, collection_mint, marketplace, .. } = listed();
+
+    let seller = funded_keypair(&mut svm);
+    let maker_mint = mint_nft(&mut svm, &seller.pubkey(), Some((collection_mint, true)));
+    let other_mint = mint_nft(&mut svm, &seller.pubkey(), Some((collection_mint, true)));
+
+    let bidder = funded_keypair(&mut svm);
+    send(&mut svm, make_bid_instruction(&bidder.pubkey(), &maker_mint, BidTarget::Nft, PRICE), &[&bidder]).unwrap();
+
+    // an NFT bid only takes the NFT it names, even from the same collection
+    let result = send(&mut svm, accept_bid_instruction(&seller.pubkey(), &bidder.pubkey(), &other_mint, &maker_mint), &[&seller]);
+    assert_marketplace_error(result, MarketplaceError::BidTargetMismatch);
+
+    let treasury = treasury_address(&marketplace);
+    let treasury_balance = svm.get_balance(&treasury).unwrap_or(0);
+    send(&mut svm, accept_bid_instruction(&seller.pubkey(), &bidder.pubkey(), &maker_mint, &maker_mint), &[&seller]).unwrap();
+
+    assert_eq!(token_balance(&svm, &bidder.pubkey(), &maker_mint), 1);
+    assert_eq!(token_balance(&svm, &seller.pubkey(), &maker_mint), 0);
+    assert_eq!(svm.get_balance(&treasury).unwrap(), treasury_balance + PRICE * 250 / 10_000);
+    assert!(svm.get_account(&bid_address(&marketplace, &maker_mint, &bidder.pubkey())).is_none());
+}
+
+#[tokio::test]
+async fn test_accept_bid_fills_a_collection_bid_with_any_verified_nft() {
+    let Listed { mut svm, collection_mint, marketplace, .. } = listed();
+
+    let seller = funded_keypair(&mut svm);
+    let other_collection = Pubkey::new_unique();
+    let outsider = mint_nft(&mut svm, &seller.pubkey(), Some((other_collection, true)));
+    let unverified = mint_nft(&mut svm, &seller.pubkey(), Some((collection_mint, false)));
+    let member = mint_nft(&mut svm, &seller.pubkey(), Some((collection_mint, true)));
+
+    let bidder = funded_keypair(&mut svm);
+    send(&mut svm, make_bid_instruction(&bidder.pubkey(), &collection_mint, BidTarget::Collection, PRICE), &[&bidder]).unwrap();
+
+    for wrong_nft in [outsider, unverified] {
+        let result = send(&mut svm, accept_bid_instruction(&seller.pubkey(), &bidder.pubkey(), &wrong_nft, &collection_mint), &[&seller]);
+        assert_marketplace_error(result, MarketplaceError::BidTargetMismatch);
+    }
+
+    let seller_balance = svm.get_balance(&seller.pubkey()).unwrap();
+    send(&mut svm, accept_bid_instruction(&seller.pubkey(), &bidder.pubkey(), &member, &collection_mint), &[&seller]).unwrap();
+
+    // the seller gets the bid minus the fee, and pays for the bidder's new ata out of it
+    let fee = PRICE * 250 / 10_000;
+    let ata_rent = svm.minimum_balance_for_rent_exemption(spl_token::state::Account::LEN);
+    assert!(svm.get_balance(&seller.pubkey()).unwrap() >= seller_balance + PRICE - fee - ata_rent - 10_000);
+    assert_eq!(token_balance(&svm, &bidder.pubkey(), &member), 1);
+    assert!(svm.get_account(&bid_address(&marketplace, &collection_mint, &bidder.pubkey())).is_none());
+}
+
+#[tokio::test]
+async fn test_outbid_bidders_get_their_escrow_back() {
+    let Listed { mut svm, admin, collection_mint, .. } = listed();
+
+    let maker = funded_keypair(&mut svm);
+    let maker_mint = mint_nft(&mut svm, &maker.pubkey(), Some((collection_mint, true)));
+    send(&mut svm, create_auction_instruction(&maker.pubkey(), &maker_mint, &collection_mint, PRICE), &[&maker]).unwrap();
+
+    let first = funded_keypair(&mut svm);
+    let second = funded_keypair(&mut svm);
+    send(&mut svm, place_bid_instruction(&first.pubkey(), &maker_mint, PRICE), &[&first]).unwrap();
+
+    // the next bid has to beat the highest by the minimum increment
+    let result = send(&mut svm, place_bid_instruction(&second.pubkey(), &maker_mint, PRICE + PRICE / 20), &[&second]);
+    assert_marketplace_error(result, MarketplaceError::BidTooLow);
+    send(&mut svm, place_bid_instruction(&second.pubkey(), &maker_mint, 2 * PRICE), &[&second]).unwrap();
+
+    // the winning bid stays escrowed
+    let result = send(&mut svm, refund_auction_bid_instruction(&second.pubkey(), &maker_mint), &[&admin]);
+    assert_marketplace_error(result, MarketplaceError::BidStillWinning);
+
+    // anyone can crank the refund, the bid and the escrow rent go back to the outbid bidder
+    let balance = svm.get_balance(&first.pubkey()).unwrap();
+    send(&mut svm, refund_auction_bid_instruction(&first.pubkey(), &maker_mint), &[&admin]).unwrap();
+
+    let escrow_rent = svm.minimum_balance_for_rent_exemption(8 + AuctionBid::INIT_SPACE);
+    assert_eq!(svm.get_balance(&first.pubkey()).unwrap(), balance + PRICE + escrow_rent);
+    let auction = auction_address(&marketplace_address(), &maker_mint);
+    assert!(svm.get_account(&auction_bid_address(&auction, &first.pubkey())).is_none());
+}
+
+#[tokio::test]
+async fn test_a_late_bid_extends_the_auction() {
+    let Listed { mut svm, collection_mint, .. } = listed();
+
+    let maker = funded_keypair(&mut svm);
+    let maker_mint = mint_nft(&mut svm, &maker.pubkey(), Some((collection_mint, true)));
+    send(&mut svm, create_auction_instruction(&maker.pubkey(), &maker_mint, &collection_mint, PRICE), &[&maker]).unwrap();
+    let end_time = auction_state(&svm, &maker_mint).end_time;
+
+    // an early bid leaves the end alone
+    let early = funded_keypair(&mut svm);
+    send(&mut svm, place_bid_instruction(&early.pubkey(), &maker_mint, PRICE), &[&early]).unwrap();
+    assert_eq!(auction_state(&svm, &maker_mint).end_time, end_time);
+
+    // a bid 10 seconds before the end gives everyone `extension` seconds to answer
+    let sniper = funded_keypair(&mut svm);
+    warp_to(&mut svm, end_time - 10);
+    send(&mut svm, place_bid_instruction(&sniper.pubkey(), &maker_mint, 2 * PRICE), &[&sniper]).unwrap();
+    let extended_end = auction_state(&svm, &maker_mint).end_time;
+    assert_eq!(extended_end, end_time - 10 + AUCTION_EXTENSION);
+
+    // at the original end the auction is still running, it takes bids and can't be settled
+    warp_to(&mut svm, end_time);
+    let result = send(&mut svm, settle_auction_instruction(&maker.pubkey(), &maker.pubkey(), &sniper.pubkey(), &maker_mint), &[&maker]);
+    assert_marketplace_error(result, MarketplaceError::AuctionNotEnded);
+    send(&mut svm, place_bid_instruction(&early.pubkey(), &maker_mint, 3 * PRICE), &[&early]).unwrap();
+
+    warp_to_auction_end(&mut svm, &maker_mint);
+    let result = send(&mut svm, place_bid_instruction(&sniper.pubkey(), &maker_mint, 4 * PRICE), &[&sniper]);
+    assert_marketplace_error(result, MarketplaceError::AuctionEnded);
+}
+
+#[tokio::test]
+async fn test_settle_auction_pays_the_maker_and_sends_the_nft_to_the_winner() {
+    let Listed { mut svm, admin, collection_mint, marketplace, .. } = listed();
+
+    let maker = funded_keypair(&mut svm);
+    let maker_mint = mint_nft(&mut svm, &maker.pubkey(), Some((collection_mint, true)));
+    send(&mut svm, create_auction_instruction(&maker.pubkey(), &maker_mint, &collection_mint, PRICE), &[&maker]).unwrap();
+
+    let loser = funded_keypair(&mut svm);
+    let winner = funded_keypair(&mut svm);
+    send(&mut svm, place_bid_instruction(&loser.pubkey(), &maker_mint, PRICE), &[&loser]).unwrap();
+    send(&mut svm, place_bid_instruction(&winner.pubkey(), &maker_mint, 2 * PRICE), &[&winner]).unwrap();
+    warp_to_auction_end(&mut svm, &maker_mint);
+
+    // the winner has to be the highest bidder, not the cranker's pick
+    let result = send(&mut svm, settle_auction_instruction(&admin.pubkey(), &maker.pubkey(), &loser.pubkey(), &maker_mint), &[&admin]);
+    assert_marketplace_error(result, MarketplaceError::InvalidAuctionAccounts);
+
+    let maker_balance = svm.get_balance(&maker.pubkey()).unwrap();
+    let treasury = treasury_address(&marketplace);
+    let treasury_balance = svm.get_balance(&treasury).unwrap_or(0);
+    send(&mut svm, settle_auction_instruction(&admin.pubkey(), &maker.pubkey(), &winner.pubkey(), &maker_mint), &[&admin]).unwrap();
+
+    let fee = 2 * PRICE * 250 / 10_000;
+    assert_eq!(token_balance(&svm, &winner.pubkey(), &maker_mint), 1);
+    assert_eq!(svm.get_balance(&treasury).unwrap(), treasury_balance + fee);
+    // the maker also gets the auction and vault rent back
+    assert!(svm.get_balance(&maker.pubkey()).unwrap() > maker_balance + 2 * PRICE - fee);
+    assert!(svm.get_account(&auction_address(&marketplace, &maker_mint)).is_none());
+}
+
+#[tokio::test]
+async fn test_settle_auction_without_bids_returns_the_nft() {
+    let Listed { mut svm, admin, collection_mint, marketplace, .. } = listed();
+
+    let maker = funded_keypair(&mut svm);
+    let maker_mint = mint_nft(&mut svm, &maker.pubkey(), Some((collection_mint, true)));
+    send(&mut svm, create_auction_instruction(&maker.pubkey(), &maker_mint, &collection_mint, PRICE), &[&maker]).unwrap();
+    assert_eq!(token_balance(&svm, &maker.pubkey(), &maker_mint), 0);
+
+    warp_to_auction_end(&mut svm, &maker_mint);
+    let treasury = treasury_address(&marketplace);
+    let treasury_balance = svm.get_balance(&treasury).unwrap_or(0);
+    send(&mut svm, settle_auction_instruction(&admin.pubkey(), &maker.pubkey(), &maker.pubkey(), &maker_mint), &[&admin]).unwrap();
+
+    assert_eq!(token_balance(&svm, &maker.pubkey(), &maker_mint), 1);
+    assert_eq!(svm.get_balance(&treasury).unwrap_or(0), treasury_balance);
+    assert!(svm.get_account(&auction_address(&marketplace, &maker_mint)).is_none());
+}
@@ -16,4 +16,31 @@ pub enum StakeError {
     
     #[msg("Invalid stake account for this NFT")]
     InvalidStakeAccount,
+
+    #[msg("Math overflow")]
+    MathOverflow,
+
+    #[msg("Only the config admin can do this")]
+    Unauthorized,
+
+    #[msg("This collection can't be staked")]
+    CollectionNotAllowed,
+
+    #[msg("Too many collections in the config")]
+    TooManyCollections,
+
+    #[msg("Rarity multiplier doesn't match the rarity root")]
+    InvalidRarityProof,
+
+    #[msg("NFT was staked with a different standard, use the matching unstake")]
+    WrongNftStandard,
+
+    #[msg("NFT is not a programmable NFT")]
+    NotProgrammableNft,
+
+    #[msg("Core asset is not owned by the user or not in the collection")]
+    InvalidCoreAsset,
+
+    #[msg("Staking is paused")]
+    StakingPaused,
 }
\ No newline at end of file
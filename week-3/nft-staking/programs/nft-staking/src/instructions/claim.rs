@@ -1,20 +1,27 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{associated_token::AssociatedToken, token::{mint_to, Mint, MintTo, Token, TokenAccount}};
+use anchor_spl::{associated_token::AssociatedToken, token::{Mint, Token, TokenAccount}};
+use anchor_helpers::mint_tokens;
 
-use crate::state::{StakeConfig, UserAccount};
+use crate::{
+    error::StakeError,
+    state::{StakeAccount, StakeConfig, UserAccount},
+};
 
 // CLAIM INSTRUCTION: Convert accumulated points into reward tokens
 // This is the final step in the staking lifecycle: stake → earn → claim
+//
+// NFTs don't have to be unstaked to claim anymore: pass the user's StakeAccounts as remaining_accounts (writable)
+// and every one of them gets settled up to now, on top of the points already banked from unstaking.
 #[derive(Accounts)]
 pub struct Claim<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     
     // USER ACCOUNT: Contains the points earned from staking (will be modified to reset points)
-    // Seeds pattern: ["user", user_wallet_address] - unique per user
+    // Seeds pattern: ["user", config_address, user_wallet_address] - unique per user and config
     #[account(
         mut,
-        seeds = [b"user".as_ref(), user.key().as_ref()],
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()],
         bump = user_account.bump,
     )]
     pub user_account: Account<'info, UserAccount>,
@@ -30,9 +37,9 @@ pub struct Claim<'info> {
     pub rewards_mint: Account<'info, Mint>,
     
     // CONFIG: Global settings - serves as the mint authority for rewards
-    // Seeds pattern: ["config"] - single global configuration
+    // Seeds pattern: ["config", namespace] - one per staking setup
     #[account(
-        seeds = [b"config".as_ref()],
+        seeds = [b"config".as_ref(), config.namespace.as_ref()],
         bump = config.bump,
     )]
     pub config: Account<'info, StakeConfig>,
@@ -54,36 +61,47 @@ pub struct Claim<'info> {
 }
 
 impl<'info> Claim<'info> {
-    pub fn claim(&mut self) -> Result<()> {
-        // STEP 1: SET UP CPI TO TOKEN PROGRAM
-        // CPI = Cross-Program Invocation (calling another program's function)
-        // We're going to call the Token Program's "mint_to" function
-        let cpi_program = self.token_program.to_account_info();
+    pub fn claim(&mut self, stake_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(!self.config.paused, StakeError::StakingPaused);
 
-        // STEP 2: CREATE SIGNER SEEDS FOR CONFIG AUTHORITY
+        // STEP 0: SETTLE THE NFTS THAT ARE STILL STAKED
+        // every passed stake account has to be this config's stake PDA and belong to this user, it gets its points
+        // since last_claimed and starts accruing again from now. It's saved right away so passing it twice earns nothing extra.
+        let now = Clock::get()?.unix_timestamp;
+        let mut points = self.user_account.points;
+        let config_key = self.config.key();
+
+        for stake_account_info in stake_accounts {
+            let mut stake_account: Account<'info, StakeAccount> = Account::try_from(stake_account_info)?;
+
+            // a stake from another config (or any other StakeAccount-shaped account) would be paid at this config's mint
+            let expected = Pubkey::create_program_address(
+                &[b"stake", stake_account.mint.as_ref(), config_key.as_ref(), &[stake_account.bump]],
+                &crate::ID,
+            )
+            .map_err(|_| StakeError::InvalidStakeAccount)?;
+            require_keys_eq!(stake_account.key(), expected, StakeError::InvalidStakeAccount);
+            require!(stake_account.owner == self.user.key(), StakeError::NotOriginalStaker);
+
+            let earned = stake_account
+                .settle_rewards(now)
+                .ok_or(StakeError::MathOverflow)?;
+            points = points.checked_add(earned).ok_or(StakeError::MathOverflow)?;
+
+            stake_account.exit(&crate::ID)?;
+        }
+
+        // STEP 1: CREATE SIGNER SEEDS FOR CONFIG AUTHORITY
         // The config account is the mint authority, but it's a PDA (Program Derived Address)
         // PDAs can't sign directly - we need to use seeds to "sign" on behalf of the PDA
         // this is the SAME pattern we used in stake.rs for freeze/thaw operations...
         let seeds = &[
             b"config".as_ref(),      
+            self.config.namespace.as_ref(),
             &[self.config.bump]      
         ];
-        let signer_seeds = &[&seeds[..]];
 
-        // STEP 3: DEFINE THE MINT OPERATION
-        // This tells the Token Program: "mint new tokens"
-        let cpi_accounts = MintTo {
-            mint: self.rewards_mint.to_account_info(),     // WHAT: Which token type to mint
-            to: self.rewards_ata.to_account_info(),        // WHERE: User's token account (destination)
-            authority: self.config.to_account_info(),      // WHO: Config has permission to mint (authority)
-        };
-
-        // STEP 4: BUNDLE EVERYTHING FOR THE CPI CALL
-
-        // new_with_signer = we're using PDA signing (not user signing like in stake.rs)
-        let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-
-        // STEP 5: EXECUTE THE MINT WITH DECIMAL CONVERSION
+        // STEP 2: CONVERT POINTS WITH THE MINT'S DECIMALS
         // Key insight: self.user_account.points = human-readable number (e.g., 5 points)
         // But tokens need "atomic units" based on decimals (e.g., 5,000,000 for 6 decimals)
         //
@@ -91,12 +109,24 @@ impl<'info> Claim<'info> {
         // Example: 5 points × 10^6 = 5,000,000 atomic units = 5.000000 tokens
         //
         // Why? Same as money: $1.50 = 150 cents (multiply by 10^2 for 2 decimal places)
-        mint_to(
-            cpi_context,
-            self.user_account.points as u64 * 10_u64.pow(self.rewards_mint.decimals as u32)
+        let amount = 10_u64
+            .checked_pow(self.rewards_mint.decimals as u32)
+            .and_then(|unit| points.checked_mul(unit))
+            .ok_or(StakeError::MathOverflow)?;
+
+        // STEP 3: MINT THE REWARDS
+        // CPI = Cross-Program Invocation (calling another program's function), here the Token Program's "mint_to"
+        // signed with the config seeds (PDA signing, not user signing like in stake.rs)
+        mint_tokens(
+            &self.rewards_mint,     // WHAT: Which token type to mint
+            &self.rewards_ata,      // WHERE: User's token account (destination)
+            amount,
+            &self.config,           // WHO: Config has permission to mint (authority)
+            &self.token_program,
+            Some(&seeds[..]),
         )?;
 
-        // STEP 6: RESET USER POINTS TO ZERO
+        // STEP 4: RESET USER POINTS TO ZERO
         // Points have been "cashed in" for tokens, so clear the balance
         // This prevents double-claiming the same points
         self.user_account.points = 0;
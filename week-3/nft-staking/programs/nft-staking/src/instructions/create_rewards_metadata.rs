@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{
+        create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
+        Metadata,
+    },
+    token::Mint,
+};
+
+use crate::{error::StakeError, state::StakeConfig};
+
+// CREATE REWARDS METADATA: gives the ["rewards", config] mint a name, symbol and image in wallets.
+// The config PDA is the mint authority so it signs, the admin becomes the metadata update authority.
+#[derive(Accounts)]
+pub struct CreateRewardsMetadata<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.namespace.as_ref()],
+        bump = config.bump,
+        has_one = admin @ StakeError::Unauthorized,
+    )]
+    pub config: Account<'info, StakeConfig>,
+
+    #[account(
+        seeds = [b"rewards", config.key().as_ref()],
+        bump = config.rewards_bump,
+    )]
+    pub rewards_mint: Account<'info, Mint>,
+
+    /// CHECK: created by Token Metadata, seeds checked here
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            rewards_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> CreateRewardsMetadata<'info> {
+    pub fn create_rewards_metadata(&mut self, name: String, symbol: String, uri: String) -> Result<()> {
+        let seeds = &[
+            b"config".as_ref(),
+            self.config.namespace.as_ref(),
+            &[self.config.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = CreateMetadataAccountsV3 {
+            metadata: self.metadata.to_account_info(),
+            mint: self.rewards_mint.to_account_info(),
+            mint_authority: self.config.to_account_info(),
+            payer: self.admin.to_account_info(),
+            update_authority: self.admin.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            rent: self.rent.to_account_info(),
+        };
+
+        create_metadata_accounts_v3(
+            CpiContext::new_with_signer(self.metadata_program.to_account_info(), cpi_accounts, signer_seeds),
+            DataV2 {
+                name,
+                symbol,
+                uri,
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            true, // admin can fix the name/uri later
+            true, // admin signs this instruction
+            None,
+        )?;
+
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@ use anchor_spl::token::{Mint, Token};
 use crate::state::StakeConfig;
 
 #[derive(Accounts)]
+#[instruction(namespace: Pubkey)]
 pub struct InitializeConfig<'info> {
 
     #[account(mut)]
@@ -11,7 +12,7 @@ pub struct InitializeConfig<'info> {
     #[account(
         init,
         payer = admin,
-        seeds = [b"config"],
+        seeds = [b"config", namespace.as_ref()], // one config per namespace (e.g. per collection), not one per program
         bump,
         space = 8 + StakeConfig::INIT_SPACE, // think about whether you always need 8 bytes
     )]
@@ -37,15 +38,21 @@ pub struct InitializeConfig<'info> {
 impl <'info> InitializeConfig<'info> {
     pub fn initialize_config(
         &mut self, 
+        namespace: Pubkey,
         points_per_stake: u8, 
         max_stake: u8, 
         freeze_period: u32,  
         bumps: &InitializeConfigBumps) -> Result<()> { // what is &InitializeConfigBumps?
 
             self.config.set_inner(StakeConfig{ 
+                namespace,
+                admin: self.admin.key(),
+                paused: false,
                 points_per_stake,
                 max_stake,
                 freeze_period, 
+                collections: Vec::new(), // nothing can be staked until the admin adds a collection
+                rarity_root: [0u8; 32],
                 rewards_bump: bumps.rewards_mint,
                 bump: bumps.config,
         });
@@ -14,7 +14,7 @@ pub struct InitializeUser<'info> {
     #[account(
         init,
         payer = user,
-        seeds = [b"user", user.key().as_ref()], // if we put mint.key() as a seed also, it would create an account for each NFT the user has, each accruing their own set of rewards as seen in @user-key-and-mint-key.png. for the design of this program, we want it to be unique for each user only.
+        seeds = [b"user", config.key().as_ref(), user.key().as_ref()], // if we put mint.key() as a seed also, it would create an account for each NFT the user has, each accruing their own set of rewards as seen in @user-key-and-mint-key.png. for the design of this program, we want it to be unique for each user only (per config, so points from one staking setup can't be claimed from another).
         bump,
         space = 8 + UserAccount::INIT_SPACE,
     )]
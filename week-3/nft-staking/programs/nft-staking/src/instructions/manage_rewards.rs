@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::StakeError,
+    state::{CollectionTier, StakeConfig, MAX_COLLECTIONS},
+};
+
+// ADMIN: which collections can be staked, how much each earns, and the rarity table.
+// Rates and multipliers are copied into the StakeAccount when an NFT is staked,
+// so changing them here only affects NFTs staked afterwards.
+#[derive(Accounts)]
+pub struct ManageRewards<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.namespace.as_ref()],
+        bump = config.bump,
+        has_one = admin @ StakeError::Unauthorized,
+    )]
+    pub config: Account<'info, StakeConfig>,
+}
+
+impl<'info> ManageRewards<'info> {
+    pub fn set_collection(&mut self, collection_mint: Pubkey, points_per_stake: Option<u8>) -> Result<()> {
+        let points_per_stake = points_per_stake.unwrap_or(self.config.points_per_stake);
+        let collections = &mut self.config.collections;
+
+        // 0 points = stop accepting this collection
+        if points_per_stake == 0 {
+            collections.retain(|tier| tier.collection_mint != collection_mint);
+            return Ok(());
+        }
+
+        match collections.iter_mut().find(|tier| tier.collection_mint == collection_mint) {
+            Some(tier) => tier.points_per_stake = points_per_stake,
+            None => {
+                require!(collections.len() < MAX_COLLECTIONS, StakeError::TooManyCollections);
+                collections.push(CollectionTier {
+                    collection_mint,
+                    points_per_stake,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // root of the (nft mint, multiplier_bps) merkle tree, see StakeConfig::verify_rarity for the leaf format
+    pub fn set_rarity_root(&mut self, rarity_root: [u8; 32]) -> Result<()> {
+        self.config.rarity_root = rarity_root;
+        Ok(())
+    }
+}
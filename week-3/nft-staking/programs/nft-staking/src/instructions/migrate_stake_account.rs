@@ -0,0 +1,221 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::{
+    metadata::{
+        mpl_token_metadata::instructions::{
+            FreezeDelegatedAccountCpi, FreezeDelegatedAccountCpiAccounts, ThawDelegatedAccountCpi,
+            ThawDelegatedAccountCpiAccounts,
+        },
+        MasterEditionAccount, Metadata,
+    },
+    token::{approve, Approve, Mint, Token, TokenAccount},
+};
+
+use crate::{
+    error::StakeError,
+    state::{LegacyStakeAccount, NftStandard, StakeAccount, StakeConfig, UserAccount, BASE_MULTIPLIER_BPS},
+};
+
+// MIGRATE STAKE ACCOUNT: StakeAccounts opened before per-second accrual, collection rates, NFT standards and
+// namespaced configs live at ["stake", mint, old global config], in a shorter layout, and the NFT is frozen with
+// that PDA as its delegate. Neither claim nor unstake of a namespaced config can get at them, so this moves one over:
+// the NFT is thawed by the old stake PDA, delegated to and frozen by the new one at ["stake", mint, config], the new
+// StakeAccount gets what the old program would have used (the config's default rate, the base multiplier, accruing
+// from wherever it got to) and counts towards the user's stakes under `config`. The old account is closed, rent back to the user.
+#[derive(Accounts)]
+pub struct MigrateStakeAccount<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+    )]
+    pub user_mint_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+            b"edition",
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub edition: Account<'info, MasterEditionAccount>,
+
+    /// CHECK: the old global config, only its address is used (to sign for the old stake PDA), its layout predates namespaces
+    #[account(
+        seeds = [b"config"],
+        bump,
+    )]
+    pub legacy_config: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config", config.namespace.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"user", config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+
+    /// CHECK: stake account in an older layout, so it's loaded by hand. Owner is checked here, discriminator, seeds and staker in the handler
+    #[account(
+        mut,
+        owner = crate::ID,
+    )]
+    pub legacy_stake_account: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + StakeAccount::INIT_SPACE,
+        seeds = [b"stake", mint.key().as_ref(), config.key().as_ref()],
+        bump,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+impl<'info> MigrateStakeAccount<'info> {
+    pub fn migrate_stake_account(&mut self, bumps: &MigrateStakeAccountBumps) -> Result<()> {
+        let legacy_stake_account = self.legacy_stake_account.to_account_info();
+
+        let (stake_account, legacy_bump) = {
+            let data = legacy_stake_account.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == *StakeAccount::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+
+            // anything else is either not a stake account or already in the current layout
+            let mut rest = &data[8..];
+            let layout = rest.len();
+            require!(
+                layout == LegacyStakeAccount::SPACE
+                    || layout == LegacyStakeAccount::SPACE_WITH_ACCRUAL
+                    || layout == LegacyStakeAccount::SPACE_WITH_RATES,
+                StakeError::InvalidStakeAccount
+            );
+            let legacy = LegacyStakeAccount::deserialize(&mut rest)?;
+
+            // has to be the old global config's stake PDA for this mint, and only the staker can migrate it
+            let expected = Pubkey::create_program_address(
+                &[b"stake", legacy.mint.as_ref(), self.legacy_config.key().as_ref(), &[legacy.bump]],
+                &crate::ID,
+            )
+            .map_err(|_| StakeError::InvalidStakeAccount)?;
+            require_keys_eq!(legacy_stake_account.key(), expected, StakeError::InvalidStakeAccount);
+            require_keys_eq!(legacy.mint, self.mint.key(), StakeError::InvalidStakeAccount);
+            require!(legacy.owner == self.user.key(), StakeError::NotOriginalStaker);
+
+            let (mut accumulated_rewards, last_claimed) = if layout == LegacyStakeAccount::SPACE {
+                (0, legacy.staked_at)
+            } else {
+                (u64::deserialize(&mut rest)?, i64::deserialize(&mut rest)?)
+            };
+
+            // accumulated_rewards was in plain point-seconds before the rarity multiplier, now it's in multiplier bps
+            if layout == LegacyStakeAccount::SPACE_WITH_ACCRUAL {
+                accumulated_rewards = accumulated_rewards
+                    .checked_mul(BASE_MULTIPLIER_BPS as u64)
+                    .ok_or(StakeError::MathOverflow)?;
+            }
+
+            let (points_per_stake, multiplier_bps) = if layout == LegacyStakeAccount::SPACE_WITH_RATES {
+                (u8::deserialize(&mut rest)?, u16::deserialize(&mut rest)?)
+            } else {
+                (self.config.points_per_stake, BASE_MULTIPLIER_BPS)
+            };
+
+            let stake_account = StakeAccount {
+                owner: legacy.owner,
+                mint: legacy.mint,
+                staked_at: legacy.staked_at, // the freeze period keeps counting from the original stake
+                bump: bumps.stake_account,
+                accumulated_rewards,
+                last_claimed,
+                points_per_stake,
+                multiplier_bps,
+                standard: NftStandard::Legacy, // pNFT and Core stakes only exist in the current layout
+            };
+            (stake_account, legacy.bump)
+        };
+        self.stake_account.set_inner(stake_account);
+
+        // no max_stake check, the NFT is already staked and refusing would leave it frozen under the old PDA
+        self.user_account.amount_staked = self
+            .user_account
+            .amount_staked
+            .checked_add(1)
+            .ok_or(StakeError::MathOverflow)?;
+
+        // the old stake PDA thaws the NFT (the delegate can't change while it's frozen)...
+        let mint_key = self.mint.key();
+        let legacy_config_key = self.legacy_config.key();
+        let legacy_seeds = &[b"stake", mint_key.as_ref(), legacy_config_key.as_ref(), &[legacy_bump]];
+        ThawDelegatedAccountCpi::new(
+            &self.metadata_program.to_account_info(),
+            ThawDelegatedAccountCpiAccounts {
+                delegate: &legacy_stake_account,
+                token_account: &self.user_mint_ata.to_account_info(),
+                edition: &self.edition.to_account_info(),
+                mint: &self.mint.to_account_info(),
+                token_program: &self.token_program.to_account_info(),
+            },
+        )
+        .invoke_signed(&[&legacy_seeds[..]])?;
+
+        // ...the new one takes over as delegate and freezes it again
+        approve(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Approve {
+                    to: self.user_mint_ata.to_account_info(),
+                    delegate: self.stake_account.to_account_info(),
+                    authority: self.user.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        let config_key = self.config.key();
+        let seeds = &[b"stake", mint_key.as_ref(), config_key.as_ref(), &[self.stake_account.bump]];
+        FreezeDelegatedAccountCpi::new(
+            &self.metadata_program.to_account_info(),
+            FreezeDelegatedAccountCpiAccounts {
+                delegate: &self.stake_account.to_account_info(),
+                token_account: &self.user_mint_ata.to_account_info(),
+                edition: &self.edition.to_account_info(),
+                mint: &self.mint.to_account_info(),
+                token_program: &self.token_program.to_account_info(),
+            },
+        )
+        .invoke_signed(&[&seeds[..]])?;
+
+        // close the old account, rent back to the user
+        let user = self.user.to_account_info();
+        let rent = legacy_stake_account.lamports();
+        **user.try_borrow_mut_lamports()? = user
+            .lamports()
+            .checked_add(rent)
+            .ok_or(StakeError::MathOverflow)?;
+        **legacy_stake_account.try_borrow_mut_lamports()? = 0;
+        legacy_stake_account.assign(&system_program::ID);
+        legacy_stake_account.resize(0)?;
+
+        Ok(())
+    }
+}
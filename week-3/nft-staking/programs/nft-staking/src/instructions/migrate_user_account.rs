@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::{
+    error::StakeError,
+    state::{LegacyUserAccount, StakeConfig, UserAccount},
+};
+
+// MIGRATE USER ACCOUNT: UserAccounts used to live at ["user", user] (one per wallet, first with u32 points, then u64).
+// They're now per config at ["user", config, user], so this moves an old one over: points are copied into the new
+// account under `config`, and the old account is closed with its rent going back to the user.
+// amount_staked starts at 0: the old count is for NFTs staked under the old global config, those stake accounts
+// don't belong to `config` and never come back through its unstake, so they mustn't count against its max_stake.
+#[derive(Accounts)]
+pub struct MigrateUserAccount<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: old global user account, in either old layout, so it's loaded by hand. Seeds and owner are checked here, the discriminator in the handler
+    #[account(
+        mut,
+        seeds = [b"user", user.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub legacy_user_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config", config.namespace.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfig>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"user", config.key().as_ref(), user.key().as_ref()],
+        bump,
+        space = 8 + UserAccount::INIT_SPACE,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateUserAccount<'info> {
+    pub fn migrate_user_account(&mut self, bumps: &MigrateUserAccountBumps) -> Result<()> {
+        let legacy_user_account = self.legacy_user_account.to_account_info();
+
+        let points = {
+            let data = legacy_user_account.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == *UserAccount::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+
+            // u32 points layout, or the u64 one it was widened to
+            if data.len() == 8 + LegacyUserAccount::SPACE {
+                LegacyUserAccount::deserialize(&mut &data[8..])?.points as u64
+            } else {
+                UserAccount::try_deserialize(&mut &data[..])?.points
+            }
+        };
+
+        self.user_account.set_inner(UserAccount {
+            points,
+            amount_staked: 0,
+            bump: bumps.user_account,
+        });
+
+        // close the old account, rent back to the user
+        let user = self.user.to_account_info();
+        let rent = legacy_user_account.lamports();
+        **user.try_borrow_mut_lamports()? = user
+            .lamports()
+            .checked_add(rent)
+            .ok_or(StakeError::MathOverflow)?;
+        **legacy_user_account.try_borrow_mut_lamports()? = 0;
+        legacy_user_account.assign(&system_program::ID);
+        legacy_user_account.resize(0)?;
+
+        Ok(())
+    }
+}
@@ -1,11 +1,30 @@
+pub mod shared;
 pub mod initialize_config;
 pub mod initialize_user;
 pub mod stake;
 pub mod unstake;
 pub mod claim;
+pub mod migrate_user_account;
+pub mod migrate_stake_account;
+pub mod manage_rewards;
+pub mod update_config;
+pub mod create_rewards_metadata;
+pub mod stake_pnft;
+pub mod unstake_pnft;
+pub mod stake_core;
+pub mod unstake_core;
 
 pub use initialize_config::*;
 pub use initialize_user::*;
 pub use stake::*;
 pub use unstake::*;
-pub use claim::*;
\ No newline at end of file
+pub use claim::*;
+pub use migrate_user_account::*;
+pub use migrate_stake_account::*;
+pub use manage_rewards::*;
+pub use update_config::*;
+pub use create_rewards_metadata::*;
+pub use stake_pnft::*;
+pub use unstake_pnft::*;
+pub use stake_core::*;
+pub use unstake_core::*;
\ No newline at end of file
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::StakeError,
+    state::{NftStandard, StakeAccount, StakeConfig, UserAccount},
+};
+
+// the part of staking that's the same for every NFT standard, once the NFT itself checked out:
+// the user's limit, the collection's rate and the rarity proof. Counts the stake and returns the new StakeAccount
+#[allow(clippy::too_many_arguments)]
+pub fn open_stake(
+    config: &StakeConfig,
+    user_account: &mut UserAccount,
+    owner: Pubkey,
+    mint: Pubkey,
+    collection: Pubkey,
+    multiplier_bps: u16,
+    proof: &[[u8; 32]],
+    standard: NftStandard,
+    bump: u8,
+) -> Result<StakeAccount> {
+    require!(!config.paused, StakeError::StakingPaused);
+
+    require!(user_account.amount_staked < config.max_stake, StakeError::MaxStakeReached); // checks the limit of NFTs staked by this user is not reached
+
+    // the NFT is in `collection`, now check the config takes that collection
+    let points_per_stake = config
+        .collection_rate(&collection)
+        .ok_or(StakeError::CollectionNotAllowed)?;
+
+    // rare NFTs earn more, but only with a proof against the admin's rarity table
+    require!(
+        config.verify_rarity(&mint, multiplier_bps, proof),
+        StakeError::InvalidRarityProof
+    );
+
+    user_account.amount_staked = user_account
+        .amount_staked
+        .checked_add(1)
+        .ok_or(StakeError::MathOverflow)?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    Ok(StakeAccount {
+        owner,
+        mint,
+        staked_at: now,
+        bump,
+        accumulated_rewards: 0,
+        last_claimed: now, // points start accruing right away
+        points_per_stake,
+        multiplier_bps,
+        standard,
+    })
+}
+
+// the bookkeeping every unstake does before the NFT is released:
+// right standard, freeze period over, original staker, then the points are settled and the stake uncounted
+pub fn close_stake(
+    config: &StakeConfig,
+    user_account: &mut UserAccount,
+    stake_account: &mut StakeAccount,
+    user: &Pubkey,
+    standard: NftStandard,
+) -> Result<()> {
+    require!(stake_account.standard == standard, StakeError::WrongNftStandard);
+
+    let now = Clock::get()?.unix_timestamp;
+    let time_elapsed = now
+        .checked_sub(stake_account.staked_at)
+        .ok_or(StakeError::MathOverflow)?
+        / 86400;
+
+    // check if time elapsed is greater than freeze_period
+    require!(time_elapsed > config.freeze_period as i64, StakeError::FreezePeriodNotExpired);
+
+    // check if the user has any NFTs staked
+    require!(user_account.amount_staked > 0, StakeError::NoStakedTokens);
+
+    // check if the user is the original staker
+    require!(stake_account.owner == *user, StakeError::NotOriginalStaker);
+
+    // settle whatever this NFT earned since its last claim, down to the second
+    // (the part of a point that's left over is lost, the stake account closes after this)
+    let earned = stake_account
+        .settle_rewards(now)
+        .ok_or(StakeError::MathOverflow)?;
+
+    user_account.points = user_account
+        .points
+        .checked_add(earned)
+        .ok_or(StakeError::MathOverflow)?;
+
+    user_account.amount_staked = user_account
+        .amount_staked
+        .checked_sub(1)
+        .ok_or(StakeError::MathOverflow)?;
+
+    Ok(())
+}
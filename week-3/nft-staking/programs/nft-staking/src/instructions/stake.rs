@@ -10,8 +10,8 @@ use anchor_spl::{
 };
 
 use crate::{
-    error::StakeError,
-    state::{StakeConfig, UserAccount, StakeAccount},
+    instructions::shared::open_stake,
+    state::{NftStandard, StakeConfig, UserAccount, StakeAccount},
 };
 
 /*
@@ -25,10 +25,14 @@ ACCOUNTS NEEDED:
 3. collection_mint: Account<Mint> - The collection this NFT must belong to
 4. user_mint_ata: Account<TokenAccount> - User's Associated Token Account holding the NFT
 5. metadata: Account<MetadataAccount> - NFT's Metaplex metadata (PDA: ["metadata", metadata_program, mint])
-6. edition: Account<MetadataAccount> - NFT's master edition (PDA: ["metadata", metadata_program, mint, "edition"])
-7. config: Account<StakeConfig> - Global staking rules (PDA: ["config"])
-8. user_account: Account<UserAccount> - User's staking aggregation (PDA: ["user", user.key()])
+6. edition: Account<MasterEditionAccount> - NFT's master edition (PDA: ["metadata", metadata_program, mint, "edition"])
+7. config: Account<StakeConfig> - Staking rules for this namespace (PDA: ["config", namespace])
+8. user_account: Account<UserAccount> - User's staking aggregation (PDA: ["user", config.key(), user.key()])
 9. stake_account: Account<StakeAccount> - NEW account for this stake (PDA: ["stake", mint.key(), config.key()])
+
+ARGS:
+- multiplier_bps: the NFT's rarity multiplier from the admin's table (10_000 = 1x, needs no proof)
+- proof: merkle proof of (mint, multiplier_bps) against config.rarity_root
 10. system_program: Program<System> - For account creation
 11. token_program: Program<Token> - For token operations
 12. metadata_program: Program<Metadata> - For NFT freezing
@@ -39,6 +43,8 @@ VALIDATIONS PERFORMED:
 ✅ Correct NFT in wallet (user_mint_ata.mint == mint)
 ✅ Collection membership (metadata.collection.key == collection_mint)
 ✅ Collection verification (metadata.collection.verified == true)
+✅ Collection is one the config accepts (config.collections), its rate is used
+✅ Rarity multiplier is proven against config.rarity_root
 ✅ PDA derivations for all program-derived accounts
 ✅ User account exists and is valid
 ✅ Config account exists and is valid
@@ -108,8 +114,8 @@ PROGRAM OWNERSHIP HIERARCHY:
   └─ 🏆 edition (PDA: ["metadata", metadata_program, mint, "edition"])
 
 🏢 OUR STAKING PROGRAM owns:
-  ├─ ⚙️ config (PDA: ["config"])
-  ├─ 👥 user_account (PDA: ["user", user.key()])
+  ├─ ⚙️ config (PDA: ["config", namespace])
+  ├─ 👥 user_account (PDA: ["user", config.key(), user.key()])
   └─ 🧾 stake_account (PDA: ["stake", mint.key(), config.key()])
 
 AUTHORITY FLOW DURING STAKING:
@@ -136,7 +142,7 @@ FINAL STATE:
 - NFT is frozen and cannot be transferred by anyone
 - StakeAccount exists as proof of staking with timestamp
 - User's stake count is incremented
-- NFT begins earning rewards based on staked_at timestamp
+- NFT begins earning rewards every second from the staked_at timestamp (tracked in last_claimed)
 - Only our staking program can reverse this state (unstaking)
 */
 
@@ -182,10 +188,10 @@ pub struct Stake<'info> {
         seeds::program = metadata_program.key(),
         bump,
     )]
-    pub edition: Account<'info, MetadataAccount>,
+    pub edition: Account<'info, MasterEditionAccount>,
 
     #[account(
-        seeds = [b"config"],
+        seeds = [b"config", config.namespace.as_ref()],
         bump = config.bump,
     )]
     pub config: Account<'info, StakeConfig>, 
@@ -193,7 +199,7 @@ pub struct Stake<'info> {
 
     #[account(
         mut,
-        seeds = [b"user", user.key().as_ref()], 
+        seeds = [b"user", config.key().as_ref(), user.key().as_ref()], 
         bump = user_account.bump,
     )]
     pub user_account: Account<'info, UserAccount>,
@@ -213,16 +219,21 @@ pub struct Stake<'info> {
 }
 
 impl<'info> Stake<'info> {
-    pub fn stake(&mut self, bumps: &StakeBumps) -> Result<()> {
-
-        require!(self.user_account.amount_staked < self.config.max_stake, StakeError::MaxStakeReached); // checks the limit of NFTs staked by this user is not reached
-
-        self.stake_account.set_inner(StakeAccount {
-            owner: self.user.key(),
-            mint: self.mint.key(),
-            staked_at: Clock::get()?.unix_timestamp,
-            bump: bumps.stake_account,
-        });
+    pub fn stake(&mut self, multiplier_bps: u16, proof: Vec<[u8; 32]>, bumps: &StakeBumps) -> Result<()> {
+
+        // the metadata constraints proved the NFT is in collection_mint, the rest is the same for every standard
+        let stake_account = open_stake(
+            &self.config,
+            &mut self.user_account,
+            self.user.key(),
+            self.mint.key(),
+            self.collection_mint.key(),
+            multiplier_bps,
+            &proof,
+            NftStandard::Legacy,
+            bumps.stake_account,
+        )?;
+        self.stake_account.set_inner(stake_account);
 
         // ═══════════════════════════════════════════════════════════════
         // 🤝 AUTHORITY TRANSFER: Delegate NFT control to staking program
@@ -277,8 +288,6 @@ impl<'info> Stake<'info> {
         )
         .invoke_signed(signer_seeds)?;
 
-        self.user_account.amount_staked += 1;
-
         Ok(())
 
     } 
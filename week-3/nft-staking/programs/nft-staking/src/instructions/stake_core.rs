@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::BaseAssetV1,
+    instructions::AddPluginV1CpiBuilder,
+    types::{FreezeDelegate, Plugin, PluginAuthority, UpdateAuthority},
+    ID as CORE_PROGRAM_ID,
+};
+
+use crate::{
+    error::StakeError,
+    instructions::shared::open_stake,
+    state::{NftStandard, StakeConfig, StakeAccount, UserAccount},
+};
+
+/*
+STAKE CORE: Metaplex Core assets have no token account to freeze, the asset account itself holds the owner.
+Core's freeze delegate plugin does the same job: the user adds it with frozen = true and stake_account as its
+authority, so only our program can thaw it again. The asset stays owned by the user the whole time.
+
+`collection` is the Core collection account, it plays the part of collection_mint in the config's tiers.
+*/
+
+#[derive(Accounts)]
+pub struct StakeCore<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: Core asset, owner program checked here, owner and collection checked in the handler
+    #[account(mut, owner = CORE_PROGRAM_ID)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// CHECK: Core collection the asset belongs to, checked against the asset in the handler
+    #[account(mut, owner = CORE_PROGRAM_ID)]
+    pub collection: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config", config.namespace.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"user", config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + StakeAccount::INIT_SPACE,
+        seeds = [b"stake", asset.key().as_ref(), config.key().as_ref()],
+        bump,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> StakeCore<'info> {
+    pub fn stake_core(&mut self, multiplier_bps: u16, proof: Vec<[u8; 32]>, bumps: &StakeCoreBumps) -> Result<()> {
+        // the asset has to be the user's and sit in the collection we're paying out for
+        let asset = BaseAssetV1::try_from(&self.asset.to_account_info())?;
+        require!(
+            asset.owner == self.user.key()
+                && asset.update_authority == UpdateAuthority::Collection(self.collection.key()),
+            StakeError::InvalidCoreAsset
+        );
+
+        let stake_account = open_stake(
+            &self.config,
+            &mut self.user_account,
+            self.user.key(),
+            self.asset.key(),
+            self.collection.key(),
+            multiplier_bps,
+            &proof,
+            NftStandard::Core,
+            bumps.stake_account,
+        )?;
+        self.stake_account.set_inner(stake_account);
+
+        // user adds the freeze delegate plugin, frozen, with stake_account as the only one who can thaw it
+        AddPluginV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.collection.to_account_info()))
+            .payer(&self.user.to_account_info())
+            .authority(Some(&self.user.to_account_info()))
+            .system_program(&self.system_program.to_account_info())
+            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: true }))
+            .init_authority(PluginAuthority::Address {
+                address: self.stake_account.key(),
+            })
+            .invoke()?;
+
+        Ok(())
+    }
+}
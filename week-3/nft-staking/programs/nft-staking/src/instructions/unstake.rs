@@ -6,7 +6,7 @@ use anchor_spl::{
             ThawDelegatedAccountCpiAccounts
         }, 
         MasterEditionAccount, 
-        Metadata, MetadataAccount
+        Metadata
     }, 
     token::{
         revoke, 
@@ -18,8 +18,8 @@ use anchor_spl::{
 };
 
 use crate::{
-    error::StakeError,
-    state::{StakeConfig, UserAccount, StakeAccount},
+    instructions::shared::close_stake,
+    state::{NftStandard, StakeConfig, UserAccount, StakeAccount},
 };
 
 #[derive(Accounts)]
@@ -48,10 +48,10 @@ pub struct Unstake<'info> {
         seeds::program = metadata_program.key(),
         bump,
     )]
-    pub edition: Account<'info, MetadataAccount>, 
+    pub edition: Account<'info, MasterEditionAccount>, 
 
     #[account(
-        seeds = [b"config"],
+        seeds = [b"config", config.namespace.as_ref()],
         bump = config.bump,
     )]
     pub config: Account<'info, StakeConfig>, 
@@ -59,7 +59,7 @@ pub struct Unstake<'info> {
 
     #[account(
         mut,
-        seeds = [b"user", user.key().as_ref()], 
+        seeds = [b"user", config.key().as_ref(), user.key().as_ref()], 
         bump = user_account.bump,
     )]
     pub user_account: Account<'info, UserAccount>,
@@ -82,24 +82,17 @@ pub struct Unstake<'info> {
 impl<'info> Unstake<'info> {
     pub fn unstake(&mut self) -> Result<()> {
 
-        let time_elapsed = ((Clock::get()?.unix_timestamp - self.stake_account.staked_at) / 86400) as u32;
-
-        // check if time elapsed is greater than freeze_period
-        require!(time_elapsed > self.config.freeze_period, StakeError::FreezePeriodNotExpired);
-
-        // check if the user has any NFTs staked
-        require!(self.user_account.amount_staked > 0, StakeError::NoStakedTokens);
-        
-        // check if the user is the original staker
-        require!(self.stake_account.owner == self.user.key(), StakeError::NotOriginalStaker);
+        // freeze period, ownership and points, same for every standard
+        close_stake(
+            &self.config,
+            &mut self.user_account,
+            &mut self.stake_account,
+            &self.user.key(),
+            NftStandard::Legacy,
+        )?;
 
         let program = self.token_program.to_account_info();
 
-        // increment the user's points based on time elapsed
-
-        self.user_account.points += (self.config.points_per_stake as u32) * time_elapsed;
-
-
         let accounts = ThawDelegatedAccountCpiAccounts {
             delegate: &self.stake_account.to_account_info(),
             token_account: &self.user_mint_ata.to_account_info(),
@@ -129,7 +122,8 @@ impl<'info> Unstake<'info> {
             ];
         let signer_seeds = &[&seeds[..]];
             
-        ThawDelegatedAccountCpi::new(&self.metadata_program.to_account_info(), accounts).invoke_signed(signer_seeds);
+        // if the thaw fails the whole unstake has to fail, otherwise the stake account closes with the NFT still frozen
+        ThawDelegatedAccountCpi::new(&self.metadata_program.to_account_info(), accounts).invoke_signed(signer_seeds)?;
         
         // Revoke the approval and give it back to user
         let account  = Revoke{ 
@@ -138,9 +132,7 @@ impl<'info> Unstake<'info> {
         };
 
         let ctx = CpiContext::new(program, account);
-        revoke(ctx);
-        
-        self.user_account.amount_staked -= 1;
+        revoke(ctx)?;
 
         Ok(())
 
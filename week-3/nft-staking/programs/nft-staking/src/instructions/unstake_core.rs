@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    instructions::{RemovePluginV1CpiBuilder, UpdatePluginV1CpiBuilder},
+    types::{FreezeDelegate, Plugin, PluginType},
+    ID as CORE_PROGRAM_ID,
+};
+
+use crate::{
+    instructions::shared::close_stake,
+    state::{NftStandard, StakeConfig, StakeAccount, UserAccount},
+};
+
+// UNSTAKE CORE: stake_account thaws the asset (it's the plugin authority), then the user removes the
+// freeze delegate plugin so the asset can be staked again later
+#[derive(Accounts)]
+pub struct UnstakeCore<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: Core asset, the stake account PDA is derived from it and Core checks the plugin authority
+    #[account(mut, owner = CORE_PROGRAM_ID)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// CHECK: Core collection of the asset, checked by Core
+    #[account(mut, owner = CORE_PROGRAM_ID)]
+    pub collection: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config", config.namespace.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"user", config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"stake", asset.key().as_ref(), config.key().as_ref()],
+        bump = stake_account.bump,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UnstakeCore<'info> {
+    pub fn unstake_core(&mut self) -> Result<()> {
+        close_stake(
+            &self.config,
+            &mut self.user_account,
+            &mut self.stake_account,
+            &self.user.key(),
+            NftStandard::Core,
+        )?;
+
+        let asset_key = self.asset.key();
+        let config_key = self.config.key();
+        let seeds = &[
+            b"stake",
+            asset_key.as_ref(),
+            config_key.as_ref(),
+            &[self.stake_account.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        UpdatePluginV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.collection.to_account_info()))
+            .payer(&self.user.to_account_info())
+            .authority(Some(&self.stake_account.to_account_info()))
+            .system_program(&self.system_program.to_account_info())
+            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: false }))
+            .invoke_signed(signer_seeds)?;
+
+        RemovePluginV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.collection.to_account_info()))
+            .payer(&self.user.to_account_info())
+            .authority(Some(&self.user.to_account_info()))
+            .system_program(&self.system_program.to_account_info())
+            .plugin_type(PluginType::FreezeDelegate)
+            .invoke()?;
+
+        Ok(())
+    }
+}
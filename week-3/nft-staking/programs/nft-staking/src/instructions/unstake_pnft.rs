@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{
+        mpl_token_metadata::instructions::{RevokeStakingV1CpiBuilder, UnlockV1CpiBuilder},
+        Metadata, MetadataAccount,
+    },
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    instructions::shared::close_stake,
+    state::{NftStandard, StakeConfig, StakeAccount, UserAccount},
+};
+
+// UNSTAKE PNFT: undoes stake_pnft in reverse, unlock_v1 (signed by stake_account) then revoke_staking_v1 (by the user)
+#[derive(Accounts)]
+pub struct UnstakePnft<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+    )]
+    pub user_mint_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+
+    /// CHECK: master edition PDA, checked by seeds and by Token Metadata
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+            b"edition",
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub edition: UncheckedAccount<'info>,
+
+    /// CHECK: the pNFT's token record for user_mint_ata, checked by seeds and by Token Metadata
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+            b"token_record",
+            user_mint_ata.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub token_record: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config", config.namespace.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"user", config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"stake", mint.key().as_ref(), config.key().as_ref()],
+        bump = stake_account.bump,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// CHECK: instructions sysvar, Token Metadata reads it
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    /// CHECK: only needed for pNFTs with a rule set, checked by Token Metadata
+    pub authorization_rules_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: only needed for pNFTs with a rule set, checked by Token Metadata
+    pub authorization_rules: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+impl<'info> UnstakePnft<'info> {
+    pub fn unstake_pnft(&mut self) -> Result<()> {
+        close_stake(
+            &self.config,
+            &mut self.user_account,
+            &mut self.stake_account,
+            &self.user.key(),
+            NftStandard::Programmable,
+        )?;
+
+        let metadata_program = self.metadata_program.to_account_info();
+        let user = self.user.to_account_info();
+        let stake_account = self.stake_account.to_account_info();
+        let metadata = self.metadata.to_account_info();
+        let edition = self.edition.to_account_info();
+        let token_record = self.token_record.to_account_info();
+        let mint = self.mint.to_account_info();
+        let token = self.user_mint_ata.to_account_info();
+        let system_program = self.system_program.to_account_info();
+        let sysvar_instructions = self.sysvar_instructions.to_account_info();
+        let token_program = self.token_program.to_account_info();
+        let authorization_rules_program = self.authorization_rules_program.as_ref().map(|a| a.to_account_info());
+        let authorization_rules = self.authorization_rules.as_ref().map(|a| a.to_account_info());
+
+        let mint_key = self.mint.key();
+        let config_key = self.config.key();
+        let seeds = &[
+            b"stake",
+            mint_key.as_ref(),
+            config_key.as_ref(),
+            &[self.stake_account.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        UnlockV1CpiBuilder::new(&metadata_program)
+            .authority(&stake_account)
+            .token_owner(Some(&user))
+            .token(&token)
+            .mint(&mint)
+            .metadata(&metadata)
+            .edition(Some(&edition))
+            .token_record(Some(&token_record))
+            .payer(&user)
+            .system_program(&system_program)
+            .sysvar_instructions(&sysvar_instructions)
+            .spl_token_program(Some(&token_program))
+            .authorization_rules_program(authorization_rules_program.as_ref())
+            .authorization_rules(authorization_rules.as_ref())
+            .invoke_signed(signer_seeds)?;
+
+        // the user takes the staking delegate back, the pNFT is fully theirs again
+        RevokeStakingV1CpiBuilder::new(&metadata_program)
+            .delegate(&stake_account)
+            .metadata(&metadata)
+            .master_edition(Some(&edition))
+            .token_record(Some(&token_record))
+            .mint(&mint)
+            .token(&token)
+            .authority(&user)
+            .payer(&user)
+            .system_program(&system_program)
+            .sysvar_instructions(&sysvar_instructions)
+            .spl_token_program(Some(&token_program))
+            .authorization_rules_program(authorization_rules_program.as_ref())
+            .authorization_rules(authorization_rules.as_ref())
+            .invoke()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::StakeError, state::StakeConfig};
+
+// UPDATE CONFIG: the admin can change the limits, pause staking and hand the config to someone else.
+// Like collection rates, points_per_stake here is only the default for collections added later,
+// NFTs that are already staked keep the rate they were staked with.
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.namespace.as_ref()],
+        bump = config.bump,
+        has_one = admin @ StakeError::Unauthorized,
+    )]
+    pub config: Account<'info, StakeConfig>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn update_config(
+        &mut self,
+        points_per_stake: Option<u8>,
+        max_stake: Option<u8>,
+        freeze_period: Option<u32>,
+        paused: Option<bool>,
+        new_admin: Option<Pubkey>,
+    ) -> Result<()> {
+        let config = &mut self.config;
+
+        if let Some(points_per_stake) = points_per_stake {
+            config.points_per_stake = points_per_stake;
+        }
+        if let Some(max_stake) = max_stake {
+            config.max_stake = max_stake;
+        }
+        if let Some(freeze_period) = freeze_period {
+            config.freeze_period = freeze_period;
+        }
+        if let Some(paused) = paused {
+            config.paused = paused;
+        }
+        if let Some(new_admin) = new_admin {
+            config.admin = new_admin;
+        }
+
+        Ok(())
+    }
+}
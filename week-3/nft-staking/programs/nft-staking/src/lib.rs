@@ -13,23 +13,73 @@ pub use instructions::*;
 pub mod nft_staking {
     use super::*;
 
-    pub fn initialize_config(ctx: Context<InitializeConfig>, points_per_stake: u8, max_stake: u8, freeze_period: u32) -> Result<()> {
-        ctx.accounts.initialize_config(points_per_stake, max_stake, freeze_period, &ctx.bumps)
+    pub fn initialize_config(ctx: Context<InitializeConfig>, namespace: Pubkey, points_per_stake: u8, max_stake: u8, freeze_period: u32) -> Result<()> {
+        ctx.accounts.initialize_config(namespace, points_per_stake, max_stake, freeze_period, &ctx.bumps)
+    }
+
+    // None leaves a setting as it is
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        points_per_stake: Option<u8>,
+        max_stake: Option<u8>,
+        freeze_period: Option<u32>,
+        paused: Option<bool>,
+        new_admin: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.update_config(points_per_stake, max_stake, freeze_period, paused, new_admin)
+    }
+
+    pub fn create_rewards_metadata(ctx: Context<CreateRewardsMetadata>, name: String, symbol: String, uri: String) -> Result<()> {
+        ctx.accounts.create_rewards_metadata(name, symbol, uri)
     }
 
     pub fn initialize_user(ctx: Context<InitializeUser>) -> Result<()> {
         ctx.accounts.initialize_user(&ctx.bumps)
     }
 
-    pub fn stake(ctx: Context<Stake>) -> Result<()> {
-        ctx.accounts.stake(&ctx.bumps)
+    pub fn stake(ctx: Context<Stake>, multiplier_bps: u16, proof: Vec<[u8; 32]>) -> Result<()> {
+        ctx.accounts.stake(multiplier_bps, proof, &ctx.bumps)
     }
 
     pub fn unstake(ctx: Context<Unstake>) -> Result<()> {
         ctx.accounts.unstake()
     }
 
-    pub fn claim(ctx: Context<Claim>) -> Result<()> {
-        ctx.accounts.claim()
+    pub fn stake_pnft(ctx: Context<StakePnft>, multiplier_bps: u16, proof: Vec<[u8; 32]>) -> Result<()> {
+        ctx.accounts.stake_pnft(multiplier_bps, proof, &ctx.bumps)
+    }
+
+    pub fn unstake_pnft(ctx: Context<UnstakePnft>) -> Result<()> {
+        ctx.accounts.unstake_pnft()
+    }
+
+    pub fn stake_core(ctx: Context<StakeCore>, multiplier_bps: u16, proof: Vec<[u8; 32]>) -> Result<()> {
+        ctx.accounts.stake_core(multiplier_bps, proof, &ctx.bumps)
+    }
+
+    pub fn unstake_core(ctx: Context<UnstakeCore>) -> Result<()> {
+        ctx.accounts.unstake_core()
+    }
+
+    // remaining_accounts: the user's StakeAccounts to settle without unstaking (writable)
+    pub fn claim<'info>(ctx: Context<'_, '_, 'info, 'info, Claim<'info>>) -> Result<()> {
+        ctx.accounts.claim(ctx.remaining_accounts)
+    }
+
+    pub fn migrate_user_account(ctx: Context<MigrateUserAccount>) -> Result<()> {
+        ctx.accounts.migrate_user_account(&ctx.bumps)
+    }
+
+    pub fn migrate_stake_account(ctx: Context<MigrateStakeAccount>) -> Result<()> {
+        ctx.accounts.migrate_stake_account(&ctx.bumps)
+    }
+
+    // points_per_stake = None uses the config's default rate, Some(0) removes the collection
+    pub fn set_collection(ctx: Context<ManageRewards>, collection_mint: Pubkey, points_per_stake: Option<u8>) -> Result<()> {
+        ctx.accounts.set_collection(collection_mint, points_per_stake)
+    }
+
+    pub fn set_rarity_root(ctx: Context<ManageRewards>, rarity_root: [u8; 32]) -> Result<()> {
+        ctx.accounts.set_rarity_root(rarity_root)
     }
 }
\ No newline at end of file
@@ -1,12 +1,63 @@
 use anchor_lang::prelude::*;
 
+use crate::state::BASE_MULTIPLIER_BPS;
+
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+// how the NFT is held in place while staked, unstaking has to use the matching instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum NftStandard {
+    Legacy,       // token metadata NFT, frozen with freeze_delegated_account
+    Programmable, // pNFT, staking delegate + lock on the token record
+    Core,         // metaplex core asset, freeze delegate plugin
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct StakeAccount {
     pub owner: Pubkey, // who staked the NFT
     pub mint: Pubkey, // which specific NFT is staked
-    pub staked_at: i64, // when the NFT was staked,  need to know how long theyve staked to provide appropriate reward for them
+    pub staked_at: i64, // when the NFT was staked, the freeze period counts from here
     pub bump: u8, 
-    // pub accumulated_rewards: u64, // this would track per-nft rewards, but it's not needed for this program
-    // pub last_claimed_rewards: u64, // this would track when rewards were last claimed, not needed for this program
-}
\ No newline at end of file
+    pub accumulated_rewards: u64, // point-seconds (in multiplier bps) earned since last_claimed that don't add up to a whole point yet
+    pub last_claimed: i64, // when this NFT's points were last settled (claim or unstake), starts at staked_at
+    pub points_per_stake: u8, // the collection's rate when this NFT was staked
+    pub multiplier_bps: u16, // rarity multiplier proven at stake time, BASE_MULTIPLIER_BPS = 1x
+    pub standard: NftStandard, // which stake instruction locked it up
+}
+
+impl StakeAccount {
+    // points earn per second: points_per_stake per day times the rarity multiplier,
+    // so points_per_stake * multiplier_bps * seconds / (86400 * 10_000)
+    // the leftover (part of a point) stays in accumulated_rewards for the next settle instead of getting lost
+    pub fn settle_rewards(&mut self, now: i64) -> Option<u64> {
+        let elapsed = now.checked_sub(self.last_claimed)?.max(0) as u64;
+        let earned = elapsed
+            .checked_mul(self.points_per_stake as u64)?
+            .checked_mul(self.multiplier_bps as u64)?;
+        let total = self.accumulated_rewards.checked_add(earned)?;
+        let one_point = SECONDS_PER_DAY * BASE_MULTIPLIER_BPS as u64;
+
+        self.accumulated_rewards = total % one_point;
+        self.last_claimed = now;
+
+        Some(total / one_point)
+    }
+}
+
+// the layout StakeAccounts were created with before the fields after bump were added, moved over with migrate_stake_account.
+// Accounts made in between have some of those fields already: first accumulated_rewards + last_claimed, then
+// points_per_stake + multiplier_bps, standard came last
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyStakeAccount {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub staked_at: i64,
+    pub bump: u8,
+}
+
+impl LegacyStakeAccount {
+    pub const SPACE: usize = 32 + 32 + 8 + 1;
+    pub const SPACE_WITH_ACCRUAL: usize = Self::SPACE + 8 + 8;
+    pub const SPACE_WITH_RATES: usize = Self::SPACE_WITH_ACCRUAL + 1 + 2;
+}
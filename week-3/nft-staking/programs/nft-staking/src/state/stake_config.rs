@@ -1,11 +1,67 @@
 use anchor_lang::prelude::*;
+use sha2::{Digest, Sha256};
+
+pub const MAX_COLLECTIONS: usize = 8;
+
+// 10_000 bps = 1x, NFTs without a rarity entry (or staked without a proof) earn the base rate
+pub const BASE_MULTIPLIER_BPS: u16 = 10_000;
+
+// a collection that can be staked, with its own reward rate
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct CollectionTier {
+    pub collection_mint: Pubkey,
+    pub points_per_stake: u8, // points per day for every NFT of this collection (before the rarity multiplier)
+}
 
 #[account]
 #[derive(InitSpace)]
 pub struct StakeConfig {
-    pub points_per_stake: u8,
+    pub namespace: Pubkey, // config PDA seed, usually the main collection mint, so several staking setups can live side by side
+    pub admin: Pubkey, // the only one who can change the config, add collections and set the rarity root
+    pub paused: bool, // stops stake and claim, unstake keeps working so nobody gets stuck
+    pub points_per_stake: u8, // default rate, used when a collection is added without its own
     pub max_stake: u8,
     pub freeze_period: u32,
+    #[max_len(MAX_COLLECTIONS)]
+    pub collections: Vec<CollectionTier>,
+    pub rarity_root: [u8; 32], // merkle root of (nft mint, multiplier_bps) leaves, all zeros = no rarity table
     pub rewards_bump: u8,
     pub bump: u8,
-}
\ No newline at end of file
+}
+
+impl StakeConfig {
+    pub fn collection_rate(&self, collection_mint: &Pubkey) -> Option<u8> {
+        self.collections
+            .iter()
+            .find(|tier| tier.collection_mint == *collection_mint)
+            .map(|tier| tier.points_per_stake)
+    }
+
+    // leaf = sha256(mint || multiplier_bps as le bytes), every level hashes the sorted pair so the proof
+    // doesn't need left/right flags. the base multiplier never needs a proof, it's what everyone gets anyway
+    pub fn verify_rarity(&self, mint: &Pubkey, multiplier_bps: u16, proof: &[[u8; 32]]) -> bool {
+        if multiplier_bps == BASE_MULTIPLIER_BPS {
+            return true;
+        }
+        if self.rarity_root == [0u8; 32] {
+            return false;
+        }
+
+        let mut node: [u8; 32] = Sha256::new()
+            .chain_update(mint.as_ref())
+            .chain_update(multiplier_bps.to_le_bytes())
+            .finalize()
+            .into();
+
+        for sibling in proof {
+            let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+            node = Sha256::new()
+                .chain_update(left)
+                .chain_update(right)
+                .finalize()
+                .into();
+        }
+
+        node == self.rarity_root
+    }
+}
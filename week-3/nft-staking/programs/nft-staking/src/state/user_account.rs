@@ -3,7 +3,19 @@ use anchor_lang::prelude::*;
 #[account]
 #[derive(InitSpace)]
 pub struct UserAccount {
+    pub points: u64, // was u32, old accounts are moved over with migrate_user_account
+    pub amount_staked: u8,
+    pub bump: u8,
+}
+
+// the layout UserAccounts were created with before points became a u64
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyUserAccount {
     pub points: u32,
     pub amount_staked: u8,
     pub bump: u8,
+}
+
+impl LegacyUserAccount {
+    pub const SPACE: usize = 4 + 1 + 1;
 }
\ No newline at end of file
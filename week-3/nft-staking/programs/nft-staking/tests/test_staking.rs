@@ -0,0 +1,677 @@
+use std::fs;
+use anchor_lang::{system_program, AccountDeserialize, AccountSerialize, Discriminator, InstructionData, ToAccountMetas};
+use anchor_spl::metadata::mpl_token_metadata::{self, types::Key};
+use sha2::{Digest, Sha256};
+use litesvm::LiteSVM;
+use nft_staking::{
+    error::StakeError,
+    state::{LegacyStakeAccount, NftStandard, StakeAccount, StakeConfig, UserAccount, BASE_MULTIPLIER_BPS},
+};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::state::AccountState;
+
+const POINTS_PER_STAKE: u8 = 2;
+const MAX_STAKE: u8 = 3;
+const ONE_DAY: i64 = 86_400;
+const ONE_POINT: u64 = 1_000_000; // rewards mint has 6 decimals
+const MASTER_EDITION_SPACE: usize = 282; // what token metadata allocates for a master edition
+
+// Everything a test needs after a config is set up and `user` has a UserAccount under it
+struct Staking {
+    svm: LiteSVM,
+    admin: Keypair,
+    user: Keypair,
+    config: Pubkey,
+}
+
+fn setup() -> Staking {
+    let mut svm = LiteSVM::new();
+
+    // Load the compiled program - it's in this program's target/deploy directory
+    let program_name = env!("CARGO_PKG_NAME").replace('-', "_");
+    let program_path = format!("target/deploy/{}.so", program_name);
+    let program_bytes = fs::read(&program_path)
+        .unwrap_or_else(|e| panic!("Failed to read program bytes at {}: {}", program_path, e));
+    svm.add_program(nft_staking::ID, &program_bytes);
+
+    let admin = Keypair::new();
+    svm.airdrop(&admin.pubkey(), 10_000_000_000).unwrap();
+    let user = Keypair::new();
+    svm.airdrop(&user.pubkey(), 10_000_000_000).unwrap();
+
+    let namespace = Pubkey::new_unique();
+    let config = config_address(&namespace);
+    send(&mut svm, initialize_config_instruction(&admin.pubkey(), &namespace), &[&admin]).unwrap();
+    send(&mut svm, initialize_user_instruction(&user.pubkey(), &config), &[&user]).unwrap();
+
+    Staking { svm, admin, user, config }
+}
+
+fn send(svm: &mut LiteSVM, instruction: Instruction, signers: &[&Keypair]) -> Result<(), TransactionError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&signers[0].pubkey()),
+        signers,
+        svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(transaction).map(|_| ()).map_err(|failed| failed.err);
+    // the same instruction sent twice in a test has to be a new transaction
+    svm.expire_blockhash();
+    result
+}
+
+fn assert_stake_error(result: Result<(), TransactionError>, expected: StakeError) {
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(0, InstructionError::Custom(expected.into()))),
+    );
+}
+
+fn config_address(namespace: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"config", namespace.as_ref()], &nft_staking::ID).0
+}
+
+fn rewards_address(config: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"rewards", config.as_ref()], &nft_staking::ID).0
+}
+
+fn user_address(config: &Pubkey, user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"user", config.as_ref(), user.as_ref()], &nft_staking::ID).0
+}
+
+// the global config every stake was made under before configs got a namespace
+fn legacy_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &nft_staking::ID).0
+}
+
+fn edition_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", mpl_token_metadata::ID.as_ref(), mint.as_ref(), b"edition"],
+        &mpl_token_metadata::ID,
+    )
+    .0
+}
+
+fn legacy_user_address(user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"user", user.as_ref()], &nft_staking::ID)
+}
+
+fn stake_address(mint: &Pubkey, config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"stake", mint.as_ref(), config.as_ref()], &nft_staking::ID)
+}
+
+fn initialize_config_instruction(admin: &Pubkey, namespace: &Pubkey) -> Instruction {
+    let config = config_address(namespace);
+    Instruction {
+        program_id: nft_staking::ID,
+        accounts: nft_staking::accounts::InitializeConfig {
+            admin: *admin,
+            config,
+            rewards_mint: rewards_address(&config),
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: nft_staking::instruction::InitializeConfig {
+            namespace: *namespace,
+            points_per_stake: POINTS_PER_STAKE,
+            max_stake: MAX_STAKE,
+            freeze_period: 0,
+        }
+        .data(),
+    }
+}
+
+fn initialize_user_instruction(user: &Pubkey, config: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: nft_staking::ID,
+        accounts: nft_staking::accounts::InitializeUser {
+            user: *user,
+            config: *config,
+            user_account: user_address(config, user),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: nft_staking::instruction::InitializeUser {}.data(),
+    }
+}
+
+// stake_accounts go in as remaining accounts, in the order given (duplicates included)
+fn claim_instruction(user: &Pubkey, config: &Pubkey, stake_accounts: &[Pubkey]) -> Instruction {
+    let rewards_mint = rewards_address(config);
+    let mut accounts = nft_staking::accounts::Claim {
+        user: *user,
+        user_account: user_address(config, user),
+        rewards_mint,
+        config: *config,
+        rewards_ata: get_associated_token_address(user, &rewards_mint),
+        system_program: system_program::ID,
+        token_program: spl_token::ID,
+        associated_token_program: spl_associated_token_account::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(stake_accounts.iter().map(|stake_account| AccountMeta::new(*stake_account, false)));
+
+    Instruction {
+        program_id: nft_staking::ID,
+        accounts,
+        data: nft_staking::instruction::Claim {}.data(),
+    }
+}
+
+fn migrate_user_account_instruction(user: &Pubkey, config: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: nft_staking::ID,
+        accounts: nft_staking::accounts::MigrateUserAccount {
+            user: *user,
+            legacy_user_account: legacy_user_address(user).0,
+            config: *config,
+            user_account: user_address(config, user),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: nft_staking::instruction::MigrateUserAccount {}.data(),
+    }
+}
+
+fn update_config_instruction(admin: &Pubkey, config: &Pubkey, paused: bool) -> Instruction {
+    Instruction {
+        program_id: nft_staking::ID,
+        accounts: nft_staking::accounts::UpdateConfig { admin: *admin, config: *config }.to_account_metas(None),
+        data: nft_staking::instruction::UpdateConfig {
+            points_per_stake: None,
+            max_stake: None,
+            freeze_period: None,
+            paused: Some(paused),
+            new_admin: None,
+        }
+        .data(),
+    }
+}
+
+fn migrate_stake_account_instruction(
+    user: &Pubkey,
+    config: &Pubkey,
+    mint: &Pubkey,
+    legacy_stake_account: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: nft_staking::ID,
+        accounts: nft_staking::accounts::MigrateStakeAccount {
+            user: *user,
+            mint: *mint,
+            user_mint_ata: get_associated_token_address(user, mint),
+            edition: edition_address(mint),
+            legacy_config: legacy_config_address(),
+            config: *config,
+            user_account: user_address(config, user),
+            legacy_stake_account: *legacy_stake_account,
+            stake_account: stake_address(mint, config).0,
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            metadata_program: mpl_token_metadata::ID,
+        }
+        .to_account_metas(None),
+        data: nft_staking::instruction::MigrateStakeAccount {}.data(),
+    }
+}
+
+fn unstake_instruction(user: &Pubkey, config: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: nft_staking::ID,
+        accounts: nft_staking::accounts::Unstake {
+            user: *user,
+            mint: *mint,
+            user_mint_ata: get_associated_token_address(user, mint),
+            edition: edition_address(mint),
+            config: *config,
+            user_account: user_address(config, user),
+            stake_account: stake_address(mint, config).0,
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            metadata_program: mpl_token_metadata::ID,
+        }
+        .to_account_metas(None),
+        data: nft_staking::instruction::Unstake {}.data(),
+    }
+}
+
+fn set_account(svm: &mut LiteSVM, address: Pubkey, data: Vec<u8>, owner: Pubkey) {
+    let lamports = svm.minimum_balance_for_rent_exemption(data.len());
+    svm.set_account(address, Account { lamports, data, owner, executable: false, rent_epoch: 0 }).unwrap();
+}
+
+// A StakeAccount as `stake` would have left it at `staked_at`, written straight into `address`.
+// Staking itself freezes the NFT through token metadata, which these tests don't load.
+fn set_stake_account(svm: &mut LiteSVM, address: Pubkey, owner: &Pubkey, mint: &Pubkey, bump: u8, staked_at: i64) {
+    let stake_account = StakeAccount {
+        owner: *owner,
+        mint: *mint,
+        staked_at,
+        bump,
+        accumulated_rewards: 0,
+        last_claimed: staked_at,
+        points_per_stake: POINTS_PER_STAKE,
+        multiplier_bps: BASE_MULTIPLIER_BPS,
+        standard: NftStandard::Legacy,
+    };
+    let mut data = Vec::new();
+    stake_account.try_serialize(&mut data).unwrap();
+    set_account(svm, address, data, nft_staking::ID);
+}
+
+// stakes a fresh NFT under `config` at the current clock, returns the stake account address
+fn stake_nft(svm: &mut LiteSVM, owner: &Pubkey, config: &Pubkey) -> Pubkey {
+    let mint = Pubkey::new_unique();
+    let (address, bump) = stake_address(&mint, config);
+    let now = now(svm);
+    set_stake_account(svm, address, owner, &mint, bump, now);
+    address
+}
+
+// Unstaking thaws the NFT through token metadata, which LiteSVM doesn't ship. Dump it from mainnet first:
+// solana program dump -u m metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s tests/fixtures/mpl_token_metadata.so
+fn load_token_metadata(svm: &mut LiteSVM) {
+    let program_path = "tests/fixtures/mpl_token_metadata.so";
+    let program_bytes = fs::read(program_path)
+        .unwrap_or_else(|e| panic!("Failed to read program bytes at {}: {}", program_path, e));
+    svm.add_program(mpl_token_metadata::ID, &program_bytes);
+}
+
+// A token metadata NFT in `owner`'s ATA, frozen with `delegate` as its delegate, the way `stake` leaves it.
+// Mint, master edition and token account are written straight in, only their layouts matter to the thaw and freeze.
+fn frozen_nft(svm: &mut LiteSVM, owner: &Pubkey, delegate: &Pubkey) -> Pubkey {
+    let mint = Pubkey::new_unique();
+    let edition = edition_address(&mint);
+
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        mint_authority: COption::Some(edition),
+        supply: 1,
+        decimals: 0,
+        is_initialized: true,
+        freeze_authority: COption::Some(edition),
+    }
+    .pack_into_slice(&mut data);
+    set_account(svm, mint, data, spl_token::ID);
+
+    // MasterEditionV2 with supply 0 and max_supply Some(0), padded to the size token metadata allocates
+    let mut data = vec![Key::MasterEditionV2 as u8];
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.push(1);
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.resize(MASTER_EDITION_SPACE, 0);
+    set_account(svm, edition, data, mpl_token_metadata::ID);
+
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint,
+        owner: *owner,
+        amount: 1,
+        delegate: COption::Some(*delegate),
+        state: AccountState::Frozen,
+        is_native: COption::None,
+        delegated_amount: 1,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    set_account(svm, get_associated_token_address(owner, &mint), data, spl_token::ID);
+
+    mint
+}
+
+fn nft_account(svm: &LiteSVM, owner: &Pubkey, mint: &Pubkey) -> spl_token::state::Account {
+    let account = svm.get_account(&get_associated_token_address(owner, mint)).unwrap();
+    spl_token::state::Account::unpack(&account.data).unwrap()
+}
+
+fn now(svm: &LiteSVM) -> i64 {
+    svm.get_sysvar::<Clock>().unix_timestamp
+}
+
+fn warp_by(svm: &mut LiteSVM, seconds: i64) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp += seconds;
+    svm.set_sysvar(&clock);
+}
+
+fn stake_account(svm: &LiteSVM, address: &Pubkey) -> StakeAccount {
+    let account = svm.get_account(address).unwrap();
+    StakeAccount::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+fn rewards_balance(svm: &LiteSVM, config: &Pubkey, user: &Pubkey) -> u64 {
+    let ata = get_associated_token_address(user, &rewards_address(config));
+    let account = svm.get_account(&ata).unwrap();
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+// sha256(mint || multiplier_bps) leaves, sorted pairs up the tree, same as StakeConfig::verify_rarity
+fn rarity_leaf(mint: &Pubkey, multiplier_bps: u16) -> [u8; 32] {
+    Sha256::new().chain_update(mint.as_ref()).chain_update(multiplier_bps.to_le_bytes()).finalize().into()
+}
+
+fn rarity_parent(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    Sha256::new().chain_update(left).chain_update(right).finalize().into()
+}
+
+fn user_account(svm: &LiteSVM, config: &Pubkey, user: &Pubkey) -> UserAccount {
+    let account = svm.get_account(&user_address(config, user)).unwrap();
+    UserAccount::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+#[tokio::test]
+async fn test_claim_rejects_a_stake_account_from_another_config() {
+    let Staking { mut svm, user, config, .. } = setup();
+
+    // a perfectly good stake account, just under a different config
+    let other_config = config_address(&Pubkey::new_unique());
+    let stake_account = stake_nft(&mut svm, &user.pubkey(), &other_config);
+    warp_by(&mut svm, 86_400);
+
+    assert_stake_error(
+        send(&mut svm, claim_instruction(&user.pubkey(), &config, &[stake_account]), &[&user]),
+        StakeError::InvalidStakeAccount,
+    );
+
+    // same data at an address that isn't a stake PDA at all
+    let mint = Pubkey::new_unique();
+    let (_, bump) = stake_address(&mint, &config);
+    let fake = Pubkey::new_unique();
+    let staked_at = now(&svm) - 86_400;
+    set_stake_account(&mut svm, fake, &user.pubkey(), &mint, bump, staked_at);
+
+    assert_stake_error(
+        send(&mut svm, claim_instruction(&user.pubkey(), &config, &[fake]), &[&user]),
+        StakeError::InvalidStakeAccount,
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_user_account_from_both_legacy_layouts() {
+    let Staking { mut svm, config, .. } = setup();
+
+    // points as a u32 (the first layout) and as a u64, both with stakes counted under the old global config
+    for (points, wide) in [(7u64, false), (42u64, true)] {
+        let user = Keypair::new();
+        svm.airdrop(&user.pubkey(), 1_000_000_000).unwrap();
+
+        let (legacy_user_account, bump) = legacy_user_address(&user.pubkey());
+        let mut data = UserAccount::DISCRIMINATOR.to_vec();
+        if wide {
+            data.extend_from_slice(&points.to_le_bytes());
+        } else {
+            data.extend_from_slice(&(points as u32).to_le_bytes());
+        }
+        data.push(MAX_STAKE); // amount_staked
+        data.push(bump);
+        set_account(&mut svm, legacy_user_account, data, nft_staking::ID);
+
+        send(&mut svm, migrate_user_account_instruction(&user.pubkey(), &config), &[&user]).unwrap();
+
+        let migrated = user_account(&svm, &config, &user.pubkey());
+        assert_eq!(migrated.points, points);
+        assert_eq!(migrated.amount_staked, 0);
+        assert!(svm.get_account(&legacy_user_account).is_none_or(|account| account.lamports == 0));
+    }
+}
+
+#[tokio::test]
+async fn test_points_accrue_per_second_and_carry_the_remainder() {
+    let Staking { mut svm, user, config, .. } = setup();
+    let stake = stake_nft(&mut svm, &user.pubkey(), &config);
+
+    // 1.5 days at 2 points a day is exactly 3 points
+    warp_by(&mut svm, ONE_DAY * 3 / 2);
+    send(&mut svm, claim_instruction(&user.pubkey(), &config, &[stake]), &[&user]).unwrap();
+    assert_eq!(rewards_balance(&svm, &config, &user.pubkey()), 3 * ONE_POINT);
+
+    // a third of a day is 2/3 of a point, nothing to mint yet but the part is kept
+    warp_by(&mut svm, ONE_DAY / 3);
+    send(&mut svm, claim_instruction(&user.pubkey(), &config, &[stake]), &[&user]).unwrap();
+    assert_eq!(rewards_balance(&svm, &config, &user.pubkey()), 3 * ONE_POINT);
+    assert_eq!(
+        stake_account(&svm, &stake).accumulated_rewards,
+        (ONE_DAY as u64 / 3) * POINTS_PER_STAKE as u64 * BASE_MULTIPLIER_BPS as u64,
+    );
+
+    // another sixth of a day tops that up to a whole point
+    warp_by(&mut svm, ONE_DAY / 6);
+    send(&mut svm, claim_instruction(&user.pubkey(), &config, &[stake]), &[&user]).unwrap();
+    assert_eq!(rewards_balance(&svm, &config, &user.pubkey()), 4 * ONE_POINT);
+    assert_eq!(stake_account(&svm, &stake).accumulated_rewards, 0);
+}
+
+#[tokio::test]
+async fn test_claim_settles_each_staked_nft_once() {
+    let Staking { mut svm, user, config, .. } = setup();
+    let first = stake_nft(&mut svm, &user.pubkey(), &config);
+    let second = stake_nft(&mut svm, &user.pubkey(), &config);
+
+    // the first NFT passed twice still only earns its day once
+    warp_by(&mut svm, ONE_DAY);
+    send(&mut svm, claim_instruction(&user.pubkey(), &config, &[first, first, second]), &[&user]).unwrap();
+    assert_eq!(rewards_balance(&svm, &config, &user.pubkey()), 2 * POINTS_PER_STAKE as u64 * ONE_POINT);
+    assert_eq!(user_account(&svm, &config, &user.pubkey()).points, 0);
+
+    let now = now(&svm);
+    assert_eq!(stake_account(&svm, &first).last_claimed, now);
+    assert_eq!(stake_account(&svm, &second).last_claimed, now);
+
+    // someone else's stake can't be claimed
+    let other = Keypair::new();
+    svm.airdrop(&other.pubkey(), 1_000_000_000).unwrap();
+    send(&mut svm, initialize_user_instruction(&other.pubkey(), &config), &[&other]).unwrap();
+    assert_stake_error(
+        send(&mut svm, claim_instruction(&other.pubkey(), &config, &[first]), &[&other]),
+        StakeError::NotOriginalStaker,
+    );
+}
+
+#[tokio::test]
+async fn test_rarity_proof_proves_the_multiplier() {
+    let rare = Pubkey::new_unique();
+    let common = Pubkey::new_unique();
+    let rare_leaf = rarity_leaf(&rare, 20_000);
+    let common_leaf = rarity_leaf(&common, 12_500);
+
+    let config = StakeConfig {
+        namespace: Pubkey::new_unique(),
+        admin: Pubkey::new_unique(),
+        paused: false,
+        points_per_stake: POINTS_PER_STAKE,
+        max_stake: MAX_STAKE,
+        freeze_period: 0,
+        collections: Vec::new(),
+        rarity_root: rarity_parent(rare_leaf, common_leaf),
+        rewards_bump: 0,
+        bump: 0,
+    };
+
+    assert!(config.verify_rarity(&rare, 20_000, &[common_leaf]));
+    assert!(config.verify_rarity(&common, 12_500, &[rare_leaf]));
+    // the right proof with a made up multiplier, or someone else's multiplier
+    assert!(!config.verify_rarity(&rare, 30_000, &[common_leaf]));
+    assert!(!config.verify_rarity(&common, 20_000, &[rare_leaf]));
+    assert!(!config.verify_rarity(&rare, 20_000, &[]));
+    // the base rate never needs a proof
+    assert!(config.verify_rarity(&Pubkey::new_unique(), BASE_MULTIPLIER_BPS, &[]));
+
+    // a 2x NFT at 2 points a day earns 4 points a day
+    let mut stake = StakeAccount {
+        owner: Pubkey::new_unique(),
+        mint: rare,
+        staked_at: 0,
+        bump: 0,
+        accumulated_rewards: 0,
+        last_claimed: 0,
+        points_per_stake: POINTS_PER_STAKE,
+        multiplier_bps: 20_000,
+        standard: NftStandard::Legacy,
+    };
+    assert_eq!(stake.settle_rewards(ONE_DAY), Some(4));
+    assert_eq!(stake.settle_rewards(ONE_DAY + ONE_DAY / 4), Some(1));
+}
+
+#[tokio::test]
+async fn test_pause_stops_claims_until_unpaused() {
+    let Staking { mut svm, admin, user, config } = setup();
+    let stake = stake_nft(&mut svm, &user.pubkey(), &config);
+    warp_by(&mut svm, ONE_DAY);
+
+    // only the admin can pause
+    assert_stake_error(
+        send(&mut svm, update_config_instruction(&user.pubkey(), &config, true), &[&user]),
+        StakeError::Unauthorized,
+    );
+
+    send(&mut svm, update_config_instruction(&admin.pubkey(), &config, true), &[&admin]).unwrap();
+    assert_stake_error(
+        send(&mut svm, claim_instruction(&user.pubkey(), &config, &[stake]), &[&user]),
+        StakeError::StakingPaused,
+    );
+
+    // nothing was lost while paused
+    send(&mut svm, update_config_instruction(&admin.pubkey(), &config, false), &[&admin]).unwrap();
+    send(&mut svm, claim_instruction(&user.pubkey(), &config, &[stake]), &[&user]).unwrap();
+    assert_eq!(rewards_balance(&svm, &config, &user.pubkey()), POINTS_PER_STAKE as u64 * ONE_POINT);
+}
+
+#[tokio::test]
+async fn test_migrate_stake_account_from_older_layouts() {
+    let Staking { mut svm, user, config, .. } = setup();
+    load_token_metadata(&mut svm);
+    let staked_at = now(&svm);
+    let half_point_seconds = ONE_DAY as u64 / 2; // plain point-seconds, before multiplier bps
+
+    // every old layout lives under the global config: the first one, then + accumulated_rewards/last_claimed,
+    // then + points_per_stake/multiplier_bps. Each NFT is frozen by its old stake PDA
+    let layouts = [
+        LegacyStakeAccount::SPACE,
+        LegacyStakeAccount::SPACE_WITH_ACCRUAL,
+        LegacyStakeAccount::SPACE_WITH_RATES,
+    ];
+    let mut stakes = Vec::new();
+    for layout in layouts {
+        let mint = Pubkey::new_unique();
+        let (address, bump) = stake_address(&mint, &legacy_config_address());
+
+        let mut data = StakeAccount::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(user.pubkey().as_ref());
+        data.extend_from_slice(mint.as_ref());
+        data.extend_from_slice(&staked_at.to_le_bytes());
+        data.push(bump);
+        if layout >= LegacyStakeAccount::SPACE_WITH_ACCRUAL {
+            let accumulated_rewards = if layout == LegacyStakeAccount::SPACE_WITH_ACCRUAL {
+                half_point_seconds
+            } else {
+                half_point_seconds * BASE_MULTIPLIER_BPS as u64
+            };
+            data.extend_from_slice(&accumulated_rewards.to_le_bytes());
+            data.extend_from_slice(&staked_at.to_le_bytes());
+        }
+        if layout == LegacyStakeAccount::SPACE_WITH_RATES {
+            data.push(1); // points_per_stake
+            data.extend_from_slice(&15_000u16.to_le_bytes());
+        }
+        assert_eq!(data.len(), 8 + layout);
+
+        let nft = frozen_nft(&mut svm, &user.pubkey(), &address);
+        set_account(&mut svm, address, data, nft_staking::ID);
+        stakes.push((nft, address));
+    }
+    let (first_mint, first_stake) = stakes[0];
+
+    // the same old data anywhere but the global config's stake PDA isn't an old stake
+    let copy = Pubkey::new_unique();
+    let old_data = svm.get_account(&first_stake).unwrap().data;
+    set_account(&mut svm, copy, old_data, nft_staking::ID);
+    assert_stake_error(
+        send(&mut svm, migrate_stake_account_instruction(&user.pubkey(), &config, &first_mint, &copy), &[&user]),
+        StakeError::InvalidStakeAccount,
+    );
+
+    // stake accounts that are already current have nothing to migrate
+    let (second_mint, second_stake) = stakes[1];
+    let current = Pubkey::new_unique();
+    let (_, bump) = stake_address(&second_mint, &legacy_config_address());
+    set_stake_account(&mut svm, current, &user.pubkey(), &second_mint, bump, staked_at);
+    assert_stake_error(
+        send(
+            &mut svm,
+            migrate_stake_account_instruction(&user.pubkey(), &config, &second_mint, &current),
+            &[&user],
+        ),
+        StakeError::InvalidStakeAccount,
+    );
+
+    // only the staker can migrate
+    let other = Keypair::new();
+    svm.airdrop(&other.pubkey(), 1_000_000_000).unwrap();
+    send(&mut svm, initialize_user_instruction(&other.pubkey(), &config), &[&other]).unwrap();
+    assert_stake_error(
+        send(
+            &mut svm,
+            migrate_stake_account_instruction(&other.pubkey(), &config, &second_mint, &second_stake),
+            &[&other],
+        ),
+        StakeError::NotOriginalStaker,
+    );
+
+    for (mint, legacy_stake) in &stakes {
+        send(
+            &mut svm,
+            migrate_stake_account_instruction(&user.pubkey(), &config, mint, legacy_stake),
+            &[&user],
+        )
+        .unwrap();
+
+        // the old stake account is gone and the NFT is frozen by this config's stake PDA now
+        assert!(svm.get_account(legacy_stake).is_none_or(|account| account.lamports == 0));
+        let nft = nft_account(&svm, &user.pubkey(), mint);
+        assert_eq!(nft.delegate, COption::Some(stake_address(mint, &config).0));
+        assert_eq!(nft.state, AccountState::Frozen);
+    }
+    assert_eq!(user_account(&svm, &config, &user.pubkey()).amount_staked, 3);
+
+    let migrated: Vec<Pubkey> = stakes.iter().map(|(mint, _)| stake_address(mint, &config).0).collect();
+    let oldest = stake_account(&svm, &migrated[0]);
+    assert_eq!((oldest.accumulated_rewards, oldest.last_claimed, oldest.staked_at), (0, staked_at, staked_at));
+    assert_eq!((oldest.points_per_stake, oldest.multiplier_bps), (POINTS_PER_STAKE, BASE_MULTIPLIER_BPS));
+    assert_eq!(oldest.bump, stake_address(&first_mint, &config).1);
+    assert!(oldest.standard == NftStandard::Legacy);
+
+    // the old point-seconds are scaled to multiplier bps, so they're still half a point
+    let with_accrual = stake_account(&svm, &migrated[1]);
+    assert_eq!(with_accrual.accumulated_rewards, half_point_seconds * BASE_MULTIPLIER_BPS as u64);
+    assert_eq!(with_accrual.points_per_stake, POINTS_PER_STAKE);
+
+    let with_rates = stake_account(&svm, &migrated[2]);
+    assert_eq!((with_rates.points_per_stake, with_rates.multiplier_bps), (1, 15_000));
+
+    // migrated stakes claim like any other: a day is 2 points, 2 (+ the half, which carries over) and 2 (1.5 at 1.5x + the half)
+    warp_by(&mut svm, ONE_DAY);
+    send(&mut svm, claim_instruction(&user.pubkey(), &config, &migrated), &[&user]).unwrap();
+    assert_eq!(rewards_balance(&svm, &config, &user.pubkey()), 6 * ONE_POINT);
+
+    // and unstake like any other, the NFT comes back thawed with no delegate left
+    for (mint, _) in &stakes {
+        send(&mut svm, unstake_instruction(&user.pubkey(), &config, mint), &[&user]).unwrap();
+
+        let nft = nft_account(&svm, &user.pubkey(), mint);
+        assert_eq!((nft.state, nft.delegate, nft.amount), (AccountState::Initialized, COption::None, 1));
+        assert!(svm.get_account(&stake_address(mint, &config).0).is_none_or(|account| account.lamports == 0));
+    }
+    assert_eq!(user_account(&svm, &config, &user.pubkey()).amount_staked, 0);
+}